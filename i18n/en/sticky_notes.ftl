@@ -3,13 +3,36 @@ page-id = Page { $num }
 data = Data
 load = Load
 save = Save
+revert = Revert changes
+revert-confirm = All changes made since the notes were saved will be lost.
+revert-confirm-button = Discard changes and reload
 import = Import
+merge = Merge import
 export = Export
+import-from = Import from...
+export-to = Export to...
+import-from-title = Import notes from indicator-stickynotes file
+import-keep = Import from Google Keep...
+import-keep-title = Choose Google Keep folder of Google Takeout
+validate-file = Validate file...
+validate-file-title = Choose Sticky Notes JSON file to validate
+validation-title = Notes file validation
+validation-counts = Notes: { $notes }, styles: { $styles }
+validation-no-issues = No issues found, the file can be imported as is
+validation-missing-default-style = The default style is missing, another style becomes the default one on import
+validation-unknown-style = Note { $note } refers to the missing style { $style }, it gets the default style on import
+validation-failed = The file cannot be read: { $error }
+export-to-title = Export notes to file
+export-format-indicator-stickynotes = indicator-stickynotes
+export-format-native-json = Sticky Notes JSON
+export-format-markdown = Markdown
+export-format-plain-text = Plain text
 view = View
 about = About...
 about-title = Application information
 about-author = Alexander Avramenko
 about-comments = The implementation of sticky notes for Cosmic desktop environment
+about-back = Back to menu
 about-copyright = Ⓒ Alexander Avramenko (github.com/0xAAE)
 project-repo = Project on GitHub
 author-profile = Author on GitHub
@@ -21,25 +44,128 @@ developers = Developers
 hide-all = Hide all
 show-all = Show all
 lock-all = Lock all
+unlock-all = Unlock all
+stop-click-through = Make notes clickable
 settings = Settings...
 settings-title = Settings
 quit = Quit
+reminder-hint = Remind at
+clear-history = Forget edits
+reminder-invalid = The reminder is invalid, the expected format is YYYY-MM-DD HH:MM
+reminder-title = Sticky note reminder
+notes-limit-title = Too many sticky notes shown
+notes-limit-hidden = The oldest notes are hidden: { $titles }
+notes-limit-untitled = (untitled)
+save-failed-title = Failed saving sticky notes
+save-failed = Notes are not saved: { $error }
+export-unwritable = Notes can't be exported: { $reason }
+find-replace = Replace...
+find-hint = Find
+replace-hint = Replace with
+match-case = Match case
+replace-all = Replace all
+attachment-hint = File path or URL to attach
+attach = Attach
+menu-section-notes = Notes
+menu-section-file = File
+menu-section-view = View
+menu-section-app = Application
+start-service = Start notes service
+starting-service = Starting notes service…
+service-started = Sticky Notes service started
+service-started-body = The requested action will be done as soon as the service is ready
 restore-notes = Restore note...
+show-hidden = Show hidden note...
+overview = Overview...
+new-from-template = New from template
+new-note = New note
+paste-note = Paste shared note
+paste-note-failed = Failed pasting the shared note
+copy-as-json = Copy as JSON
+overview-title = Notes overview
+overview-description = Click any note to bring it to front
+overview-select = Select
+export-selected = Export selected
+lock-selected = Lock selected
+unlock-selected = Unlock selected
+style-selected = Set style of selected...
+delete-selected = Delete selected
 recently-deleted-title = Recently deleted notes
 recently-deleted-description = To restore any note press button on the right
+recently-deleted-empty = There are no deleted notes to restore
+hidden-notes-title = Hidden notes
+hidden-notes-description = To show any hidden note press button on the right
+note-deleted = Deleted "{ $title }"
+undo = Undo
 styles-list-description = Currently available note styles
+style-group-header = { $group } ({ $count })
 select-default-style = New sticky window style:
+choose-style = Choose a style...
+select-screen-default-style = New sticky window style on the screen { $screen }:
+screen-default-style-global = Same as on all screens
+reset-screen-default-style = Reset
 create-new-style = Add new style
+high-contrast = High contrast notes
+reassign-style-target = Move notes to...
+reassign-style = Reassign
+style-used-by = (used by { $count ->
+    [one] 1 note
+   *[other] { $count } notes
+})
+style-unused = (unused)
+delete-style-title = Delete style "{ $name }"?
+delete-style-confirm = { $count ->
+    [one] 1 note uses this style, it will be reassigned to the default style.
+   *[other] { $count } notes use this style, they will be reassigned to the default style.
+}
+delete-style-ok = Delete
+gc-styles = Remove unused styles
+gc-styles-title = Remove unused styles?
+gc-styles-confirm = { $count ->
+    [one] 1 style is not used by any note or template: { $names }
+   *[other] { $count } styles are not used by any note or template: { $names }
+}
+stats-title = Statistics
+stats-notes = Notes: { $count }, deleted: { $deleted }
+stats-styles = Styles: { $count }
+stats-chars = Characters in notes: { $count }
+stats-modified = Modified from { $oldest } to { $newest }
+templates-title = Templates
+templates-description = New notes are created from templates via the panel menu, {"{"}date{"}"} and {"{"}time{"}"} in content are replaced with the current date and time
+create-new-template = Add new template
+new-template-name = Template { $index }
+template-name-hint = Template name
+template-content-hint = Initial content of the note
+template-style = Style of the note
 new-style-name = style
+style-copy-name = { $name } (copy)
 repository = Repository
 git-description = Git commit {$hash} on {$date}
 problem-text = Internal error: see errors in terminal or system log for details
+problem-title = The note can't be shown
+problem-note-missing = The note of this window is missing in the collection
+problem-style-missing = The style of this note is missing, it might have been deleted while the note was open
+problem-reset-style = Use the default style
+headless-export-failed = Export failed: { $error }
 edit-style-title = Edit selected style
 edit-style-comment = You can rename style and adjust background color, font style and font size.
 edit-style-name = Style name
+edit-style-group = Group
+edit-style-group-hint = no group
+edit-style-bg-image = Background image
+edit-style-corner-radius = Corner radius
+edit-style-corner-radius-theme = As in theme
+edit-style-border = Border width and color
+edit-style-bg-image-hint = path to svg or png, the color is used if not set
 edit-style-font = Font
 edit-style-font-size = size
+edit-style-font-family = Font family
+edit-style-font-family-hint = built-in font of the selected style
 edit-style-font-sample = Note text example
+edit-style-text-align = Text alignment
+edit-style-text-align-start = Start
+edit-style-text-align-center = Center
+edit-style-text-align-end = End
 edit-style-hex = HEX
 edit-style-rgb = RGB
 edit-style-bg = Background
@@ -50,3 +176,4 @@ edit-style-bg-copied = Color was copied to clipboard
 edit-style-ok = OK
 edit-style-cancel = Cancel
 view-style-font = font
+note-timestamps = created {$created}, modified {$modified}
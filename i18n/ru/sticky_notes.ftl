@@ -3,13 +3,36 @@ page-id = Вкладка { $num }
 data = Данные
 load = Загрузить
 save = Сохранить
+revert = Отменить изменения
+revert-confirm = Все изменения после последнего сохранения заметок будут потеряны.
+revert-confirm-button = Отменить изменения и перезагрузить
 import = Импорт
+merge = Импорт с объединением
 export = Экспорт
+import-from = Импорт из...
+export-to = Экспорт в...
+import-from-title = Импорт заметок из файла indicator-stickynotes
+import-keep = Импорт из Google Keep...
+import-keep-title = Выберите папку Google Keep из Google Takeout
+validate-file = Проверить файл...
+validate-file-title = Выберите файл заметок Sticky Notes JSON для проверки
+validation-title = Проверка файла заметок
+validation-counts = Заметок: { $notes }, стилей: { $styles }
+validation-no-issues = Ошибок не найдено, файл можно импортировать как есть
+validation-missing-default-style = Стиль по умолчанию отсутствует, при импорте стилем по умолчанию станет другой стиль
+validation-unknown-style = Заметка { $note } ссылается на отсутствующий стиль { $style }, при импорте ей будет назначен стиль по умолчанию
+validation-failed = Не удалось прочитать файл: { $error }
+export-to-title = Экспорт заметок в файл
+export-format-indicator-stickynotes = indicator-stickynotes
+export-format-native-json = Sticky Notes JSON
+export-format-markdown = Markdown
+export-format-plain-text = Простой текст
 view = Вид
 about = Информация...
 about-title = Информация о приложении
 about-author = Александр Авраменко (https://github.com/0xAAE)
 about-comments = Реализация заметок на стикерах для рабочего стола Cosmic
+about-back = Вернуться в меню
 about-copyright = Ⓒ Александр Авраменко (github.com/0xAAE)
 project-repo = Проект на GitHub
 author-profile = Автор на GitHub
@@ -21,25 +44,119 @@ developers = Разработчики
 hide-all = Скрыть все
 show-all = Показать все
 lock-all = Заблокировать все
+unlock-all = Разблокировать все
+stop-click-through = Сделать заметки кликабельными
 settings = Настройки...
 settings-title = Настройки
+service-started = Служба заметок запущена
+service-started-body = Выбранное действие будет выполнено, как только служба будет готова
+menu-section-notes = Заметки
+menu-section-file = Файл
+menu-section-view = Просмотр
+menu-section-app = Приложение
+start-service = Запустить службу заметок
+starting-service = Служба заметок запускается…
+reminder-hint = Напомнить
+clear-history = Забыть правки
+reminder-invalid = Неверное напоминание, ожидаемый формат ГГГГ-ММ-ДД ЧЧ:ММ
+reminder-title = Напоминание заметки
+notes-limit-title = Показано слишком много заметок
+notes-limit-hidden = Самые старые заметки скрыты: { $titles }
+notes-limit-untitled = (без заголовка)
+save-failed-title = Не удалось сохранить заметки
+save-failed = Заметки не сохранены: { $error }
+export-unwritable = Экспорт заметок невозможен: { $reason }
+find-replace = Заменить...
+find-hint = Найти
+replace-hint = Заменить на
+match-case = Учитывать регистр
+replace-all = Заменить все
+attachment-hint = Путь к файлу или ссылка
+attach = Прикрепить
 quit = Завершить работу
+overview = Обзор...
+new-from-template = Создать по шаблону
+new-note = Новая заметка
+paste-note = Вставить заметку
+paste-note-failed = Не удалось вставить заметку
+copy-as-json = Копировать как JSON
+overview-title = Обзор заметок
+overview-description = Нажмите на заметку, чтобы показать её поверх остальных
+overview-select = Выбрать
+export-selected = Экспортировать выбранные
+lock-selected = Заблокировать выбранные
+unlock-selected = Разблокировать выбранные
+style-selected = Задать стиль выбранным...
+delete-selected = Удалить выбранные
 restore-notes = Восстановить заметку...
+show-hidden = Показать скрытую заметку...
 recently-deleted-title = Недавно удаленные заметки
 recently-deleted-description = Чтобы восстановить ранее удаленную заметку, найдите ее и нажмите кнопку справа
+recently-deleted-empty = Нет удаленных заметок для восстановления
+hidden-notes-title = Скрытые заметки
+hidden-notes-description = Чтобы снова показать скрытую заметку, нажмите кнопку справа
+note-deleted = Удалена "{ $title }"
+undo = Отменить
 styles-list-description = Стили отрисовки заметок
+style-group-header = { $group } ({ $count })
 select-default-style = Стиль для новых заметок:
+choose-style = Выберите стиль...
+select-screen-default-style = Стиль для новых заметок на экране { $screen }:
+screen-default-style-global = Как на всех экранах
+reset-screen-default-style = Сбросить
 create-new-style = Добавить новый стиль
+high-contrast = Контрастные заметки
+reassign-style-target = Переместить заметки в...
+reassign-style = Переназначить
+style-used-by = (заметок: { $count })
+style-unused = (не используется)
+delete-style-title = Удалить стиль "{ $name }"?
+delete-style-confirm = Заметок с этим стилем: { $count }, им будет назначен стиль по умолчанию.
+delete-style-ok = Удалить
+gc-styles = Удалить неиспользуемые стили
+gc-styles-title = Удалить неиспользуемые стили?
+gc-styles-confirm = Стили, которые не используются ни заметками, ни шаблонами ({ $count }): { $names }
+stats-title = Статистика
+stats-notes = Заметок: { $count }, удалено: { $deleted }
+stats-styles = Стилей: { $count }
+stats-chars = Символов в заметках: { $count }
+stats-modified = Изменены с { $oldest } по { $newest }
+templates-title = Шаблоны
+templates-description = Заметки по шаблону создаются через меню на панели, {"{"}date{"}"} и {"{"}time{"}"} в тексте заменяются на текущие дату и время
+create-new-template = Добавить шаблон
+new-template-name = Шаблон { $index }
+template-name-hint = Название шаблона
+template-content-hint = Начальный текст заметки
+template-style = Стиль заметки
 new-style-name = Стиль 
+style-copy-name = { $name } (копия)
 repository = Репозиторий
 git-description = Git commit {$hash} от {$date}
 problem-text = Внутренняя ошибка: см. сообщения об  ошибках в терминале или в системном журнале
+problem-title = Заметку невозможно показать
+problem-note-missing = Заметка этого окна отсутствует в коллекции
+problem-style-missing = Стиль этой заметки отсутствует, возможно, он был удалён, пока заметка была открыта
+problem-reset-style = Применить стиль по умолчанию
+headless-export-failed = Ошибка экспорта: { $error }
 edit-style-title = Настроить выбранный стиль
 edit-style-comment = Можно переименовать стиль, настроить цвет фона, а также стиль и размер шрифта.
 edit-style-name = Название
+edit-style-group = Группа
+edit-style-group-hint = без группы
+edit-style-bg-image = Фоновое изображение
+edit-style-corner-radius = Радиус углов
+edit-style-corner-radius-theme = Как в теме
+edit-style-border = Толщина и цвет рамки
+edit-style-bg-image-hint = путь к svg или png, без него используется цвет
 edit-style-font = Шрифт
 edit-style-font-size = размер
+edit-style-font-family = Семейство шрифтов
+edit-style-font-family-hint = встроенный шрифт выбранного стиля
 edit-style-font-sample = Пример текста заметки
+edit-style-text-align = Выравнивание текста
+edit-style-text-align-start = По началу
+edit-style-text-align-center = По центру
+edit-style-text-align-end = По концу
 edit-style-hex = HEX
 edit-style-rgb = RGB
 edit-style-bg = Фон заметки
@@ -50,3 +167,4 @@ edit-style-bg-copied = Цвет скопирован в буфер обмена
 edit-style-ok = OK
 edit-style-cancel = Отмена
 view-style-font = шрифт
+note-timestamps = создана {$created}, изменена {$modified}
@@ -1,40 +1,83 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use crate::notes::ExportFormat;
 use std::str::FromStr;
 use thiserror::Error;
 pub use {
-    applet::AppletModel,
-    service::{ServiceFlags, ServiceModel},
+    applet::{AppletFlags, AppletModel},
+    headless::try_run_headless_export,
+    service::{ServiceFlags, ServiceModel, exit_code},
     utils::to_f32,
 };
 
 mod about_window;
 mod applet;
 mod edit_style;
+mod headless;
+mod hidden_view;
+mod notification;
+mod overview_view;
 mod restore_view;
 mod service;
 mod settings_view;
+mod spellcheck;
 mod sticky_window;
 mod styles_view;
+mod templates_view;
+mod undo_toast_view;
 mod utils;
+mod validation_view;
+mod window_registry;
 
+// libcosmic requires the app id to be a constant, it names the application (e.g. its icon),
+// while the config, the notes storage and the DBus name follow the app id of the instance, see app_id()
 const APP_ID: &str = "com.github.aae.sticky_notes";
+const APP_ID_ARG: &str = "--app-id";
+const APP_ID_VAR: &str = "STICKY_NOTES_APP_ID";
+
+/// The app id of the instance: `--app-id <id>` argument, `STICKY_NOTES_APP_ID` environment variable or the default one.
+/// Another instance (e.g. "work profile") has its own notes, config and DBus name
+#[must_use]
+pub fn app_id(args: &[String]) -> &'static str {
+    args.iter()
+        .position(|arg| arg == APP_ID_ARG)
+        .and_then(|index| args.get(index + 1))
+        .cloned()
+        .or_else(|| std::env::var(APP_ID_VAR).ok())
+        .filter(|app_id| !app_id.is_empty() && app_id != APP_ID)
+        // the only app id of the process lives as long as the process
+        .map_or(APP_ID, |app_id| Box::leak(app_id.into_boxed_str()))
+}
 
 /// Messages emitted by the application and its widgets.
 #[derive(Debug, Clone)]
 pub enum Command {
     Ping,
     Quit,
+    NewNote,
+    NewNoteAtCursor,
+    NewNoteFromTemplate(usize), // template index
     LoadNotes,
+    RevertNotes,
     SaveNotes,
     ImportNotes,
+    MergeNotes,
     ExportNotes,
+    PickImportFile,
+    PickKeepImport,
+    PickValidateFile,
+    PickExportFile(ExportFormat),
     ShowAllNotes,
     HideAllNotes,
     LockAll,
+    UnlockAll,
+    StopClickThrough, // locked notes stay locked, but intercept clicks again
     RestoreNotes,
+    ShowHiddenNotes,
+    OpenOverview,
     OpenSettings,
     OpenAbout,
+    PasteNote,
 }
 
 #[derive(Debug, Error)]
@@ -46,35 +89,74 @@ pub enum NotesAppError {
 
 const PING: &str = "PING";
 const QUIT: &str = "QUIT";
+const NEW: &str = "NEW";
+const NEW_AT_CURSOR: &str = "NEW_AT_CURSOR";
+const NEW_FROM_TEMPLATE: &str = "NEW_FROM_TEMPLATE";
 const LOAD: &str = "LOAD";
+const REVERT: &str = "REVERT";
 const SAVE: &str = "SAVE";
 const IMPORT: &str = "IMPORT";
+const MERGE: &str = "MERGE";
 const EXPORT: &str = "EXPORT";
+const IMPORT_FROM: &str = "IMPORT_FROM";
+const EXPORT_TO: &str = "EXPORT_TO";
+const IMPORT_KEEP: &str = "IMPORT_KEEP";
+const VALIDATE: &str = "VALIDATE";
 const SHOW: &str = "SHOW";
 const HIDE: &str = "HIDE";
 const LOCK: &str = "LOCK";
+const UNLOCK: &str = "UNLOCK";
+const CLICKABLE: &str = "CLICKABLE";
 const RESTORE: &str = "RESTORE";
+const HIDDEN: &str = "HIDDEN";
+const OVERVIEW: &str = "OVERVIEW";
 const SETTINGS: &str = "SETTINGS";
 const ABOUT: &str = "ABOUT";
+const PASTE_NOTE: &str = "PASTE_NOTE";
+// separates the command from its argument, e.g. EXPORT_TO:MARKDOWN or NEW_FROM_TEMPLATE:0
+const ARG_SEPARATOR: char = ':';
 
 impl std::fmt::Display for Command {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the default export format is implied to stay compatible with earlier versions
+        if let Command::PickExportFile(format) = self
+            && *format != ExportFormat::default()
+        {
+            return write!(f, "{EXPORT_TO}{ARG_SEPARATOR}{format}");
+        }
+        if let Command::NewNoteFromTemplate(index) = self {
+            return write!(f, "{NEW_FROM_TEMPLATE}{ARG_SEPARATOR}{index}");
+        }
         write!(
             f,
             "{}",
             match self {
                 Command::Ping => PING,
                 Command::Quit => QUIT,
+                Command::NewNote => NEW,
+                Command::NewNoteAtCursor => NEW_AT_CURSOR,
+                Command::NewNoteFromTemplate(_) => NEW_FROM_TEMPLATE,
                 Command::LoadNotes => LOAD,
+                Command::RevertNotes => REVERT,
                 Command::SaveNotes => SAVE,
                 Command::ImportNotes => IMPORT,
+                Command::MergeNotes => MERGE,
                 Command::ExportNotes => EXPORT,
+                Command::PickImportFile => IMPORT_FROM,
+                Command::PickKeepImport => IMPORT_KEEP,
+                Command::PickValidateFile => VALIDATE,
+                Command::PickExportFile(_) => EXPORT_TO,
                 Command::ShowAllNotes => SHOW,
                 Command::HideAllNotes => HIDE,
                 Command::LockAll => LOCK,
+                Command::UnlockAll => UNLOCK,
+                Command::StopClickThrough => CLICKABLE,
                 Command::RestoreNotes => RESTORE,
+                Command::ShowHiddenNotes => HIDDEN,
+                Command::OpenOverview => OVERVIEW,
                 Command::OpenSettings => SETTINGS,
                 Command::OpenAbout => ABOUT,
+                Command::PasteNote => PASTE_NOTE,
             }
         )
     }
@@ -84,19 +166,44 @@ impl FromStr for Command {
     type Err = NotesAppError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((EXPORT_TO, format)) = s.split_once(ARG_SEPARATOR) {
+            return format
+                .parse()
+                .map(Self::PickExportFile)
+                .map_err(|_| NotesAppError::ParseError(s.to_string()));
+        }
+        if let Some((NEW_FROM_TEMPLATE, index)) = s.split_once(ARG_SEPARATOR) {
+            return index
+                .parse()
+                .map(Self::NewNoteFromTemplate)
+                .map_err(|_| NotesAppError::ParseError(s.to_string()));
+        }
         match s {
             PING => Ok(Self::Ping),
             QUIT => Ok(Self::Quit),
+            NEW => Ok(Self::NewNote),
+            NEW_AT_CURSOR => Ok(Self::NewNoteAtCursor),
             LOAD => Ok(Self::LoadNotes),
+            REVERT => Ok(Self::RevertNotes),
             SAVE => Ok(Self::SaveNotes),
             IMPORT => Ok(Self::ImportNotes),
+            MERGE => Ok(Self::MergeNotes),
             EXPORT => Ok(Self::ExportNotes),
+            IMPORT_FROM => Ok(Self::PickImportFile),
+            IMPORT_KEEP => Ok(Self::PickKeepImport),
+            VALIDATE => Ok(Self::PickValidateFile),
+            EXPORT_TO => Ok(Self::PickExportFile(ExportFormat::default())),
             SHOW => Ok(Self::ShowAllNotes),
             HIDE => Ok(Self::HideAllNotes),
             LOCK => Ok(Self::LockAll),
+            UNLOCK => Ok(Self::UnlockAll),
+            CLICKABLE => Ok(Self::StopClickThrough),
             RESTORE => Ok(Self::RestoreNotes),
+            HIDDEN => Ok(Self::ShowHiddenNotes),
+            OVERVIEW => Ok(Self::OpenOverview),
             SETTINGS => Ok(Self::OpenSettings),
             ABOUT => Ok(Self::OpenAbout),
+            PASTE_NOTE => Ok(Self::PasteNote),
             _ => Err(NotesAppError::ParseError(s.to_string())),
         }
     }
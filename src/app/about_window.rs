@@ -37,6 +37,11 @@ impl AboutWindow {
         }
     }
 
+    /// The applet shows the same information while the service is unavailable
+    pub fn about(&self) -> &About {
+        &self.about
+    }
+
     pub fn build_view(&self) -> Element<'_, Message> {
         widget::container(widget::about(&self.about, |url| {
             Message::OpenUrl(url.to_string())
@@ -1,6 +1,13 @@
-use std::collections::HashMap;
-
-use crate::{app::Command, config::Config, fl, icons};
+use std::{collections::HashMap, time::Duration};
+
+use super::{APP_ID_VAR, about_window::AboutWindow, notification::send_notification};
+use crate::{
+    app::Command,
+    config::{Config, MenuSection},
+    fl, icons,
+    notes::ExportFormat,
+    state::State,
+};
 use cosmic::prelude::*;
 use cosmic::{
     applet,
@@ -8,34 +15,72 @@ use cosmic::{
     dbus_activation::DbusActivationInterfaceProxy,
     desktop,
     iced::{
-        self, Alignment, Limits, Subscription,
+        self, Alignment, Length, Limits, Subscription,
         widget::column,
         window::{self, Id},
     },
     widget,
 };
 
+// how many times to ping the launched service before giving up
+const SERVICE_START_ATTEMPTS: u32 = 10;
+// the delay before each ping of the launched service
+const SERVICE_START_DELAY: Duration = Duration::from_millis(500);
+// how often to test the service is still alive
+const HEARTBEAT_PERIOD: Duration = Duration::from_secs(5);
+
 /// Messages emitted by the application and its widgets.
 #[derive(Debug, Clone)]
 pub enum Message {
     UpdateConfig(Config),
+    UpdateState(State),
     TogglePopup,
     ClosePopupIfOpen,
+    ToggleExportFormats,            // show / hide formats to export to
+    ToggleRevertConfirm,            // ask / stop asking to confirm reverting unsaved changes
+    ToggleTemplates,                // show / hide templates to create new note from
+    ToggleAbout,                    // show / hide about while the service is down
+    ToggleMenuSection(MenuSection), // collapse / expand the section of popup
+    OpenUrl(String),
     Signal(Command),
     SignalResult(Command, bool), // (command, success or not)
     ZbusConnection(zbus::Result<zbus::Connection>),
     DbusProxy(zbus::Result<DbusActivationInterfaceProxy<'static>>),
+    Heartbeat,
+    HeartbeatResult(bool), // service is alive or not
+}
+
+pub struct AppletFlags {
+    /// The app id of the instance to talk to, see `app_id()`
+    pub app_id: &'static str,
 }
 
 pub struct AppletModel {
     // Application state which is managed by the COSMIC runtime.
     core: cosmic::Core,
+    // The app id of the instance, see AppletFlags::app_id
+    app_id: &'static str,
     // Configuration data that persists between application runs.
     config: Config,
+    // The state of notes published by the service
+    state: State,
     main_popup_id: Option<Id>,
     zbus_connection: Option<zbus::Connection>,
     dbus_proxy: Option<DbusActivationInterfaceProxy<'static>>,
     dbus_object_path: String,
+    // the service has responded recently
+    service_alive: bool,
+    // the formats submenu of "export to" is shown
+    export_formats_expanded: bool,
+    // reverting unsaved changes waits for the confirmation
+    revert_confirm: bool,
+    // the templates submenu of "new from template" is shown
+    templates_expanded: bool,
+    // the popup shows the information about application instead of menu
+    about_expanded: bool,
+    // a command is sent to the unreachable service, so the service is being launched
+    service_starting: bool,
+    about: AboutWindow,
     #[cfg(not(feature = "xdg_icons"))]
     icons: icons::IconSet,
     #[cfg(feature = "xdg_icons")]
@@ -48,7 +93,7 @@ impl cosmic::Application for AppletModel {
     type Executor = cosmic::executor::Default;
 
     /// Data that your application receives to its init method.
-    type Flags = ();
+    type Flags = AppletFlags;
 
     /// Messages which the application and its widgets will emit.
     type Message = Message;
@@ -65,12 +110,10 @@ impl cosmic::Application for AppletModel {
     }
 
     /// Initializes the application with any given flags and startup commands.
-    fn init(
-        core: cosmic::Core,
-        _flags: Self::Flags,
-    ) -> (Self, Task<cosmic::Action<Self::Message>>) {
+    fn init(core: cosmic::Core, flags: Self::Flags) -> (Self, Task<cosmic::Action<Self::Message>>) {
         // Load config
-        let config = cosmic_config::Config::new(Self::APP_ID, Config::VERSION)
+        let app_id = flags.app_id;
+        let config = cosmic_config::Config::new(app_id, Config::VERSION)
             .map(|context| match Config::get_entry(&context) {
                 Ok(config) => config,
                 Err((errors, config)) => {
@@ -82,15 +125,38 @@ impl cosmic::Application for AppletModel {
             })
             .unwrap_or_default();
 
+        // Load the state of notes recently published by the service
+        let state = cosmic_config::Config::new_state(app_id, State::VERSION)
+            .map(|context| match State::get_entry(&context) {
+                Ok(state) => state,
+                Err((errors, state)) => {
+                    for why in errors {
+                        tracing::error!("error loading notes state: {why}");
+                    }
+                    state
+                }
+            })
+            .unwrap_or_default();
+
+        let icons = icons::IconSet::new();
         // Construct the app model with the runtime's core.
         let app = Self {
             core,
+            app_id,
             config,
+            state,
             zbus_connection: None,
             dbus_proxy: None,
-            dbus_object_path: format!("/{}", Self::APP_ID.replace('.', "/")),
+            dbus_object_path: format!("/{}", app_id.replace('.', "/")),
+            service_alive: false,
+            export_formats_expanded: false,
+            revert_confirm: false,
+            templates_expanded: false,
+            about_expanded: false,
+            service_starting: false,
             main_popup_id: None,
-            icons: icons::IconSet::new(),
+            about: AboutWindow::new(&icons),
+            icons,
         };
 
         let zbus_session_cmd = Task::perform(zbus::Connection::session(), |res| {
@@ -105,11 +171,26 @@ impl cosmic::Application for AppletModel {
     /// Application events will be processed through the view. Any messages emitted by
     /// events received by widgets will be passed to the update method.
     fn view(&self) -> Element<'_, Self::Message> {
-        self.core
+        let button = self
+            .core
             .applet
-            .icon_button_from_handle(self.icons.notes())
-            .on_press_down(Message::TogglePopup)
-            .into()
+            .icon_button_from_handle(
+                if self.service_alive && self.state.save_failed().is_none() {
+                    self.icons.notes()
+                } else {
+                    self.icons.notes_offline()
+                },
+            )
+            .on_press_down(Message::TogglePopup);
+        // the count of visible notes is only known while the service is alive
+        if self.service_alive && self.state.has_visible_notes() {
+            iced::widget::Stack::new()
+                .push(button)
+                .push(build_badge(self.state.visible_notes))
+                .into()
+        } else {
+            button.into()
+        }
     }
 
     /// Constructs views for other windows.
@@ -134,13 +215,22 @@ impl cosmic::Application for AppletModel {
         let subscriptions = vec![
             // Watch for application configuration changes.
             self.core()
-                .watch_config::<Config>(Self::APP_ID)
+                .watch_config::<Config>(self.app_id)
                 .map(|update| {
                     for e in update.errors {
                         tracing::error!("config error: {e}");
                     }
                     Message::UpdateConfig(update.config)
                 }),
+            // Watch for notes state changes published by the service.
+            self.core().watch_state::<State>(self.app_id).map(|update| {
+                for e in update.errors {
+                    tracing::error!("notes state error: {e}");
+                }
+                Message::UpdateState(update.config)
+            }),
+            // Test the service is alive periodically.
+            iced::time::every(HEARTBEAT_PERIOD).map(|_| Message::Heartbeat),
         ];
         Subscription::batch(subscriptions)
     }
@@ -156,10 +246,41 @@ impl cosmic::Application for AppletModel {
                 self.config = config;
             }
 
+            Message::UpdateState(state) => {
+                self.state = state;
+            }
+
             Message::ClosePopupIfOpen => {
                 return self.close_popup();
             }
 
+            Message::ToggleExportFormats => {
+                self.export_formats_expanded = !self.export_formats_expanded;
+            }
+
+            Message::ToggleRevertConfirm => {
+                self.revert_confirm = !self.revert_confirm;
+            }
+
+            Message::ToggleTemplates => {
+                self.templates_expanded = !self.templates_expanded;
+            }
+
+            Message::ToggleMenuSection(section) => {
+                if let Err(e) = self.save_menu_section_toggled(section) {
+                    tracing::error!("failed saving collapsed menu sections: {e}");
+                }
+            }
+
+            Message::ToggleAbout => {
+                self.about_expanded = !self.about_expanded;
+            }
+
+            Message::OpenUrl(url) => match open::that_detached(&url) {
+                Ok(()) => tracing::debug!("go to URL {url}"),
+                Err(err) => tracing::error!("failed to open {url:?}: {err}"),
+            },
+
             Message::TogglePopup => {
                 if self.main_popup_id.is_some() {
                     return self.close_popup();
@@ -186,19 +307,45 @@ impl cosmic::Application for AppletModel {
                     "successfully built dbus proxy client, testing service availability then"
                 );
                 self.dbus_proxy = Some(proxy);
-                // test service availability, this will try to launch service if unavailable:
-                return self.send_command_via_dbus(Command::Ping);
+                // enable popup actions as soon as the running service answers, not on the next heartbeat,
+                // then test service availability, this will try to launch service if unavailable:
+                return cosmic::task::batch([
+                    self.ping_service(),
+                    self.send_command_via_dbus(Command::Ping),
+                ]);
             }
 
             Message::Signal(command) => {
                 tracing::debug!("requested {command}");
+                // the command launches the unreachable service, see send_command_via_dbus()
+                if !self.service_alive {
+                    self.service_starting = true;
+                }
                 // toggle (actually close) Popup, then send signal via DBus
                 return Task::done(cosmic::Action::App(Message::TogglePopup))
                     .chain(self.send_command_via_dbus(command));
             }
 
+            Message::Heartbeat => {
+                return self.ping_service();
+            }
+
+            Message::HeartbeatResult(is_alive) => {
+                if is_alive != self.service_alive {
+                    if is_alive {
+                        tracing::info!("notes-service is available");
+                    } else {
+                        tracing::warn!("notes-service is unreachable");
+                    }
+                }
+                self.service_alive = is_alive;
+            }
+
             Message::SignalResult(command, success) => {
+                self.service_starting = false;
                 if success {
+                    // any command delivered proves the service is alive
+                    self.service_alive = true;
                     tracing::debug!("successfully sent {command}");
                 } else {
                     tracing::warn!("failed sending {command}");
@@ -219,6 +366,10 @@ impl cosmic::Application for AppletModel {
 
 impl AppletModel {
     fn close_popup(&mut self) -> Task<cosmic::Action<Message>> {
+        self.export_formats_expanded = false;
+        self.revert_confirm = false;
+        self.templates_expanded = false;
+        self.about_expanded = false;
         if let Some(p) = self.main_popup_id.take() {
             tracing::debug!("destroying popup menu");
             cosmic::iced::platform_specific::shell::commands::popup::destroy_popup(p)
@@ -247,52 +398,168 @@ impl AppletModel {
     }
 
     fn build_main_popup_view(&self) -> Element<'_, Message> {
-        let save_load = column![
-            applet::menu_button(widget::text::body(fl!("load")))
-                .on_press(Message::Signal(Command::LoadNotes)),
-            applet::menu_button(widget::text::body(fl!("save")))
-                .on_press(Message::Signal(Command::SaveNotes)),
-        ];
+        if self.about_expanded {
+            return self.build_about_popup_view();
+        }
+        // menu items having no effect are disabled, so are all of them while the service is unreachable
+        let alive = self.service_alive;
+        let import_avail = alive && !self.config.import_file.is_empty();
+        let show_all_avail = alive && self.state.has_hidden_notes();
+        let hide_all_avail = alive && self.state.has_visible_notes();
+        let lock_all_avail = alive && self.state.any_unlocked();
+        let unlock_all_avail = alive && self.state.any_locked();
+        let clickable_avail = alive && self.state.any_click_through();
+        let restore_avail = alive && self.state.has_deleted_notes();
+        let revert_avail = alive && self.state.is_unsaved();
+        let templates_avail = alive && !self.state.templates.is_empty();
+
+        let save_load =
+            column![
+                applet::menu_button(widget::text::body(fl!("load")))
+                    .on_press_maybe(alive.then_some(Message::Signal(Command::LoadNotes))),
+                applet::menu_button(widget::text::body(fl!("save")))
+                    .on_press_maybe(alive.then_some(Message::Signal(Command::SaveNotes))),
+                applet::menu_button(widget::text::body(fl!("revert")))
+                    .on_press_maybe(revert_avail.then_some(Message::ToggleRevertConfirm)),
+            ]
+            // unsaved changes are lost for good, so reverting is to be confirmed
+            .push_maybe((revert_avail && self.revert_confirm).then(|| {
+                column![
+                    applet::padded_control(widget::text::body(fl!("revert-confirm"))),
+                    applet::menu_button(
+                        widget::container(widget::text::body(fl!("revert-confirm-button")))
+                            .padding([0, 0, 0, cosmic::theme::spacing().space_m]),
+                    )
+                    .on_press(Message::Signal(Command::RevertNotes)),
+                ]
+            }));
+        // PING launches the service if it is unreachable, it is shown even if all sections are collapsed
+        let start_service = (!alive).then(|| {
+            if self.service_starting {
+                applet::padded_control(widget::text::body(fl!("starting-service"))).into()
+            } else {
+                Element::from(
+                    applet::menu_button(widget::text::body(fl!("start-service")))
+                        .on_press(Message::Signal(Command::Ping)),
+                )
+            }
+        });
+        let spacing = cosmic::theme::spacing();
         let import_export = column![
             applet::menu_button(widget::text::body(fl!("import")))
-                .on_press(Message::Signal(Command::ImportNotes)),
+                .on_press_maybe(import_avail.then_some(Message::Signal(Command::ImportNotes))),
+            applet::menu_button(widget::text::body(fl!("merge")))
+                .on_press_maybe(import_avail.then_some(Message::Signal(Command::MergeNotes))),
             applet::menu_button(widget::text::body(fl!("export")))
-                .on_press(Message::Signal(Command::ExportNotes)),
-        ];
+                .on_press_maybe(alive.then_some(Message::Signal(Command::ExportNotes))),
+            applet::menu_button(widget::text::body(fl!("import-from")))
+                .on_press_maybe(alive.then_some(Message::Signal(Command::PickImportFile))),
+            applet::menu_button(widget::text::body(fl!("import-keep")))
+                .on_press_maybe(alive.then_some(Message::Signal(Command::PickKeepImport))),
+            applet::menu_button(widget::text::body(fl!("validate-file")))
+                .on_press_maybe(alive.then_some(Message::Signal(Command::PickValidateFile))),
+            applet::menu_button(widget::text::body(fl!("export-to")))
+                .on_press_maybe(alive.then_some(Message::ToggleExportFormats)),
+        ]
+        .extend(
+            ExportFormat::ALL
+                .into_iter()
+                .filter(|_| alive && self.export_formats_expanded)
+                .map(|format| {
+                    applet::menu_button(
+                        widget::container(widget::text::body(export_format_name(format)))
+                            .padding([0, 0, 0, spacing.space_m]),
+                    )
+                    .on_press(Message::Signal(Command::PickExportFile(format)))
+                    .into()
+                }),
+        );
+        // the templates are listed right under "new from template" while it is expanded
+        let indent = [0, 0, 0, spacing.space_m];
+        let templates = widget::column::with_children(
+            self.state
+                .templates
+                .iter()
+                .enumerate()
+                .filter(|_| templates_avail && self.templates_expanded)
+                .map(|(index, name)| {
+                    applet::menu_button(widget::container(widget::text::body(name)).padding(indent))
+                        .on_press(Message::Signal(Command::NewNoteFromTemplate(index)))
+                        .into()
+                })
+                .collect(),
+        );
         let show_lock = column![
+            applet::menu_button(widget::text::body(fl!("new-note")))
+                .on_press_maybe(alive.then_some(Message::Signal(Command::NewNote))),
+            applet::menu_button(widget::text::body(fl!("new-from-template")))
+                .on_press_maybe(templates_avail.then_some(Message::ToggleTemplates)),
+            templates,
+            applet::menu_button(widget::text::body(fl!("paste-note")))
+                .on_press_maybe(alive.then_some(Message::Signal(Command::PasteNote))),
             applet::menu_button(widget::text::body(fl!("show-all")))
-                .on_press(Message::Signal(Command::ShowAllNotes)),
+                .on_press_maybe(show_all_avail.then_some(Message::Signal(Command::ShowAllNotes))),
             applet::menu_button(widget::text::body(fl!("hide-all")))
-                .on_press(Message::Signal(Command::HideAllNotes)),
+                .on_press_maybe(hide_all_avail.then_some(Message::Signal(Command::HideAllNotes))),
             applet::menu_button(widget::text::body(fl!("lock-all")))
-                .on_press(Message::Signal(Command::LockAll)),
+                .on_press_maybe(lock_all_avail.then_some(Message::Signal(Command::LockAll))),
+            applet::menu_button(widget::text::body(fl!("unlock-all")))
+                .on_press_maybe(unlock_all_avail.then_some(Message::Signal(Command::UnlockAll))),
+            applet::menu_button(widget::text::body(fl!("stop-click-through"))).on_press_maybe(
+                clickable_avail.then_some(Message::Signal(Command::StopClickThrough))
+            ),
         ];
-        let settings_restore = column![
+        let view = column![
+            applet::menu_button(widget::text::body(fl!("overview")))
+                .on_press_maybe(hide_all_avail.then_some(Message::Signal(Command::OpenOverview))),
             applet::menu_button(widget::text::body(fl!("restore-notes")))
-                .on_press(Message::Signal(Command::RestoreNotes)),
+                .on_press_maybe(restore_avail.then_some(Message::Signal(Command::RestoreNotes))),
+            applet::menu_button(widget::text::body(fl!("show-hidden"))).on_press_maybe(
+                show_all_avail.then_some(Message::Signal(Command::ShowHiddenNotes))
+            ),
+        ];
+        let app = column![
+            // the settings window is owned by the service, so the service is launched if unreachable
             applet::menu_button(widget::text::body(fl!("settings")))
                 .on_press(Message::Signal(Command::OpenSettings)),
-            applet::menu_button(widget::text::body(fl!("about")))
-                .on_press(Message::Signal(Command::OpenAbout)),
+            // the information about application is shown right in the popup if the service is unreachable
+            applet::menu_button(widget::text::body(fl!("about"))).on_press(if alive {
+                Message::Signal(Command::OpenAbout)
+            } else {
+                Message::ToggleAbout
+            }),
             applet::menu_button(widget::text::body(fl!("quit")))
                 .on_press(Message::Signal(Command::Quit)),
         ];
 
-        let spacing = cosmic::theme::spacing();
-        let content = column![
-            save_load,
-            applet::padded_control(widget::divider::horizontal::default())
-                .padding([spacing.space_xxs, spacing.space_s]),
-            import_export,
-            applet::padded_control(widget::divider::horizontal::default())
-                .padding([spacing.space_xxs, spacing.space_s]),
-            show_lock,
+        // keep the failure in sight until notes are saved successfully
+        let save_failed = self.state.save_failed().map(|error| {
+            applet::padded_control(widget::text::body(fl!("save-failed", error = error)))
+        });
+        let export_unwritable = self.state.export_unwritable().map(|reason| {
+            applet::padded_control(widget::text::body(fl!(
+                "export-unwritable",
+                reason = reason
+            )))
+        });
+        let divider = || {
             applet::padded_control(widget::divider::horizontal::default())
-                .padding([spacing.space_xxs, spacing.space_s]),
-            settings_restore
-        ]
-        .align_x(Alignment::Start)
-        .padding([8, 0]);
+                .padding([spacing.space_xxs, spacing.space_s])
+        };
+        let file = column![save_load, divider(), import_export];
+        let content = widget::column::with_capacity(10)
+            .push_maybe(start_service)
+            .push(self.build_menu_section(MenuSection::File, file))
+            .push(divider())
+            .push(self.build_menu_section(MenuSection::Notes, show_lock))
+            .push(divider())
+            .push(self.build_menu_section(MenuSection::View, view))
+            .push(divider())
+            .push(self.build_menu_section(MenuSection::App, app))
+            .push_maybe(save_failed)
+            .push_maybe(export_unwritable)
+            .align_x(Alignment::Start)
+            .padding([8, 0]);
 
         self.core
             .applet
@@ -302,15 +569,54 @@ impl AppletModel {
             .into()
     }
 
+    // the section title collapses or expands the items following it
+    fn build_menu_section<'a>(
+        &self,
+        section: MenuSection,
+        items: widget::Column<'a, Message>,
+    ) -> widget::Column<'a, Message> {
+        column![
+            applet::menu_button(widget::text::heading(menu_section_name(section)))
+                .on_press(Message::ToggleMenuSection(section))
+        ]
+        .push_maybe((!self.config.is_menu_section_collapsed(section)).then_some(items))
+    }
+
+    fn save_menu_section_toggled(
+        &mut self,
+        section: MenuSection,
+    ) -> Result<(), cosmic_config::Error> {
+        let global_config = cosmic_config::Config::new(self.app_id, Config::VERSION)?;
+        let collapsed = self.config.with_menu_section_toggled(section);
+        self.config
+            .set_collapsed_menu_sections(&global_config, collapsed)?;
+        Ok(())
+    }
+
+    fn build_about_popup_view(&self) -> Element<'_, Message> {
+        let content = column![
+            widget::about(self.about.about(), |url| Message::OpenUrl(url.to_string())),
+            applet::menu_button(widget::text::body(fl!("about-back")))
+                .on_press(Message::ToggleAbout),
+        ]
+        .padding([8, 0]);
+        self.core
+            .applet
+            .popup_container(widget::scrollable(content))
+            .max_height(500.)
+            .max_width(500.)
+            .into()
+    }
+
     fn try_build_dbus_proxy(&self) -> Task<cosmic::Action<Message>> {
         if let Some(zbus_conn) = self.zbus_connection.clone() {
             tracing::info!("try building proxy client");
             let path = self.dbus_object_path.clone();
             match DbusActivationInterfaceProxy::builder(&zbus_conn)
-                .destination(<Self as cosmic::Application>::APP_ID)
+                .destination(self.app_id)
                 //.ok()
                 .and_then(|b| b.path(path))
-                .and_then(|b| b.destination(<Self as cosmic::Application>::APP_ID))
+                .and_then(|b| b.destination(self.app_id))
             {
                 Ok(proxy_builder) => {
                     return Task::perform(async move { proxy_builder.build().await }, |res| {
@@ -325,32 +631,167 @@ impl AppletModel {
         Task::none()
     }
 
+    // unlike send_command_via_dbus() it never launches the service
+    fn ping_service(&self) -> Task<cosmic::Action<Message>> {
+        if let Some(mut proxy) = self.dbus_proxy.clone() {
+            let ping = Command::Ping.to_string();
+            return Task::future(async move {
+                let is_alive = proxy
+                    .activate_action(ping.as_str(), Vec::new(), HashMap::new())
+                    .await
+                    .is_ok();
+                cosmic::Action::App(Message::HeartbeatResult(is_alive))
+            });
+        }
+        Task::none()
+    }
+
     fn send_command_via_dbus(&self, command: Command) -> Task<cosmic::Action<Message>> {
         if let Some(mut proxy) = self.dbus_proxy.clone() {
             let command_str = command.to_string();
             let service_exec = self.config.service_bin.clone();
+            let app_id = self.app_id;
+            let notify_connection = self
+                .config
+                .notify_on_service_start
+                .then(|| self.zbus_connection.clone())
+                .flatten();
             return Task::future(async move {
-                if let Err(e) = proxy
+                match proxy
                     .activate_action(command_str.as_str(), Vec::new(), HashMap::new())
                     .await
                 {
-                    tracing::error!("failed sending {command_str}: {e}");
-                    //todo: test error before spawning service; valid candidates are: InterfaceNotFound, Failure(e)
-                    tracing::info!("trying to launch notes-service binary: {}", &service_exec);
-                    desktop::spawn_desktop_exec(
-                        service_exec.as_str(),
-                        Vec::<(String, String)>::new(),
-                        Some(<Self as cosmic::Application>::APP_ID),
-                        false,
-                    )
-                    .await;
-                    //todo: consider waiting for a while to prevent spamming with calls to spawn_desktop_exec(), then repeat command again
-                    cosmic::Action::App(Message::SignalResult(command, false))
-                } else {
-                    cosmic::Action::App(Message::SignalResult(command, true))
+                    Ok(()) => cosmic::Action::App(Message::SignalResult(command, true)),
+                    Err(e) if !is_service_missing(&e) => {
+                        // the service is running but failed handling the command, launching it again won't help
+                        tracing::error!("failed sending {command_str}: {e}");
+                        cosmic::Action::App(Message::SignalResult(command, false))
+                    }
+                    Err(e) => {
+                        tracing::info!("notes-service is not running ({e})");
+                        tracing::info!("trying to launch notes-service binary: {}", &service_exec);
+                        // the service of the same instance is launched
+                        desktop::spawn_desktop_exec(
+                            service_exec.as_str(),
+                            vec![(APP_ID_VAR.to_string(), app_id.to_string())],
+                            Some(app_id),
+                            false,
+                        )
+                        .await;
+                        if let Some(connection) = notify_connection
+                            && let Err(e) = send_notification(
+                                &connection,
+                                &fl!("service-started"),
+                                &fl!("service-started-body"),
+                            )
+                            .await
+                        {
+                            tracing::warn!("failed sending desktop notification: {e}");
+                        }
+                        // wait for the service is up, then repeat command again
+                        let is_sent = retry_command_via_dbus(&mut proxy, &command_str).await;
+                        cosmic::Action::App(Message::SignalResult(command, is_sent))
+                    }
                 }
             });
         }
         Task::none()
     }
 }
+
+// test if the error means there is no service to handle the command
+fn is_service_missing(error: &zbus::Error) -> bool {
+    const MISSING_SERVICE_ERRORS: [&str; 4] = [
+        "org.freedesktop.DBus.Error.ServiceUnknown",
+        "org.freedesktop.DBus.Error.NameHasNoOwner",
+        "org.freedesktop.DBus.Error.UnknownObject",
+        "org.freedesktop.DBus.Error.UnknownInterface",
+    ];
+    match error {
+        zbus::Error::InterfaceNotFound => true,
+        zbus::Error::MethodError(name, _, _) => MISSING_SERVICE_ERRORS.contains(&name.as_str()),
+        zbus::Error::FDO(fdo_error) => matches!(
+            **fdo_error,
+            zbus::fdo::Error::ServiceUnknown(_)
+                | zbus::fdo::Error::NameHasNoOwner(_)
+                | zbus::fdo::Error::UnknownObject(_)
+                | zbus::fdo::Error::UnknownInterface(_)
+        ),
+        _ => false,
+    }
+}
+
+// polls the just launched service with PING, then sends the command once the service responds
+async fn retry_command_via_dbus(
+    proxy: &mut DbusActivationInterfaceProxy<'static>,
+    command_str: &str,
+) -> bool {
+    let ping = Command::Ping.to_string();
+    for _ in 0..SERVICE_START_ATTEMPTS {
+        tokio::time::sleep(SERVICE_START_DELAY).await;
+        if proxy
+            .activate_action(ping.as_str(), Vec::new(), HashMap::new())
+            .await
+            .is_ok()
+        {
+            if command_str == ping {
+                return true;
+            }
+            return match proxy
+                .activate_action(command_str, Vec::new(), HashMap::new())
+                .await
+            {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::error!("failed resending {command_str}: {e}");
+                    false
+                }
+            };
+        }
+    }
+    tracing::error!("notes-service has not responded, {command_str} is dropped");
+    false
+}
+
+fn menu_section_name(section: MenuSection) -> String {
+    match section {
+        MenuSection::Notes => fl!("menu-section-notes"),
+        MenuSection::File => fl!("menu-section-file"),
+        MenuSection::View => fl!("menu-section-view"),
+        MenuSection::App => fl!("menu-section-app"),
+    }
+}
+
+fn export_format_name(format: ExportFormat) -> String {
+    match format {
+        ExportFormat::IndicatorStickyNotes => fl!("export-format-indicator-stickynotes"),
+        ExportFormat::NativeJson => fl!("export-format-native-json"),
+        ExportFormat::Markdown => fl!("export-format-markdown"),
+        ExportFormat::PlainText => fl!("export-format-plain-text"),
+    }
+}
+
+// the small counter at the bottom right corner of the applet icon
+fn build_badge<'a>(count: usize) -> Element<'a, Message> {
+    widget::container(
+        widget::container(widget::text::caption(count.to_string()))
+            .class(cosmic::style::Container::custom(|theme: &Theme| {
+                let cosmic = theme.cosmic();
+                iced::widget::container::Style {
+                    text_color: Some(cosmic.accent.on.into()),
+                    background: Some(iced::Background::Color(cosmic.accent_color().into())),
+                    border: iced::Border {
+                        radius: cosmic.corner_radii.radius_xl.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            }))
+            .padding([0, cosmic::theme::spacing().space_xxxs]),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .align_x(Alignment::End)
+    .align_y(Alignment::End)
+    .into()
+}
@@ -1,10 +1,10 @@
 use super::{
     service::Message,
-    utils::{cosmic_font, with_background},
+    utils::{cosmic_font, text_alignment, theme_corner_radius, with_background},
 };
 use crate::{
     fl,
-    notes::{Font, FontStyle, NoteStyle},
+    notes::{Font, FontStyle, NoteStyle, TextAlign},
 };
 use cosmic::prelude::*;
 use cosmic::{
@@ -12,30 +12,72 @@ use cosmic::{
     widget::{self, color_picker::ColorPickerUpdate},
 };
 use palette::FromColor;
+use std::ops::RangeInclusive;
 use uuid::Uuid;
 
-const MIN_FONT_SIZE: u16 = 6;
-const MAX_FONT_SIZE: u16 = 72;
+const RECENT_COLOR_SIZE: f32 = 24.0;
+const MAX_CORNER_RADIUS: u16 = 32;
+const MAX_BORDER_WIDTH: u16 = 8;
+// the order of text alignments in the dropdown
+const TEXT_ALIGNS: [TextAlign; 3] = [TextAlign::Start, TextAlign::Center, TextAlign::End];
 
 pub struct EditStyleDialog {
     style_id: Uuid,
+    // the style has been created for this dialog, so it is deleted unless Ok is pressed
+    is_new: bool,
     name: String,
+    group: String,
     font: Font,
     bgcolor: Color,
+    // the color of the style before editing, the style itself is changed on Ok only
+    original_bgcolor: Color,
+    text_align: TextAlign,
+    background_image: String,
+    // the theme radius is used if not set
+    corner_radius: Option<u16>,
+    corner_radius_text: String,
+    border_width: u16,
+    border_width_text: String,
+    // the last valid color typed in
+    border_color: Color,
+    border_color_text: String,
     color_picker_model: widget::ColorPickerModel,
     avail_fonts: Vec<String>,
     font_size_text: String,
+    font_sizes: RangeInclusive<u16>,
+    // shared by all styles, the most recent is the first one
+    recent_colors: Vec<Color>,
 }
 
 impl EditStyleDialog {
-    pub fn new(style_id: Uuid, style: &NoteStyle) -> Self {
+    pub fn new(
+        style_id: Uuid,
+        style: &NoteStyle,
+        is_new: bool,
+        font_sizes: RangeInclusive<u16>,
+        recent_colors: Vec<Color>,
+    ) -> Self {
         let font = style.get_font().clone();
         let font_size_text = font.size.to_string();
         Self {
             style_id,
+            is_new,
             name: style.get_name().to_string(),
+            group: style.get_group().unwrap_or_default().to_string(),
             font,
             bgcolor: style.get_background_color(),
+            original_bgcolor: style.get_background_color(),
+            text_align: style.get_text_align(),
+            background_image: style.get_background_image().unwrap_or_default().to_string(),
+            corner_radius: style.get_corner_radius(),
+            corner_radius_text: style
+                .get_corner_radius()
+                .unwrap_or_else(theme_corner_radius)
+                .to_string(),
+            border_width: style.get_border_width(),
+            border_width_text: style.get_border_width().to_string(),
+            border_color: style.get_border_color(),
+            border_color_text: to_hex(style.get_border_color()),
             color_picker_model: widget::ColorPickerModel::new(
                 fl!("edit-style-hex"),
                 fl!("edit-style-rgb"),
@@ -44,6 +86,8 @@ impl EditStyleDialog {
             ),
             avail_fonts: get_avail_fonts().iter().map(ToString::to_string).collect(),
             font_size_text,
+            font_sizes,
+            recent_colors,
         }
     }
 
@@ -51,23 +95,69 @@ impl EditStyleDialog {
         self.name = name;
     }
 
+    pub fn update_group(&mut self, group: String) {
+        self.group = group;
+    }
+
     pub fn update_font_style(&mut self, font_style: FontStyle) {
         self.font.style = font_style;
     }
 
+    pub fn update_font_family(&mut self, family: String) {
+        self.font.family = (!family.trim().is_empty()).then_some(family);
+    }
+
     pub fn update_font_size(&mut self, font_size: u16) {
+        let font_size = font_size.clamp(*self.font_sizes.start(), *self.font_sizes.end());
         self.font.size = font_size;
         self.font_size_text = font_size.to_string();
     }
 
+    pub fn update_text_align(&mut self, text_align: TextAlign) {
+        self.text_align = text_align;
+    }
+
+    pub fn update_background_image(&mut self, path: String) {
+        self.background_image = path;
+    }
+
+    pub fn update_corner_radius(&mut self, corner_radius: Option<u16>) {
+        self.corner_radius = corner_radius.map(|radius| radius.min(MAX_CORNER_RADIUS));
+        self.corner_radius_text = self
+            .corner_radius
+            .unwrap_or_else(theme_corner_radius)
+            .to_string();
+    }
+
+    pub fn update_border_width(&mut self, border_width: u16) {
+        self.border_width = border_width.min(MAX_BORDER_WIDTH);
+        self.border_width_text = self.border_width.to_string();
+    }
+
+    /// The color is applied as soon as the text is a valid `#rrggbb` one
+    pub fn update_border_color(&mut self, text: String) {
+        if let Some(color) = from_hex(&text) {
+            self.border_color = color;
+        }
+        self.border_color_text = text;
+    }
+
     pub fn get_id(&self) -> Uuid {
         self.style_id
     }
 
+    pub fn is_new(&self) -> bool {
+        self.is_new
+    }
+
     pub fn get_name(&self) -> &str {
         &self.name
     }
 
+    pub fn get_group(&self) -> &str {
+        &self.group
+    }
+
     pub fn get_font(&self) -> Font {
         self.font.clone()
     }
@@ -76,6 +166,30 @@ impl EditStyleDialog {
         self.bgcolor
     }
 
+    pub fn get_original_background_color(&self) -> Color {
+        self.original_bgcolor
+    }
+
+    pub fn get_text_align(&self) -> TextAlign {
+        self.text_align
+    }
+
+    pub fn get_background_image(&self) -> &str {
+        &self.background_image
+    }
+
+    pub fn get_corner_radius(&self) -> Option<u16> {
+        self.corner_radius
+    }
+
+    pub fn get_border_width(&self) -> u16 {
+        self.border_width
+    }
+
+    pub fn get_border_color(&self) -> Color {
+        self.border_color
+    }
+
     pub fn on_color_picker_update(
         &mut self,
         event: ColorPickerUpdate,
@@ -113,7 +227,10 @@ impl EditStyleDialog {
                 self.bgcolor,
             ))
             .primary_action(
-                widget::button::text(fl!("edit-style-ok")).on_press(Message::EditStyleUpdate),
+                // the style must have a name
+                widget::button::text(fl!("edit-style-ok")).on_press_maybe(
+                    (!self.name.trim().is_empty()).then_some(Message::EditStyleUpdate),
+                ),
             )
             .secondary_action(
                 widget::button::text(fl!("edit-style-cancel")).on_press(Message::EditStyleCancel),
@@ -122,7 +239,9 @@ impl EditStyleDialog {
     }
 
     fn build_edit_style_control(&self) -> Element<'_, Message> {
-        widget::column::with_capacity(4)
+        let font_sample = fl!("edit-style-font-sample");
+        let font_sample_align = text_alignment(self.text_align, &font_sample);
+        widget::column::with_capacity(7)
             .spacing(cosmic::theme::spacing().space_m)
             .push(
                 widget::row::with_capacity(1).push(
@@ -131,6 +250,23 @@ impl EditStyleDialog {
                         .on_input(Message::InputStyleName),
                 ),
             )
+            .push(
+                widget::row::with_capacity(1).push(
+                    widget::text_input(fl!("edit-style-group-hint"), &self.group)
+                        .label(fl!("edit-style-group"))
+                        .on_input(Message::InputStyleGroup),
+                ),
+            )
+            .push(
+                widget::row::with_capacity(1).push(
+                    widget::text_input(
+                        fl!("edit-style-font-family-hint"),
+                        self.font.family.as_deref().unwrap_or_default(),
+                    )
+                    .label(fl!("edit-style-font-family"))
+                    .on_input(Message::FontFamilyUpdate),
+                ),
+            )
             .push(
                 widget::row::with_capacity(4)
                     .spacing(cosmic::theme::spacing().space_m)
@@ -155,26 +291,124 @@ impl EditStyleDialog {
                         &self.font_size_text, // is required if 'a11y' feature is on
                         self.font.size,
                         1,
-                        MIN_FONT_SIZE,
-                        MAX_FONT_SIZE,
+                        *self.font_sizes.start(),
+                        *self.font_sizes.end(),
                         Message::FontSizeUpdate,
                     )),
             )
             .push(
-                widget::text(fl!("edit-style-font-sample"))
-                    .font(cosmic_font(self.font.style))
-                    .size(self.font.size),
+                widget::row::with_capacity(2)
+                    .spacing(cosmic::theme::spacing().space_m)
+                    .align_y(Alignment::Center)
+                    .push(widget::text(fl!("edit-style-text-align")))
+                    .push(widget::dropdown(
+                        vec![
+                            fl!("edit-style-text-align-start"),
+                            fl!("edit-style-text-align-center"),
+                            fl!("edit-style-text-align-end"),
+                        ],
+                        TEXT_ALIGNS
+                            .iter()
+                            .position(|text_align| *text_align == self.text_align),
+                        |selected_index| {
+                            Message::TextAlignUpdate(
+                                TEXT_ALIGNS.get(selected_index).copied().unwrap_or_default(),
+                            )
+                        },
+                    )),
+            )
+            .push(
+                widget::row::with_capacity(3)
+                    .spacing(cosmic::theme::spacing().space_m)
+                    .align_y(Alignment::Center)
+                    .push(widget::text(fl!("edit-style-corner-radius")))
+                    .push(widget::spin_button::vertical(
+                        &self.corner_radius_text,
+                        &self.corner_radius_text, // is required if 'a11y' feature is on
+                        self.corner_radius.unwrap_or_else(theme_corner_radius),
+                        1,
+                        0,
+                        MAX_CORNER_RADIUS,
+                        |radius| Message::CornerRadiusUpdate(Some(radius)),
+                    ))
+                    .push(
+                        widget::button::text(fl!("edit-style-corner-radius-theme")).on_press_maybe(
+                            self.corner_radius
+                                .is_some()
+                                .then_some(Message::CornerRadiusUpdate(None)),
+                        ),
+                    ),
+            )
+            .push(
+                widget::row::with_capacity(3)
+                    .spacing(cosmic::theme::spacing().space_m)
+                    .align_y(Alignment::Center)
+                    .push(widget::text(fl!("edit-style-border")))
+                    .push(widget::spin_button::vertical(
+                        &self.border_width_text,
+                        &self.border_width_text, // is required if 'a11y' feature is on
+                        self.border_width,
+                        1,
+                        0,
+                        MAX_BORDER_WIDTH,
+                        Message::BorderWidthUpdate,
+                    ))
+                    .push(
+                        widget::text_input("#000000", &self.border_color_text)
+                            .on_input(Message::BorderColorInput),
+                    ),
+            )
+            .push(
+                widget::text(font_sample)
+                    .font(cosmic_font(&self.font))
+                    .size(self.font.size)
+                    .align_x(font_sample_align)
+                    .width(Length::Fill),
             )
             .push(
                 widget::column::with_capacity(2)
                     .spacing(cosmic::theme::spacing().space_m)
                     .push(widget::text(fl!("edit-style-bg")).align_y(Alignment::Center))
+                    .push(
+                        widget::text_input(fl!("edit-style-bg-image-hint"), &self.background_image)
+                            .label(fl!("edit-style-bg-image"))
+                            .on_input(Message::InputStyleBackgroundImage),
+                    )
+                    .push_maybe(self.build_recent_colors())
                     .push(self.build_color_picker())
                     .height(Length::Fill),
             )
             .into()
     }
 
+    // the colors chosen recently for any style
+    fn build_recent_colors(&self) -> Option<Element<'_, Message>> {
+        (!self.recent_colors.is_empty()).then(|| {
+            widget::row::with_children(
+                self.recent_colors
+                    .iter()
+                    .map(|color| {
+                        let hsv =
+                            palette::Hsv::from_color(palette::Srgb::new(color.r, color.g, color.b));
+                        widget::button::custom(with_background(
+                            widget::Space::new(
+                                Length::Fixed(RECENT_COLOR_SIZE),
+                                Length::Fixed(RECENT_COLOR_SIZE),
+                            )
+                            .into(),
+                            *color,
+                        ))
+                        .padding(0)
+                        .on_press(Message::ColorUpdate(ColorPickerUpdate::ActiveColor(hsv)))
+                        .into()
+                    })
+                    .collect(),
+            )
+            .spacing(cosmic::theme::spacing().space_xxs)
+            .into()
+        })
+    }
+
     fn build_color_picker(&self) -> Element<'_, Message> {
         self.color_picker_model
             .builder(Message::ColorUpdate)
@@ -206,3 +440,21 @@ const fn get_avail_fonts() -> &'static [FontStyle] {
         FontStyle::Monospace,
     ]
 }
+
+fn to_hex(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn from_hex(text: &str) -> Option<Color> {
+    let hex = text.trim().strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let component = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+    Some(Color::from_rgb8(
+        component(0)?,
+        component(2)?,
+        component(4)?,
+    ))
+}
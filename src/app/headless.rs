@@ -0,0 +1,87 @@
+use crate::{
+    config::Config,
+    notes::{ExportFormat, NotesCollection},
+};
+use cosmic::cosmic_config::{self, ConfigGet, CosmicConfigEntry};
+use thiserror::Error;
+
+const EXPORT_ARG: &str = "--export";
+const FORMAT_ARG: &str = "--format";
+
+#[derive(Debug, Error)]
+pub enum HeadlessError {
+    #[error("{EXPORT_ARG} requires the file path")]
+    MissingExportFile,
+    #[error("unknown format {0}, expected one of: native, indicator, markdown, text")]
+    UnknownFormat(String),
+    #[error("failed reading config: {0}")]
+    Config(cosmic_config::Error),
+    #[error("there are no saved notes")]
+    NoNotes,
+    #[error("{0}")]
+    Notes(crate::Error),
+    #[error("failed writing {0}: {1}")]
+    Write(String, std::io::Error),
+}
+
+/// Export the saved notes of the `app_id` instance if `--export <path> [--format <format>]` is among `args`
+/// without starting GUI, returns None if no export is requested
+pub fn try_run_headless_export(
+    args: &[String],
+    app_id: &str,
+) -> Option<Result<usize, HeadlessError>> {
+    let export_index = args.iter().position(|arg| arg == EXPORT_ARG)?;
+    Some(
+        parse_export_args(args, export_index)
+            .and_then(|(export_file, format)| export_saved_notes(app_id, export_file, format)),
+    )
+}
+
+fn parse_export_args(
+    args: &[String],
+    export_index: usize,
+) -> Result<(&str, ExportFormat), HeadlessError> {
+    let export_file = args
+        .get(export_index + 1)
+        .filter(|arg| !arg.starts_with("--"))
+        .ok_or(HeadlessError::MissingExportFile)?;
+    let format = match args
+        .iter()
+        .position(|arg| arg == FORMAT_ARG)
+        .map(|index| args.get(index + 1).map_or("", String::as_str))
+    {
+        // the native format is the only one keeping everything about notes
+        None | Some("native") => ExportFormat::NativeJson,
+        Some("indicator") => ExportFormat::IndicatorStickyNotes,
+        Some("markdown") => ExportFormat::Markdown,
+        Some("text") => ExportFormat::PlainText,
+        Some(unknown) => return Err(HeadlessError::UnknownFormat(unknown.to_string())),
+    };
+    Ok((export_file, format))
+}
+
+fn export_saved_notes(
+    app_id: &str,
+    export_file: &str,
+    format: ExportFormat,
+) -> Result<usize, HeadlessError> {
+    let context =
+        cosmic_config::Config::new(app_id, Config::VERSION).map_err(HeadlessError::Config)?;
+    let config = Config::get_entry(&context).unwrap_or_else(|(errors, config)| {
+        for why in errors {
+            tracing::error!("error loading app config: {why}");
+        }
+        config
+    });
+    // unlike the service, never export default notes instead of the saved ones
+    if config.notes.is_empty() {
+        return Err(HeadlessError::NoNotes);
+    }
+    let notes =
+        NotesCollection::try_read_parts(&config.notes, |key| context.get::<String>(key).ok())
+            .map_err(HeadlessError::Notes)?;
+    let content = notes.try_format(format).map_err(HeadlessError::Notes)?;
+    std::fs::write(export_file, content)
+        .map_err(|e| HeadlessError::Write(export_file.to_string(), e))?;
+    Ok(notes.get_notes_count())
+}
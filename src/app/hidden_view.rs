@@ -0,0 +1,70 @@
+use super::{service::Message, utils::with_background};
+use crate::{
+    fl,
+    icons::IconSet,
+    notes::{NoteData, NoteSort, NotesCollection},
+};
+use cosmic::prelude::*;
+use cosmic::{
+    iced::{Color, Length, widget::keyed_column},
+    widget,
+};
+use uuid::Uuid;
+
+pub fn build_hidden_notes_view<'a>(
+    notes: &'a NotesCollection,
+    icons: &IconSet,
+    icon_size: u16,
+) -> Element<'a, Message> {
+    widget::column::with_capacity(2)
+        .spacing(cosmic::theme::spacing().space_m)
+        .push(widget::text(fl!("hidden-notes-description")))
+        .push(
+            widget::scrollable(keyed_column(
+                notes
+                    .notes_with_style_sorted(NoteSort::TitleAsc)
+                    .into_iter()
+                    .filter(|(_, note, _)| !note.is_visible())
+                    .map(|(note_id, note, style)| {
+                        (
+                            note_id,
+                            build_note_list_item(
+                                note_id,
+                                note,
+                                style.get_background_color(),
+                                icons,
+                                icon_size,
+                            ),
+                        )
+                    }),
+            ))
+            .width(Length::Fill)
+            .height(Length::Fill),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+fn build_note_list_item<'a>(
+    note_id: Uuid,
+    note: &'a NoteData,
+    bgcolor: Color,
+    icons: &IconSet,
+    icon_size: u16,
+) -> Element<'a, Message> {
+    let child = widget::row::with_capacity(2)
+        .spacing(cosmic::theme::spacing().space_s)
+        .width(Length::Fill)
+        .push(widget::text(note.get_title()).width(Length::Fill))
+        .push(
+            icons
+                .show()
+                .apply(widget::button::icon)
+                .icon_size(icon_size)
+                .on_press(Message::NoteShow(note_id))
+                .width(Length::Shrink),
+        )
+        .into();
+    with_background(child, bgcolor)
+}
@@ -0,0 +1,29 @@
+use crate::fl;
+use std::collections::HashMap;
+
+/// Shows desktop notification via freedesktop notifications service
+pub async fn send_notification(
+    connection: &zbus::Connection,
+    summary: &str,
+    body: &str,
+) -> zbus::Result<()> {
+    connection
+        .call_method(
+            Some("org.freedesktop.Notifications"),
+            "/org/freedesktop/Notifications",
+            Some("org.freedesktop.Notifications"),
+            "Notify",
+            &(
+                fl!("app-title"),
+                0u32, // replaces_id
+                super::APP_ID,
+                summary,
+                body,
+                Vec::<&str>::new(),                                // actions
+                HashMap::<&str, zbus::zvariant::Value<'_>>::new(), // hints
+                -1i32, // expire timeout is up to the notification server
+            ),
+        )
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use super::{
+    service::{BulkAction, Message},
+    utils::with_background,
+};
+use crate::{
+    fl,
+    notes::{NoteData, NoteSort, NotesCollection},
+};
+use cosmic::prelude::*;
+use cosmic::{
+    iced::{Alignment, Color, Length},
+    widget,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+const CARD_WIDTH: f32 = 160.0;
+const CARD_HEIGHT: f32 = 120.0;
+const PREVIEW_LINES: usize = 5;
+const PREVIEW_COLS: usize = 20;
+
+pub fn build_overview_view<'a>(
+    notes: &'a NotesCollection,
+    selection: &HashSet<Uuid>,
+) -> Element<'a, Message> {
+    let cards = notes
+        .notes_with_style_sorted(NoteSort::ModifiedDesc)
+        .into_iter()
+        .filter(|(_, note, _)| note.is_visible())
+        .map(|(note_id, note, style)| {
+            build_note_card(
+                note_id,
+                note,
+                style.get_background_color(),
+                selection.contains(&note_id),
+            )
+        })
+        .collect();
+    let selected: Vec<Uuid> = selection.iter().copied().collect();
+    widget::column::with_capacity(3)
+        .spacing(cosmic::theme::spacing().space_m)
+        .push(widget::text(fl!("overview-description")))
+        .push(build_selection_actions(notes, selected))
+        .push(
+            widget::scrollable(
+                widget::flex_row(cards)
+                    .column_spacing(cosmic::theme::spacing().space_s)
+                    .row_spacing(cosmic::theme::spacing().space_s),
+            )
+            .width(Length::Fill)
+            .height(Length::Fill),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+// the actions applied to all of the selected notes at once
+fn build_selection_actions(notes: &NotesCollection, selected: Vec<Uuid>) -> Element<'_, Message> {
+    let any_selected = !selected.is_empty();
+    let bulk =
+        |action: BulkAction| any_selected.then(|| Message::BulkApply(selected.clone(), action));
+    let style_ids: Vec<Uuid> = notes.iter_styles().map(|(style_id, _)| *style_id).collect();
+    let style_selected = selected.clone();
+    widget::row::with_capacity(5)
+        .spacing(cosmic::theme::spacing().space_s)
+        .align_y(Alignment::Center)
+        .push(
+            widget::button::text(fl!("export-selected"))
+                .on_press_maybe(any_selected.then(|| Message::ExportSelected(selected.clone()))),
+        )
+        .push(
+            widget::button::text(fl!("lock-selected")).on_press_maybe(bulk(BulkAction::Lock(true))),
+        )
+        .push(
+            widget::button::text(fl!("unlock-selected"))
+                .on_press_maybe(bulk(BulkAction::Lock(false))),
+        )
+        .push(
+            widget::dropdown(notes.get_style_names(), None, move |index| {
+                Message::BulkApply(
+                    style_selected.clone(),
+                    BulkAction::SetStyle(style_ids.get(index).copied().unwrap_or_default()),
+                )
+            })
+            .placeholder(fl!("style-selected")),
+        )
+        .push(
+            widget::button::destructive(fl!("delete-selected"))
+                .on_press_maybe(bulk(BulkAction::Delete)),
+        )
+        .into()
+}
+
+fn build_note_card(
+    note_id: Uuid,
+    note: &NoteData,
+    bgcolor: Color,
+    is_selected: bool,
+) -> Element<'_, Message> {
+    widget::column::with_capacity(2)
+        .spacing(cosmic::theme::spacing().space_xxs)
+        .push(
+            widget::button::custom(with_background(
+                widget::text(note.preview(PREVIEW_LINES, PREVIEW_COLS))
+                    .width(Length::Fixed(CARD_WIDTH))
+                    .height(Length::Fixed(CARD_HEIGHT))
+                    .into(),
+                bgcolor,
+            ))
+            .padding(0)
+            .on_press(Message::OverviewNoteSelected(note_id)),
+        )
+        .push(
+            widget::checkbox(fl!("overview-select"), is_selected)
+                .on_toggle(move |on| Message::OverviewNoteToggled(note_id, on)),
+        )
+        .into()
+}
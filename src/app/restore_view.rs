@@ -2,41 +2,53 @@ use super::{service::Message, utils::with_background};
 use crate::{
     fl,
     icons::IconSet,
-    notes::{NoteData, NoteStyle, NotesCollection},
+    notes::{NoteData, NoteSort, NotesCollection},
 };
 use cosmic::prelude::*;
 use cosmic::{
-    iced::{Color, Length, widget::keyed_column},
+    iced::{self, Color, Length, widget::keyed_column},
     widget,
 };
 use uuid::Uuid;
 
+// the order of deleted notes in the restore window
+pub const RESTORE_SORT: NoteSort = NoteSort::ModifiedDesc;
+
 pub fn build_restore_view<'a>(
     notes: &'a NotesCollection,
     icons: &IconSet,
     icon_size: u16,
+    selection: Option<Uuid>,
 ) -> Element<'a, Message> {
+    // the view is rebuilt on every change of notes, so the list empties in place once the last note is restored
+    if !notes.has_deleted_notes() {
+        return widget::text(fl!("recently-deleted-empty"))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into();
+    }
     widget::column::with_capacity(2)
         .spacing(cosmic::theme::spacing().space_m)
         .push(widget::text(fl!("recently-deleted-description")))
         .push(
-            widget::scrollable(keyed_column(notes.iter_deleted_notes().map(
-                |(note_id, note)| {
-                    (
-                        *note_id,
-                        build_note_list_item(
-                            *note_id,
-                            note,
-                            notes
-                                .try_get_note_style(*note_id)
-                                .map(NoteStyle::get_background_color)
-                                .ok(),
-                            icons,
-                            icon_size,
-                        ),
-                    )
-                },
-            )))
+            widget::scrollable(keyed_column(
+                notes
+                    .deleted_notes_with_style_sorted(RESTORE_SORT)
+                    .into_iter()
+                    .map(|(note_id, note, style)| {
+                        (
+                            note_id,
+                            build_note_list_item(
+                                note_id,
+                                note,
+                                style.get_background_color(),
+                                icons,
+                                icon_size,
+                                selection == Some(note_id),
+                            ),
+                        )
+                    }),
+            ))
             .width(Length::Fill)
             .height(Length::Fill),
         )
@@ -48,9 +60,10 @@ pub fn build_restore_view<'a>(
 fn build_note_list_item<'a>(
     note_id: Uuid,
     note: &'a NoteData,
-    bgcolor: Option<Color>,
+    bgcolor: Color,
     icons: &IconSet,
     icon_size: u16,
+    is_selected: bool,
 ) -> Element<'a, Message> {
     let child = widget::row::with_capacity(2)
         .spacing(cosmic::theme::spacing().space_s)
@@ -65,9 +78,28 @@ fn build_note_list_item<'a>(
                 .width(Length::Shrink),
         )
         .into();
-    if let Some(note_bg) = bgcolor {
-        with_background(child, note_bg)
+    let item = with_background(child, bgcolor);
+    if is_selected {
+        with_selection_border(item)
     } else {
-        child
+        item
     }
 }
+
+// highlights the item selected with keyboard
+fn with_selection_border(child: Element<'_, Message>) -> Element<'_, Message> {
+    widget::container(child)
+        .class(cosmic::style::Container::custom(|theme: &Theme| {
+            let cosmic = theme.cosmic();
+            iced::widget::container::Style {
+                border: iced::Border {
+                    color: cosmic.accent_color().into(),
+                    width: 2.0,
+                    radius: cosmic.corner_radii.radius_s.into(),
+                },
+                ..Default::default()
+            }
+        }))
+        .padding(cosmic::theme::spacing().space_xxxs)
+        .into()
+}
@@ -5,38 +5,117 @@ use crate::{
         Command,
         about_window::AboutWindow,
         edit_style::EditStyleDialog,
-        restore_view::build_restore_view,
+        hidden_view::build_hidden_notes_view,
+        notification::send_notification,
+        overview_view::build_overview_view,
+        restore_view::{RESTORE_SORT, build_restore_view},
         settings_view::build_settings_view,
+        spellcheck::SpellChecker,
         sticky_window::StickyWindow,
-        utils::{to_f32, to_usize},
+        undo_toast_view::build_undo_toast_view,
+        utils::{cascade_position, to_f32, to_usize},
+        validation_view::build_validation_view,
+        window_registry::{WindowRegistry, WindowRegistryError},
     },
-    config::Config,
+    config::{Config, WindowCloseAction},
     fl, icons,
-    notes::{Font, FontStyle, NoteData, NotesCollection},
+    notes::{
+        ChangeEvent, ExportFormat, FontStyle, MergeStrategy, NoteData, NoteTemplate,
+        NotesCollection, NotesCollectionError, StyleMatch, TextAlign, ValidationReport,
+    },
+    state::State,
 };
+use chrono::{Local, Utc};
+use cosmic::cctk::{sctk::output::OutputInfo, wayland_client::Proxy as _};
 use cosmic::prelude::*;
 use cosmic::{
-    app::CosmicFlags,
     applet,
-    cosmic_config::{self, ConfigSet, CosmicConfigEntry},
+    cosmic_config::{self, ConfigGet, ConfigSet, CosmicConfigEntry},
     dbus_activation,
+    dialog::file_chooser,
     iced::{
-        self, Color, Event, Point, Size, Subscription,
+        self, Color, Event, Point, Rectangle, Size, Subscription,
         core::mouse::Button as MouseButton,
-        event::Status as EventStatus,
+        event::{
+            PlatformSpecific, Status as EventStatus,
+            wayland::{Event as WaylandEvent, OutputEvent},
+        },
+        keyboard::{Event as KeyEvent, Key, key::Named as NamedKey},
         mouse::Event as MouseEvent,
         window::{self, Event as WindowEvent, Id, Position},
     },
     widget,
 };
-use std::{collections::HashMap, str::FromStr};
+use futures_util::{SinkExt, StreamExt};
+use i18n_embed::unic_langid::LanguageIdentifier;
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicI32, Ordering},
+    },
+    time::{Duration, Instant},
+};
 use uuid::Uuid;
 
-pub struct ServiceFlags;
+// how often to test reminders of notes are due
+const REMINDERS_CHECK_PERIOD: Duration = Duration::from_secs(60);
+// how often to test notes have been left without interaction long enough to hide them
+const IDLE_CHECK_PERIOD: Duration = Duration::from_secs(30);
+// how long the undo toast is shown after the note is deleted by middle-click
+const UNDO_TOAST_TIMEOUT: Duration = Duration::from_secs(5);
+const UNDO_TOAST_WIDTH: f32 = 320.0;
+const UNDO_TOAST_HEIGHT: f32 = 56.0;
+// how long the copy button shows the content is copied
+const COPIED_FEEDBACK_TIMEOUT: Duration = Duration::from_millis(1500);
+// how often to track the cursor position, every move would update the service and redraw notes
+const CURSOR_TRACK_PERIOD: Duration = Duration::from_millis(100);
+// the shift of the each next new note position
+const NEW_NOTE_CASCADE_OFFSET: usize = 24;
+// the file name suggested in the export dialog, the extension is of the chosen format
+const EXPORT_FILE_STEM: &str = "sticky-notes";
+
+pub struct ServiceFlags {
+    /// The system's preferred languages, the spelling is checked in them
+    pub requested_languages: Vec<LanguageIdentifier>,
+    /// The app id of the instance naming its config, notes storage and DBus service, see `app_id()`
+    pub app_id: &'static str,
+}
+
+/// The screen reported by the compositor, its name and bounds are unknown until it reports them
+#[derive(Debug, Clone)]
+pub struct ScreenOutput {
+    name: Option<String>,
+    bounds: Option<Rectangle>,
+}
+
+impl From<&OutputInfo> for ScreenOutput {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(info: &OutputInfo) -> Self {
+        let bounds =
+            info.logical_position
+                .zip(info.logical_size)
+                .map(|((x, y), (width, height))| Rectangle {
+                    x: x as f32,
+                    y: y as f32,
+                    width: width as f32,
+                    height: height as f32,
+                });
+        Self {
+            name: info.name.clone(),
+            bounds,
+        }
+    }
+}
 
-impl CosmicFlags for ServiceFlags {
-    type SubCommand = usize;
-    type Args = Vec<String>;
+/// The action applied at once to the notes selected in the overview window
+#[derive(Debug, Clone, Copy)]
+pub enum BulkAction {
+    Lock(bool),
+    SetStyle(Uuid),
+    Delete,
 }
 
 /// Messages emitted by the application and its widgets.
@@ -49,47 +128,162 @@ pub enum Message {
     // Windows creating
     StickyWindowCreated(Id, Uuid), // (window_id, note_id)
     RestoreWindowCreated(Id),
+    OverviewWindowCreated(Id),
+    HiddenWindowCreated(Id),
     SettingsWindowCreated(Id),
-    EditStyleWindowCreated(Id, Uuid), // (window_id, style_id)
+    EditStyleWindowCreated(Id, Uuid, bool), // (window_id, style_id, style is new)
     AboutWindowCreated(Id),
+    ValidationWindowCreated(Id),
+    UndoToastCreated(Id, Uuid), // (window_id, deleted note_id)
+    UndoToastExpired(Id),
     // Settings actions
-    SetDefaultStyle(usize), // set default style by index
+    SetDefaultStyle(usize),       // set default style by index
+    SetScreenDefaultStyle(usize), // set default style of the current screen by index
+    ResetScreenDefaultStyle,      // use the default style on the current screen
+    SetHighContrast(bool),        // override all note styles with the high contrast palette
+    // Templates of new notes in settings
+    TemplateNew,                         // add new template having empty content
+    TemplateNameInput(usize, String),    // rename template by index
+    TemplateEditContent(usize),          // open / close the content editor of template by index
+    TemplateStyleSelected(usize, usize), // (template index, style index)
+    TemplateDelete(usize),               // delete template by index
+    // Redirect editor actions to the content of the template being edited
+    TemplateContentEdit(widget::text_editor::Action),
+    // Lock or unlock all notes at once
+    SetAllLocked(bool),
     // Notes collection load results
     LoadNotesCompleted(NotesCollection),
     LoadNotesFailed(String), // error message
+    // Import notes and merge them into existing ones
+    MergeImport,
+    MergeNotesCompleted(NotesCollection),
     // Export notes results
     ExportNotesCompleted,
-    ExportNotesFailed(String), // error message
+    ExportNotesFailed(String),         // error message
+    ExportPathChecked(Option<String>), // the reason the configured export file is unwritable if any
+    // Choose file interactively, then import from or export to it
+    PickImportFile,
+    PickExportFile(ExportFormat),
+    PickKeepImport,
+    ImportFromPath(String),
+    ImportKeepFromPath(String),
+    ExportToPath(String, ExportFormat),
+    // Choose notes file interactively, then check it without applying
+    PickValidateFile,
+    ValidateFromPath(String),
+    ValidationCompleted(String, Result<ValidationReport, String>), // (path, report or error message)
     // Redirect editor actions to the edit context
     Edit(Id, widget::text_editor::Action),
     // "system" events handling
     AppWindowEvent((Id, WindowEvent)),
     AppMouseEvent((Id, MouseEvent)),
+    CursorMoved(Id, Point), // the position is relative to the window
+    AppKeyPressed((Id, Key)),
     DbusActivation(dbus_activation::Message),
+    // The DBus service of the instance is not available, e.g. another service of the same app id is running
+    DbusServeFailed(String),
+    // The dictionaries are loaded in background, they are used if the spelling check is still on
+    SpellCheckerLoaded(Arc<SpellChecker>),
+    // Saving notes has failed, the error is shown to the user
+    SaveFailed(String),
+    // Saving notes on exit has failed, the user has been notified
+    ExitFailed,
     // Ignorable dummy message (example: message is caught in dbus_activation::subscription() but isn't a DbusActivation)
     Ignore,
     // response on window::get_position() request
     WindowPositionResponse((Id, Option<Point>)),
+    // response on window::monitor_size() request
+    MonitorSizeResponse((Id, Option<Size>)),
+    // the screen of the protocol id is added or updated, it is removed if None
+    OutputChanged(u32, Option<ScreenOutput>),
     // Sticky window buttons
-    NoteLock(Id, bool),           // lock / unlock note
-    NoteEdit(Id, bool),           // edit / save note content
-    NoteStyle(Id),                // select style (background, font) for sticky window
+    NoteLock(Id, bool),                 // lock / unlock note
+    NoteClickThrough(Id, bool),         // let clicks pass through the locked note or not
+    NoteOpacity(Id, f32),               // dim the note regardless of its style
+    NoteEdit(Id, bool),                 // edit / save note content
+    NoteUndo(Id),                       // restore the content preceding the recent edit
+    NoteClearHistory(Id),               // forget the previous contents of the note
+    NoteStyle(Id),                      // select style (background, font) for sticky window
     NoteStyleSelected(Id, usize), // style (background, font) for sticky window was selected by index in styles collection
     NoteNew,                      // create new note with default style and begin edit
+    NoteNewAtCursor,              // create new note under the mouse cursor if it is known
+    NoteNewFromTemplate(usize),   // create new note filled by the template with index
+    PasteNote,                    // create new note shared as JSON via clipboard
+    PasteNoteText(Option<String>), // the clipboard content to create the note from
     NoteDelete(Id),               // delete note
+    NoteHide(Id),                 // hide note keeping it in the collection
+    NoteShow(Uuid),               // show the hidden note again
     NoteRestore(Uuid),            // restore note
+    NoteReminderInput(Id, String), // reminder is being edited
+    NoteAttachmentInput(Id, String), // file path or URI to attach is being edited
+    NoteAttachmentAdd(Id),        // attach entered file path or URI
+    NoteAttachmentRemove(Id, usize), // remove attachment by index
+    NoteIconSize(Id, u16),        // change toolbar icon size of the note
+    NoteCopyContent(Id),          // copy the whole content to clipboard
+    NoteCopiedExpired(Id),        // stop showing the content is copied
+    NoteCopyAsJson(Id),           // copy the note along with its style to clipboard to share it
+    NoteReplaceToggle(Id),        // show / hide find & replace row
+    NoteFindInput(Id, String),    // text to find is being edited
+    NoteReplaceInput(Id, String), // replacement is being edited
+    NoteReplaceCaseSensitive(Id, bool), // find is case sensitive or not
+    NoteReplaceAll(Id, String, String), // replace all occurrences of the text with replacement
+    CheckReminders,               // fire due reminders
+    CheckIdle,                    // hide notes left without interaction for a while
+    RestoreSelectMove(i32),       // move selection in the restore window up (negative) or down
+    RestoreSelected,              // restore note selected in the restore window
+    OverviewNoteSelected(Uuid),   // note card was clicked in the overview window
+    OverviewNoteToggled(Uuid, bool), // note is (un)checked in the overview window
+    ExportSelected(Vec<Uuid>),    // export chosen notes only
+    BulkApply(Vec<Uuid>, BulkAction), // apply the action to chosen notes
+    NoteResizeStart(Id),          // resize grip was pressed
     // Styles view buttons
     StyleNew,                                             // add new style
     StyleEdit(Uuid),                                      // edit style by style_id
-    StyleDelete(Uuid),                                    // delete style by style_id
-    EditStyleUpdate,                                      // Ok was pressed in edit style dialog
-    EditStyleCancel,                                      // Cancel was pressed in edit style dialog
-    InputStyleName(String),                               // update currently edited style name
+    StyleDuplicate(Uuid),     // add the copy of style by style_id and edit it
+    StyleDelete(Uuid),        // delete style by style_id
+    StyleDeleteConfirm(Uuid), // delete style in use by style_id after user has confirmed it
+    StyleDeleteCancel,        // user has declined deleting style in use
+    StylesGc,                 // ask user to confirm removing styles no note uses
+    StylesGcConfirm,          // remove styles no note uses after user has confirmed it
+    StylesGcCancel,           // user has declined removing unused styles
+    StyleGroupToggle(String), // fold / unfold styles of the group in the styles list
+    ReassignStyleTarget(Uuid, usize), // choose target style (by index) to move notes of style_id into
+    ReassignStyle(Uuid, usize),       // move all notes of style_id to style selected by index
+    EditStyleUpdate,                  // Ok was pressed in edit style dialog
+    EditStyleCancel,                  // Cancel was pressed in edit style dialog
+    InputStyleName(String),           // update currently edited style name
+    InputStyleGroup(String),          // update currently edited style group
+    InputStyleBackgroundImage(String), // update currently edited style background image path
     ColorUpdate(widget::color_picker::ColorPickerUpdate), // update currently edited style color
-    FontStyleUpdate(FontStyle),                           // update currently edited style font
-    FontSizeUpdate(u16),                                  // update currently edited style font size
+    FontStyleUpdate(FontStyle),       // update currently edited style font
+    FontFamilyUpdate(String),         // update currently edited style font family
+    FontSizeUpdate(u16),              // update currently edited style font size
+    TextAlignUpdate(TextAlign),       // update currently edited style text alignment
+    CornerRadiusUpdate(Option<u16>), // update currently edited style corners, None follows the theme
+    BorderWidthUpdate(u16),          // update currently edited style border width
+    BorderColorInput(String),        // update currently edited style border color as #rrggbb
     // Open URL
     OpenUrl(String),
+    // Open local file with the default application
+    OpenPath(String),
+}
+
+impl Message {
+    // the user works with notes, the service commands but PING are sent by the user from the applet,
+    // showing or hiding all notes is the user's choice overriding hiding due to no interaction
+    fn is_interaction(&self) -> bool {
+        match self {
+            Message::Edit(..)
+            | Message::CursorMoved(..)
+            | Message::AppMouseEvent(..)
+            | Message::AppKeyPressed(..) => true,
+            Message::Signal(command) => !matches!(
+                command,
+                Command::Ping | Command::ShowAllNotes | Command::HideAllNotes
+            ),
+            _ => false,
+        }
+    }
 }
 
 /// The application model stores app-specific state used to describe its interface and
@@ -97,18 +291,70 @@ pub enum Message {
 pub struct ServiceModel {
     // Application state which is managed by the COSMIC runtime.
     core: cosmic::Core,
+    // The app id of the instance, see ServiceFlags::app_id
+    app_id: &'static str,
     /// Configuration data that persists between application runs.
     config: Config,
     // Collection of notes & styles
     notes: NotesCollection,
+    // The state of notes collection recently published for the applet
+    state: State,
+    state_context: Option<cosmic_config::Config>,
+    // the error of the recent failed saving, cleared by the successful one
+    save_error: Option<String>,
+    // the reason the configured export file is unwritable, exports are going to fail
+    export_warning: Option<String>,
     settings_window_id: Option<Id>,
+    // target style index chosen in settings to move notes of the style_id into
+    reassign_targets: HashMap<Uuid, usize>,
+    // style groups folded in the styles list of settings
+    collapsed_style_groups: HashSet<String>,
+    // the style in use the user is asked to confirm deleting
+    delete_style_confirm: Option<Uuid>,
+    // the user is asked to confirm removing unused styles
+    gc_styles_confirm: bool,
+    // the index of template which content is edited in settings and the edited content
+    template_editor: Option<(usize, widget::text_editor::Content)>,
     edit_style: Option<(Id, EditStyleDialog)>,
     restore_window_id: Option<Id>,
+    hidden_window_id: Option<Id>,
+    // deleted note highlighted in the restore window
+    restore_selection: Option<Uuid>,
+    overview_window_id: Option<Id>,
+    // notes checked in the overview window
+    overview_selection: HashSet<Uuid>,
     about_window: Option<(Id, AboutWindow)>,
+    // the report on the validated notes file and the window showing it
+    validation: Option<(String, Result<ValidationReport, String>)>,
+    validation_window_id: Option<Id>,
+    // the popup offering to restore the note deleted by middle-click
+    undo_toast: Option<(Id, Uuid)>,
     // sticky windows by ID
-    sticky_windows: HashMap<Id, StickyWindow>,
+    sticky_windows: WindowRegistry,
+    // The size of the primary screen if any sticky window has been shown already
+    screen_size: Option<Size>,
+    // The screens reported by the compositor by their protocol id
+    outputs: HashMap<u32, ScreenOutput>,
+    // The name of the screen the recently created sticky window is on if the compositor reports it
+    screen_name: Option<String>,
     // Window is under cursor at the moment
     cursor_window: Option<Id>,
+    // The recent cursor position on the screen while it is over a sticky window
+    cursor_position: Option<(usize, usize)>,
+    // The note which window has gained focus recently
+    last_focused_note: Option<Uuid>,
+    // The note to focus as soon as its window is created
+    focus_on_spawn: Option<Uuid>,
+    // The notes which windows are requested but not created yet
+    spawning_notes: HashSet<Uuid>,
+    // The recent interaction with notes, they are hidden if it was long ago, see Config::auto_hide_after_mins
+    last_interaction: Instant,
+    // The note windows are closed due to no interaction, they are shown again on the next interaction,
+    // the notes stay visible, so they are shown on the next start anyway
+    idle_hidden: bool,
+    // The languages to check spelling in and the checker loaded if Config::spellcheck is on
+    requested_languages: Vec<LanguageIdentifier>,
+    spell_checker: Option<Arc<SpellChecker>>,
     #[cfg(not(feature = "xdg_icons"))]
     icons: icons::IconSet,
     #[cfg(feature = "xdg_icons")]
@@ -138,12 +384,10 @@ impl cosmic::Application for ServiceModel {
     }
 
     /// Initializes the application with any given flags and startup commands.
-    fn init(
-        core: cosmic::Core,
-        _flags: Self::Flags,
-    ) -> (Self, Task<cosmic::Action<Self::Message>>) {
+    fn init(core: cosmic::Core, flags: Self::Flags) -> (Self, Task<cosmic::Action<Self::Message>>) {
         // Load config
-        let config = cosmic_config::Config::new(Self::APP_ID, Config::VERSION)
+        let app_id = flags.app_id;
+        let config = cosmic_config::Config::new(app_id, Config::VERSION)
             .map(|context| match Config::get_entry(&context) {
                 Ok(config) => config,
                 Err((errors, config)) => {
@@ -156,31 +400,86 @@ impl cosmic::Application for ServiceModel {
             .unwrap_or_default();
 
         // Load notes from config if config/notes is not empty
-        let notes = Self::load_notes_or_default(&config.notes);
+        let mut notes = Self::load_notes_or_default(app_id, &config);
+        // notes created in this session are never pruned, so a new blank note waits for editing
+        if config.prune_empty_on_load {
+            let pruned = notes.prune_empty_notes();
+            if pruned > 0 {
+                tracing::info!("pruned {pruned} empty notes on load");
+            }
+        }
+
+        // Prepare to publish notes state
+        let state_context = cosmic_config::Config::new_state(app_id, State::VERSION)
+            .map_err(|e| tracing::error!("failed to access state: {e}"))
+            .ok();
 
         // Construct the app model with the runtime's core.
         let mut app = ServiceModel {
             core,
+            app_id,
             // Optional configuration file for an application.
             config,
             notes,
+            state: State::default(),
+            save_error: None,
+            export_warning: None,
+            state_context,
             settings_window_id: None,
+            reassign_targets: HashMap::new(),
+            collapsed_style_groups: HashSet::new(),
+            delete_style_confirm: None,
+            gc_styles_confirm: false,
+            template_editor: None,
             edit_style: None,
             restore_window_id: None,
+            hidden_window_id: None,
+            restore_selection: None,
+            overview_window_id: None,
+            overview_selection: HashSet::new(),
             about_window: None,
-            sticky_windows: HashMap::new(),
+            validation: None,
+            validation_window_id: None,
+            undo_toast: None,
+            sticky_windows: WindowRegistry::default(),
+            screen_size: None,
+            outputs: HashMap::new(),
+            screen_name: None,
             cursor_window: None,
+            cursor_position: None,
+            last_focused_note: None,
+            focus_on_spawn: None,
+            spawning_notes: HashSet::new(),
+            last_interaction: Instant::now(),
+            idle_hidden: false,
+            requested_languages: flags.requested_languages,
+            spell_checker: None,
             icons: icons::IconSet::new(),
         };
+        let load_spell_checker = app.update_spell_checker();
+
+        // Publish actual state replacing the one left from the previous run
+        app.publish_state();
 
         // Create a startup commands: spawn note windows and (optionally) import indicator-stickynotes data
         let mut startup_tasks: Vec<Task<cosmic::Action<Message>>> = app.spawn_sticky_windows();
+        startup_tasks.push(load_spell_checker);
+        // reminders passed while the service was down are fired at once
+        startup_tasks.push(Task::done(cosmic::Action::App(Message::CheckReminders)));
         // Import notes: if notes is default and empty (so, it was not loaded from config)
         // and if indicator-stickynotes is set try import from it
         if app.notes.is_default_collection() {
             // try read import_file name from config or construct default path to indicator-stickynotes data file
             startup_tasks.push(cosmic::task::future(Self::import_notes(
                 app.config.import_file.clone(),
+                app.config.clone(),
+                Message::LoadNotesCompleted,
+            )));
+        }
+        // find out exports are failing before a backup is needed
+        if !app.config.export_file.is_empty() {
+            startup_tasks.push(cosmic::task::future(Self::check_export_path(
+                app.config.export_file.clone(),
             )));
         }
 
@@ -197,8 +496,8 @@ impl cosmic::Application for ServiceModel {
 
     /// Constructs views for other windows.
     fn view_window(&self, id: Id) -> Element<'_, Self::Message> {
-        if let Some(sticky_window) = self.sticky_windows.get(&id) {
-            sticky_window.build_view(id, &self.notes, &self.icons)
+        if let Some(sticky_window) = self.sticky_windows.get(id) {
+            sticky_window.build_view(id, &self.notes, &self.icons, self.config.high_contrast)
         } else if let Some(window_id) = self.restore_window_id
             && window_id == id
         {
@@ -206,10 +505,29 @@ impl cosmic::Application for ServiceModel {
                 &self.notes,
                 &self.icons,
                 self.config.toolbar_icon_size,
+                self.restore_selection,
+            ))
+            .class(cosmic::style::Container::Background)
+            .padding(cosmic::theme::spacing().space_s)
+            .into()
+        } else if let Some(window_id) = self.hidden_window_id
+            && window_id == id
+        {
+            widget::container(build_hidden_notes_view(
+                &self.notes,
+                &self.icons,
+                self.config.toolbar_icon_size,
             ))
             .class(cosmic::style::Container::Background)
             .padding(cosmic::theme::spacing().space_s)
             .into()
+        } else if let Some(window_id) = self.overview_window_id
+            && window_id == id
+        {
+            widget::container(build_overview_view(&self.notes, &self.overview_selection))
+                .class(cosmic::style::Container::Background)
+                .padding(cosmic::theme::spacing().space_s)
+                .into()
         } else if let Some(window_id) = self.settings_window_id
             && window_id == id
         {
@@ -217,6 +535,16 @@ impl cosmic::Application for ServiceModel {
                 &self.notes,
                 &self.icons,
                 self.config.toolbar_icon_size,
+                self.config.high_contrast,
+                self.screen_context().as_deref(),
+                &self.reassign_targets,
+                &self.collapsed_style_groups,
+                self.delete_style_confirm,
+                self.gc_styles_confirm,
+                self.edit_style.as_ref().map(|(_, dialog)| dialog.get_id()),
+                self.template_editor
+                    .as_ref()
+                    .map(|(index, content)| (*index, content)),
             ))
             .class(cosmic::style::Container::Background)
             .padding(cosmic::theme::spacing().space_s)
@@ -235,6 +563,21 @@ impl cosmic::Application for ServiceModel {
                 .class(cosmic::style::Container::Background)
                 .padding(cosmic::theme::spacing().space_s)
                 .into()
+        } else if let Some(window_id) = self.validation_window_id
+            && window_id == id
+            && let Some((path, report)) = &self.validation
+        {
+            widget::container(build_validation_view(path, report))
+                .class(cosmic::style::Container::Background)
+                .padding(cosmic::theme::spacing().space_s)
+                .into()
+        } else if let Some((window_id, note_id)) = self.undo_toast
+            && window_id == id
+        {
+            widget::container(build_undo_toast_view(&self.notes, note_id))
+                .class(cosmic::style::Container::Background)
+                .padding(cosmic::theme::spacing().space_s)
+                .into()
         } else {
             widget::text("").into()
         }
@@ -248,10 +591,10 @@ impl cosmic::Application for ServiceModel {
     /// indefinitely.
     fn subscription(&self) -> Subscription<Self::Message> {
         // Add subscriptions which are always active.
-        let subscriptions = vec![
+        let mut subscriptions = vec![
             // Watch for application configuration changes.
             self.core()
-                .watch_config::<Config>(Self::APP_ID)
+                .watch_config::<Config>(self.app_id)
                 .map(|update| {
                     for e in update.errors {
                         tracing::error!("config error: {e}");
@@ -260,8 +603,10 @@ impl cosmic::Application for ServiceModel {
                 }),
             // subscribe to some interested events from mouse and window:
             iced::event::listen_with(|evt, status, id| match evt {
-                Event::Mouse(MouseEvent::CursorMoved { .. })
-                | Event::Window(WindowEvent::RedrawRequested(_)) => None,
+                Event::Mouse(MouseEvent::CursorMoved { position }) => {
+                    cursor_track_due().then_some(Message::CursorMoved(id, position))
+                }
+                Event::Window(WindowEvent::RedrawRequested(_)) => None,
                 Event::Mouse(mouse_event) => {
                     // get Mouse events only if unhandled
                     if status == EventStatus::Ignored {
@@ -271,9 +616,10 @@ impl cosmic::Application for ServiceModel {
                     }
                 }
                 Event::Window(window_event) => {
-                    // get Closed & CloseRequested always, others only if unhandled
+                    // get Closed, CloseRequested & Focused always, others only if unhandled
                     if window_event == WindowEvent::CloseRequested
                         || window_event == WindowEvent::Closed
+                        || window_event == WindowEvent::Focused
                         || status == EventStatus::Ignored
                     {
                         Some(Message::AppWindowEvent((id, window_event)))
@@ -281,13 +627,36 @@ impl cosmic::Application for ServiceModel {
                         None
                     }
                 }
+                Event::PlatformSpecific(PlatformSpecific::Wayland(WaylandEvent::Output(
+                    event,
+                    output,
+                ))) => {
+                    let output_id = output.id().protocol_id();
+                    match event {
+                        OutputEvent::Created(Some(info)) | OutputEvent::InfoUpdate(info) => Some(
+                            Message::OutputChanged(output_id, Some(ScreenOutput::from(&info))),
+                        ),
+                        OutputEvent::Removed => Some(Message::OutputChanged(output_id, None)),
+                        OutputEvent::Created(None) => None,
+                    }
+                }
+                Event::Keyboard(KeyEvent::KeyPressed { key, .. }) => {
+                    // get key presses only if unhandled
+                    if status == EventStatus::Ignored {
+                        Some(Message::AppKeyPressed((id, key)))
+                    } else {
+                        None
+                    }
+                }
                 _ => None,
             }),
-            dbus_activation::subscription::<ServiceModel>().map(|evt| match evt {
-                cosmic::Action::DbusActivation(msg) => Message::DbusActivation(msg),
-                _ => Message::Ignore,
-            }),
+            // Fire due reminders
+            iced::time::every(REMINDERS_CHECK_PERIOD).map(|_| Message::CheckReminders),
+            dbus_activation_subscription(self.app_id),
         ];
+        if self.config.auto_hide_timeout().is_some() {
+            subscriptions.push(iced::time::every(IDLE_CHECK_PERIOD).map(|_| Message::CheckIdle));
+        }
         Subscription::batch(subscriptions)
     }
 
@@ -304,8 +673,66 @@ impl cosmic::Application for ServiceModel {
     ///
     /// Tasks may be returned for asynchronous execution of code in the background
     /// on the application's async runtime.
-    #[allow(clippy::too_many_lines)]
     fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
+        let wake_up = if message.is_interaction() {
+            self.on_interaction()
+        } else {
+            Task::none()
+        };
+        // the cursor moves change no note, so there is nothing to publish
+        let is_cursor_move = matches!(message, Message::CursorMoved(..));
+        let task = wake_up.chain(self.on_message(message));
+        if is_cursor_move {
+            return task;
+        }
+        let changes = self.notes.take_change_log();
+        let update_titles = if changes.is_empty() {
+            Task::none()
+        } else {
+            tracing::trace!("notes changed: {changes:?}");
+            self.update_window_titles(&changes)
+        };
+        // let the applet know about changes if any
+        self.publish_state();
+        task.chain(update_titles)
+    }
+
+    /// Called when a window is resized.
+    fn on_window_resize(&mut self, id: window::Id, width: f32, height: f32) {
+        if self.sticky_windows.contains(id) {
+            let minimum = self.config.sticky_window_minimum();
+            match self.try_get_note_mut(id) {
+                Ok(note) => {
+                    note.set_size(
+                        to_usize(width).max(minimum.width),
+                        to_usize(height).max(minimum.height),
+                    );
+                }
+                Err(e) => tracing::error!("failed to update sticky window size: {e}"),
+            }
+        }
+    }
+
+    /// Called when the escape key is pressed.
+    fn on_escape(&mut self) -> Task<cosmic::Action<Self::Message>> {
+        // discard edited content, the stored note content remains unchanged
+        if let Some(window_id) = self.core.focused_window()
+            && let Some(window) = self.sticky_windows.get_mut(window_id)
+            && let Err(e) = window.cancel_edit()
+        {
+            tracing::error!("failed cancelling edit: {e}");
+        }
+        Task::none()
+    }
+
+    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
+        Some(applet::style())
+    }
+}
+
+impl ServiceModel {
+    #[allow(clippy::too_many_lines)]
+    fn on_message(&mut self, message: Message) -> Task<cosmic::Action<Message>> {
         match message {
             Message::Signal(command) => {
                 return self.on_signal(&command);
@@ -313,6 +740,14 @@ impl cosmic::Application for ServiceModel {
 
             Message::UpdateConfig(config) => {
                 self.config = config;
+                return self.update_spell_checker();
+            }
+
+            Message::SpellCheckerLoaded(spell_checker) => {
+                if self.config.spellcheck {
+                    self.spell_checker = Some(spell_checker);
+                    self.check_spelling_of_edited();
+                }
             }
 
             Message::DbusActivation(msg) => {
@@ -320,16 +755,180 @@ impl cosmic::Application for ServiceModel {
                 return Self::on_dbus_activation_message(msg);
             }
 
+            Message::DbusServeFailed(e) => {
+                // the commands never reach the instance, so the running one is left alone with the notes
+                tracing::error!("failed serving DBus as {}: {e}", self.app_id);
+                return iced::exit();
+            }
+
             Message::Ignore => {}
 
+            Message::SaveFailed(error) => {
+                self.save_error = Some(error.clone());
+                return Self::notify(fl!("save-failed-title"), error, Message::Ignore);
+            }
+
+            Message::ExitFailed => {
+                // let the caller know the notes might be lost, see exit_code()
+                EXIT_CODE.store(1, Ordering::Relaxed);
+                return iced::exit();
+            }
+
+            Message::SetHighContrast(high_contrast) => {
+                if let Err(e) = self.save_high_contrast(high_contrast) {
+                    tracing::error!("failed saving high contrast mode: {e}");
+                }
+            }
+
             Message::SetDefaultStyle(style_index) => {
                 if let Err(e) = self.notes.try_set_default_style_by_index(style_index) {
                     tracing::error!("failed changing default style: {e}");
                 }
             }
 
+            Message::SetScreenDefaultStyle(style_index) => {
+                if let Some(context) = self.screen_context()
+                    && let Err(e) = self
+                        .notes
+                        .try_set_default_style_for_by_index(&context, style_index)
+                {
+                    tracing::error!("failed changing default style of screen {context}: {e}");
+                }
+            }
+
+            Message::ResetScreenDefaultStyle => {
+                if let Some(context) = self.screen_context() {
+                    self.notes.reset_default_style_for(&context);
+                }
+            }
+
+            Message::TemplateNew => {
+                let name = fl!(
+                    "new-template-name",
+                    index = self.notes.templates().len() + 1
+                );
+                let style_id = self.notes.default_style_for(None);
+                self.notes
+                    .add_template(NoteTemplate::new(name, String::new(), style_id));
+                // the new template is empty, so it is to be filled
+                self.template_editor = Some((
+                    self.notes.templates().len() - 1,
+                    widget::text_editor::Content::new(),
+                ));
+            }
+
+            Message::TemplateNameInput(index, name) => match self.notes.try_get_template_mut(index)
+            {
+                Ok(template) => template.set_name(&name),
+                Err(e) => tracing::error!("failed to rename template: {e}"),
+            },
+
+            Message::TemplateEditContent(index) => {
+                if self
+                    .template_editor
+                    .as_ref()
+                    .is_some_and(|(edited, _)| *edited == index)
+                {
+                    self.template_editor = None;
+                } else {
+                    match self.notes.templates().get(index) {
+                        Some(template) => {
+                            self.template_editor = Some((
+                                index,
+                                widget::text_editor::Content::with_text(template.get_content()),
+                            ));
+                        }
+                        None => tracing::error!("template {index} is not found to edit"),
+                    }
+                }
+            }
+
+            Message::TemplateContentEdit(action) => {
+                if let Some((index, content)) = &mut self.template_editor {
+                    let is_edit = action.is_edit();
+                    content.perform(action);
+                    if is_edit {
+                        match self.notes.try_get_template_mut(*index) {
+                            Ok(template) => template.set_content(&content.text()),
+                            Err(e) => tracing::error!("failed to change template content: {e}"),
+                        }
+                    }
+                }
+            }
+
+            Message::TemplateStyleSelected(index, style_index) => {
+                let style_id = self
+                    .notes
+                    .iter_styles()
+                    .nth(style_index)
+                    .map(|(style_id, _)| *style_id);
+                match (self.notes.try_get_template_mut(index), style_id) {
+                    (Ok(template), Some(style_id)) => template.set_style(style_id),
+                    (Err(e), _) => tracing::error!("failed to change template style: {e}"),
+                    (_, None) => tracing::error!("style index {style_index} is not found"),
+                }
+            }
+
+            Message::TemplateDelete(index) => {
+                if let Err(e) = self.notes.delete_template(index) {
+                    tracing::error!("failed to delete template: {e}");
+                } else {
+                    // the next templates are shifted to fill the gap
+                    self.template_editor = self
+                        .template_editor
+                        .take()
+                        .filter(|(edited, _)| *edited != index)
+                        .map(|(edited, content)| {
+                            (if edited > index { edited - 1 } else { edited }, content)
+                        });
+                }
+            }
+
+            Message::SetAllLocked(is_on) => {
+                // unlocking stops click-through of notes, so they should intercept clicks again
+                let click_through_windows: Vec<Id> = self
+                    .sticky_windows
+                    .iter()
+                    .filter(|(id, _)| {
+                        self.sticky_windows
+                            .try_get_note(*id, &self.notes)
+                            .is_ok_and(NoteData::is_click_through)
+                    })
+                    .map(|(id, _)| id)
+                    .collect();
+                self.notes.for_each_note_mut(|note| note.set_locking(is_on));
+                if !is_on {
+                    return cosmic::task::batch(
+                        click_through_windows
+                            .into_iter()
+                            .map(window::disable_mouse_passthrough),
+                    );
+                }
+            }
+
             Message::LoadNotesCompleted(imported) => {
-                self.notes = imported;
+                self.notes.replace(imported);
+                self.template_editor = None;
+                return cosmic::task::batch(self.spawn_sticky_windows());
+            }
+
+            Message::MergeImport => {
+                let import_file = self.config.import_file.clone();
+                return cosmic::task::future(Self::import_notes(
+                    import_file,
+                    self.config.clone(),
+                    Message::MergeNotesCompleted,
+                ));
+            }
+
+            Message::MergeNotesCompleted(imported) => {
+                let style_match = if self.config.merge_styles_by_name {
+                    StyleMatch::ByName
+                } else {
+                    StyleMatch::ById
+                };
+                self.notes
+                    .merge(imported, MergeStrategy::KeepNewer, style_match);
                 return cosmic::task::batch(self.spawn_sticky_windows());
             }
 
@@ -345,15 +944,103 @@ impl cosmic::Application for ServiceModel {
                 tracing::error!("failed exporting notes: {msg}");
             }
 
+            Message::ExportPathChecked(warning) => {
+                if let Some(reason) = &warning {
+                    tracing::warn!("notes can't be exported: {reason}");
+                }
+                self.export_warning = warning;
+            }
+
+            Message::PickImportFile => {
+                return cosmic::task::future(Self::pick_import_file());
+            }
+
+            Message::PickExportFile(format) => {
+                return cosmic::task::future(Self::pick_export_file(format));
+            }
+
+            Message::PickKeepImport => {
+                return cosmic::task::future(Self::pick_keep_import());
+            }
+
+            Message::ImportKeepFromPath(path) => {
+                // imported notes are added to existing ones
+                return cosmic::task::future(Self::import_keep_notes(path));
+            }
+
+            Message::PickValidateFile => {
+                return cosmic::task::future(Self::pick_validate_file());
+            }
+
+            Message::ValidateFromPath(path) => {
+                return cosmic::task::future(Self::validate_notes_file(path));
+            }
+
+            Message::ValidationCompleted(path, report) => {
+                self.validation = Some((path, report));
+                return self.spawn_validation_window();
+            }
+
+            Message::ImportFromPath(import_file) => {
+                if self.notes.is_unsaved() {
+                    // todo: ask to overwrite unsaved notes
+                    tracing::error!("drop unsaved changes while importing collection");
+                }
+                return cosmic::task::future(Self::import_notes(
+                    import_file,
+                    self.config.clone(),
+                    Message::LoadNotesCompleted,
+                ));
+            }
+
+            Message::ExportToPath(export_file, format) => {
+                let notes = self.notes.clone();
+                return cosmic::task::future(Self::export_notes(export_file, notes, format));
+            }
+
             // message related to windows management
             Message::StickyWindowCreated(id, note_id) => {
-                self.sticky_windows.insert(
-                    id,
-                    StickyWindow::new(note_id, self.config.toolbar_icon_size),
-                );
-                if let Ok(note) = self.notes.try_get_note(&note_id) {
-                    return self.set_window_title(note.get_title().to_string(), id);
-                }
+                // the note may override the icon size configured for all notes
+                let icon_size = self
+                    .notes
+                    .try_get_note(&note_id)
+                    .ok()
+                    .and_then(NoteData::get_icon_size)
+                    .unwrap_or(self.config.toolbar_icon_size);
+                self.spawning_notes.remove(&note_id);
+                self.sticky_windows
+                    .insert(id, StickyWindow::new(note_id, icon_size));
+                let click_through = self
+                    .notes
+                    .try_get_note(&note_id)
+                    .is_ok_and(NoteData::is_click_through);
+                let set_title = match self.build_window_title(note_id) {
+                    Ok(title) => {
+                        if let Some(sticky_window) = self.sticky_windows.get_mut(id) {
+                            sticky_window.update_title(&title);
+                        }
+                        self.set_window_title(title, id)
+                    }
+                    Err(e) => {
+                        tracing::error!("failed to set sticky window title: {e}");
+                        Task::none()
+                    }
+                };
+                let set_title = if click_through {
+                    set_title.chain(window::enable_mouse_passthrough(id))
+                } else {
+                    set_title
+                };
+                let set_title = if self.focus_on_spawn == Some(note_id) {
+                    self.focus_on_spawn = None;
+                    set_title.chain(window::gain_focus(id))
+                } else {
+                    set_title
+                };
+                // learn the screen size to place new notes and test the note is visible on the screen
+                return set_title.chain(window::monitor_size(id).map(move |size| {
+                    cosmic::Action::App(Message::MonitorSizeResponse((id, size)))
+                }));
             }
 
             Message::RestoreWindowCreated(id) => {
@@ -364,6 +1051,39 @@ impl cosmic::Application for ServiceModel {
                 return self.set_window_title(fl!("recently-deleted-title"), id);
             }
 
+            Message::UndoToastCreated(id, note_id) => {
+                // auto-dismiss the toast
+                let expire = cosmic::task::future(async move {
+                    tokio::time::sleep(UNDO_TOAST_TIMEOUT).await;
+                    Message::UndoToastExpired(id)
+                });
+                if let Some((existing_window_id, _)) = self.undo_toast.replace((id, note_id)) {
+                    tracing::warn!("replacing existing undo toast with new one");
+                    return cosmic::task::batch([window::close(existing_window_id), expire]);
+                }
+                return expire;
+            }
+
+            Message::UndoToastExpired(id) => {
+                return self.close_undo_toast(|window_id, _| window_id == id);
+            }
+
+            Message::HiddenWindowCreated(id) => {
+                if self.hidden_window_id.is_some() {
+                    tracing::warn!("replacing existing hidden notes window ID with new one");
+                }
+                self.hidden_window_id = Some(id);
+                return self.set_window_title(fl!("hidden-notes-title"), id);
+            }
+
+            Message::OverviewWindowCreated(id) => {
+                if self.overview_window_id.is_some() {
+                    tracing::warn!("replacing existing overview window ID with new one");
+                }
+                self.overview_window_id = Some(id);
+                return self.set_window_title(fl!("overview-title"), id);
+            }
+
             Message::SettingsWindowCreated(id) => {
                 if self.settings_window_id.is_some() {
                     tracing::warn!("replacing existing settings window ID with new one");
@@ -372,16 +1092,30 @@ impl cosmic::Application for ServiceModel {
                 return self.set_window_title(fl!("settings-title"), id);
             }
 
-            Message::EditStyleWindowCreated(window_id, style_id) => {
+            Message::EditStyleWindowCreated(window_id, style_id, is_new) => {
                 match self.notes.try_get_style(&style_id) {
                     Ok(style) => {
                         if self.edit_style.is_some() {
                             tracing::warn!("replacing existing edit style dialog with new one");
                         }
-                        self.edit_style = Some((window_id, EditStyleDialog::new(style_id, style)));
-                        return self.set_window_title(fl!("create-new-style"), window_id);
+                        self.edit_style = Some((
+                            window_id,
+                            EditStyleDialog::new(
+                                style_id,
+                                style,
+                                is_new,
+                                self.config.font_size_range(),
+                                self.config.recent_colors(),
+                            ),
+                        ));
+                        let title = if is_new {
+                            fl!("create-new-style")
+                        } else {
+                            fl!("edit-style-title")
+                        };
+                        return self.set_window_title(title, window_id);
                     }
-                    Err(e) => eprint!("Failed to edit style: {e}"),
+                    Err(e) => tracing::error!("failed to edit style: {e}"),
                 }
             }
 
@@ -393,12 +1127,29 @@ impl cosmic::Application for ServiceModel {
                 return self.set_window_title(fl!("about-title"), id);
             }
 
+            Message::ValidationWindowCreated(id) => {
+                if self.validation_window_id.is_some() {
+                    tracing::warn!("replacing existing validation window ID with new one");
+                }
+                self.validation_window_id = Some(id);
+                return self.set_window_title(fl!("validation-title"), id);
+            }
+
             // redirect edit actions to the edit context
             Message::Edit(window_id, action) => {
-                if let Some(sticky_window) = self.sticky_windows.get_mut(&window_id)
-                    && let Err(e) = sticky_window.do_edit_action(action)
-                {
-                    tracing::error!("failed perform edit: {e}");
+                if let Some(sticky_window) = self.sticky_windows.get_mut(window_id) {
+                    let is_edit = action.is_edit();
+                    if let Err(e) = sticky_window.do_edit_action(action) {
+                        tracing::error!("failed perform edit: {e}");
+                    } else if is_edit {
+                        sticky_window.check_spelling(self.spell_checker.as_deref());
+                    }
+                }
+            }
+
+            Message::NoteResizeStart(id) => {
+                if self.sticky_windows.contains(id) {
+                    return window::drag_resize(id, window::Direction::SouthEast);
                 }
             }
 
@@ -406,6 +1157,10 @@ impl cosmic::Application for ServiceModel {
                 return self.on_mouse_event(id, &event);
             }
 
+            Message::AppKeyPressed((id, key)) => {
+                return self.on_key_pressed(id, &key);
+            }
+
             Message::AppWindowEvent((id, event)) => {
                 return self.on_window_event(id, &event);
             }
@@ -419,10 +1174,29 @@ impl cosmic::Application for ServiceModel {
                 }
             }
 
+            Message::MonitorSizeResponse((id, size)) => {
+                if let Some(size) = size {
+                    return self.on_monitor_size(id, size);
+                }
+            }
+
+            Message::OutputChanged(output_id, output) => {
+                return self.on_output_changed(output_id, output);
+            }
+
             Message::NoteLock(id, is_on) => {
-                self.on_change_note_locking(id, is_on);
+                return self.on_change_note_locking(id, is_on);
+            }
+
+            Message::NoteClickThrough(id, is_on) => {
+                return self.on_change_note_click_through(id, is_on);
             }
 
+            Message::NoteOpacity(id, opacity) => match self.try_get_note_mut(id) {
+                Ok(note) => note.set_opacity(opacity),
+                Err(e) => tracing::error!("failed to change note opacity: {e}"),
+            },
+
             Message::NoteEdit(id, is_on) => {
                 if is_on {
                     self.on_start_edit(id);
@@ -432,7 +1206,7 @@ impl cosmic::Application for ServiceModel {
             }
 
             Message::NoteStyle(id) => {
-                if let Some(sticky_window) = self.sticky_windows.get_mut(&id) {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id) {
                     sticky_window.allow_select_style(self.notes.get_style_names());
                 } else {
                     tracing::error!("{id}: sticky window is not found to change style");
@@ -444,115 +1218,384 @@ impl cosmic::Application for ServiceModel {
             }
 
             Message::NoteNew => {
-                return self.on_new_note_window();
+                return self.on_new_note_window(None);
             }
 
-            Message::NoteDelete(id) => {
-                return self.on_delete_note(id);
+            Message::NoteNewAtCursor => {
+                return self.on_new_note_window(self.cursor_position);
             }
 
-            Message::NoteRestore(note_id) => {
-                return self.on_restore_note(note_id);
+            Message::NoteNewFromTemplate(index) => {
+                return self.on_new_note_from_template(index);
             }
 
-            Message::StyleNew => {
-                return self.on_new_style();
+            Message::PasteNote => {
+                return cosmic::iced::clipboard::read()
+                    .map(|text| cosmic::Action::App(Message::PasteNoteText(text)));
             }
 
-            Message::StyleEdit(style_id) => {
-                return self.spawn_edit_style_window(style_id);
+            Message::PasteNoteText(text) => {
+                return self.on_paste_note(text.as_deref().unwrap_or_default());
             }
 
-            Message::StyleDelete(style_id) => {
-                self.on_delete_style(style_id);
+            Message::CursorMoved(id, position) => {
+                // the global cursor position is unknown, so it is tracked over sticky windows only
+                self.cursor_position =
+                    self.sticky_windows
+                        .try_get_note(id, &self.notes)
+                        .ok()
+                        .map(|note| {
+                            (
+                                note.left() + to_usize(position.x),
+                                note.top() + to_usize(position.y),
+                            )
+                        });
             }
 
-            Message::EditStyleUpdate => {
-                if let Some((window_id, dialog)) = self.edit_style.take() {
-                    self.on_style_updated(
-                        dialog.get_id(),
-                        dialog.get_name(),
-                        dialog.get_font(),
-                        dialog.get_background_color(),
-                    );
-                    return window::close(window_id);
-                }
+            Message::NoteDelete(id) => {
+                return self.on_delete_note(id);
             }
 
-            Message::EditStyleCancel => {
-                if let Some((window_id, dialog)) = self.edit_style.take() {
-                    if let Err(e) = self.notes.delete_style(dialog.get_id()) {
-                        tracing::error!("failed to delete new style: {e}");
-                    }
-                    return window::close(window_id);
-                }
+            Message::NoteHide(id) => {
+                return self.on_hide_note(id);
             }
 
-            Message::InputStyleName(value) => {
-                if let Some((_window_id, dialog)) = &mut self.edit_style {
-                    dialog.update_name(value);
+            Message::NoteUndo(id) => match self.try_get_note_mut(id) {
+                Ok(note) => {
+                    note.undo_content();
                 }
+                Err(e) => tracing::error!("[{id}] failed to undo edit: {e}"),
+            },
+
+            Message::NoteClearHistory(id) => match self.try_get_note_mut(id) {
+                Ok(note) => note.clear_history(),
+                Err(e) => tracing::error!("failed to clear note history: {e}"),
+            },
+
+            Message::NoteShow(note_id) => {
+                return self.on_show_note(note_id);
             }
 
-            Message::ColorUpdate(event) => {
-                if let Some((_window_id, dialog)) = &mut self.edit_style {
-                    return dialog.on_color_picker_update(event);
-                }
+            Message::NoteRestore(note_id) => {
+                return self.on_restore_note(note_id);
             }
 
-            Message::FontStyleUpdate(font_style) => {
+            Message::NoteReminderInput(id, reminder) => {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id)
+                    && let Err(e) = sticky_window.update_reminder(reminder)
+                {
+                    tracing::error!("[{id}] failed to update reminder: {e}");
+                }
+            }
+
+            Message::NoteAttachmentInput(id, attachment) => {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id)
+                    && let Err(e) = sticky_window.update_attachment(attachment)
+                {
+                    tracing::error!("[{id}] failed to update attachment: {e}");
+                }
+            }
+
+            Message::NoteAttachmentAdd(id) => {
+                if let Ok((sticky_window, note)) = self
+                    .sticky_windows
+                    .try_get_with_note_mut(id, &mut self.notes)
+                {
+                    match sticky_window.take_attachment() {
+                        Ok(attachment) => note.add_attachment(attachment),
+                        Err(e) => tracing::error!("[{id}] failed to add attachment: {e}"),
+                    }
+                }
+            }
+
+            Message::NoteAttachmentRemove(id, index) => {
+                if let Ok(note) = self.try_get_note_mut(id) {
+                    note.remove_attachment(index);
+                }
+            }
+
+            Message::NoteIconSize(id, icon_size) => {
+                if let Ok((sticky_window, note)) = self
+                    .sticky_windows
+                    .try_get_with_note_mut(id, &mut self.notes)
+                {
+                    note.set_icon_size(sticky_window.set_icon_size(icon_size));
+                }
+            }
+
+            Message::NoteCopyContent(id) => {
+                return self.on_copy_note_content(id);
+            }
+
+            Message::NoteCopiedExpired(id) => {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id) {
+                    sticky_window.set_copied(false);
+                }
+            }
+
+            Message::NoteCopyAsJson(id) => {
+                return self.on_copy_note_as_json(id);
+            }
+
+            Message::NoteReplaceToggle(id) => {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id)
+                    && let Err(e) = sticky_window.toggle_replace()
+                {
+                    tracing::error!("[{id}] failed to toggle find & replace: {e}");
+                }
+            }
+
+            Message::NoteFindInput(id, find) => {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id)
+                    && let Err(e) = sticky_window.update_find(find)
+                {
+                    tracing::error!("[{id}] failed to update text to find: {e}");
+                }
+            }
+
+            Message::NoteReplaceInput(id, replace) => {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id)
+                    && let Err(e) = sticky_window.update_replace(replace)
+                {
+                    tracing::error!("[{id}] failed to update replacement: {e}");
+                }
+            }
+
+            Message::NoteReplaceCaseSensitive(id, on) => {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id)
+                    && let Err(e) = sticky_window.set_case_sensitive(on)
+                {
+                    tracing::error!("[{id}] failed to change case sensitivity: {e}");
+                }
+            }
+
+            Message::NoteReplaceAll(id, find, replace) => {
+                if let Some(sticky_window) = self.sticky_windows.get_mut(id) {
+                    match sticky_window.replace_all(&find, &replace) {
+                        Ok(count) => {
+                            tracing::debug!("[{id}] replaced {count} occurrences");
+                            sticky_window.check_spelling(self.spell_checker.as_deref());
+                        }
+                        Err(e) => tracing::error!("[{id}] failed to replace: {e}"),
+                    }
+                }
+            }
+
+            Message::CheckIdle => {
+                return self.on_check_idle();
+            }
+
+            Message::CheckReminders => {
+                return self.on_check_reminders();
+            }
+
+            Message::RestoreSelectMove(delta) => {
+                self.on_restore_select_move(delta);
+            }
+
+            Message::RestoreSelected => {
+                if let Some(note_id) = self.restore_selection {
+                    return self.on_restore_note(note_id);
+                }
+            }
+
+            Message::OverviewNoteSelected(note_id) => {
+                return self.raise_note_window(note_id);
+            }
+
+            Message::OverviewNoteToggled(note_id, on) => {
+                if on {
+                    self.overview_selection.insert(note_id);
+                } else {
+                    self.overview_selection.remove(&note_id);
+                }
+            }
+
+            Message::ExportSelected(note_ids) => {
+                let export_file = self.config.export_path();
+                let notes = self.notes.subset(&note_ids);
+                return cosmic::task::future(Self::export_notes(
+                    export_file,
+                    notes,
+                    ExportFormat::IndicatorStickyNotes,
+                ));
+            }
+
+            Message::BulkApply(note_ids, action) => {
+                return self.on_bulk_apply(&note_ids, action);
+            }
+
+            Message::StyleNew => {
+                return self.on_new_style();
+            }
+
+            Message::StyleEdit(style_id) => {
+                return self.spawn_edit_style_window(style_id, false);
+            }
+
+            Message::StyleDuplicate(style_id) => {
+                return self.on_duplicate_style(style_id);
+            }
+
+            Message::StyleDelete(style_id) => {
+                return self.on_delete_style(style_id);
+            }
+
+            Message::StyleDeleteConfirm(style_id) => {
+                self.delete_style_confirm = None;
+                self.delete_style(style_id);
+            }
+
+            Message::StyleDeleteCancel => {
+                self.delete_style_confirm = None;
+            }
+
+            Message::StylesGc => {
+                self.gc_styles_confirm = true;
+            }
+
+            Message::StylesGcConfirm => {
+                self.gc_styles_confirm = false;
+                self.gc_styles();
+            }
+
+            Message::StylesGcCancel => {
+                self.gc_styles_confirm = false;
+            }
+
+            Message::StyleGroupToggle(group) => {
+                if !self.collapsed_style_groups.remove(&group) {
+                    self.collapsed_style_groups.insert(group);
+                }
+            }
+
+            Message::ReassignStyleTarget(style_id, target_index) => {
+                self.reassign_targets.insert(style_id, target_index);
+            }
+
+            Message::ReassignStyle(style_id, target_index) => {
+                self.reassign_targets.remove(&style_id);
+                if let Some(target_id) = self
+                    .notes
+                    .iter_styles()
+                    .nth(target_index)
+                    .map(|(id, _)| *id)
+                {
+                    let count = self.notes.reassign_notes_style(style_id, target_id);
+                    tracing::info!("{count} notes moved from style {style_id} to {target_id}");
+                } else {
+                    tracing::error!(
+                        "failed to reassign notes: {}",
+                        NotesCollectionError::StyleIndexNotFound(target_index)
+                    );
+                }
+            }
+
+            Message::EditStyleUpdate => {
+                if let Some((window_id, dialog)) = self.edit_style.take() {
+                    self.on_style_updated(&dialog);
+                    if let Err(e) = self.save_recent_color(dialog.get_background_color()) {
+                        tracing::error!("failed saving recent colors: {e}");
+                    }
+                    return window::close(window_id);
+                }
+            }
+
+            Message::EditStyleCancel => {
+                if let Some((window_id, dialog)) = self.edit_style.take() {
+                    // the picked color is applied on Ok only, so nothing has leaked into the style
+                    debug_assert!(
+                        self.notes
+                            .try_get_style(&dialog.get_id())
+                            .ok()
+                            .is_none_or(|style| style.get_background_color()
+                                == dialog.get_original_background_color()),
+                        "edited style color changed before Ok"
+                    );
+                    self.discard_edit_style(&dialog);
+                    return window::close(window_id);
+                }
+            }
+
+            Message::InputStyleName(value) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    dialog.update_name(value);
+                }
+            }
+
+            Message::InputStyleGroup(value) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    dialog.update_group(value);
+                }
+            }
+
+            Message::InputStyleBackgroundImage(value) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    dialog.update_background_image(value);
+                }
+            }
+
+            Message::ColorUpdate(event) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    return dialog.on_color_picker_update(event);
+                }
+            }
+
+            Message::FontStyleUpdate(font_style) => {
                 if let Some((_window_id, dialog)) = &mut self.edit_style {
                     dialog.update_font_style(font_style);
                 }
             }
 
+            Message::FontFamilyUpdate(family) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    dialog.update_font_family(family);
+                }
+            }
+
             Message::FontSizeUpdate(font_size) => {
                 if let Some((_window_id, dialog)) = &mut self.edit_style {
                     dialog.update_font_size(font_size);
                 }
             }
 
+            Message::TextAlignUpdate(text_align) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    dialog.update_text_align(text_align);
+                }
+            }
+
+            Message::CornerRadiusUpdate(corner_radius) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    dialog.update_corner_radius(corner_radius);
+                }
+            }
+
+            Message::BorderWidthUpdate(border_width) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    dialog.update_border_width(border_width);
+                }
+            }
+
+            Message::BorderColorInput(text) => {
+                if let Some((_window_id, dialog)) = &mut self.edit_style {
+                    dialog.update_border_color(text);
+                }
+            }
+
             Message::OpenUrl(url) => match open::that_detached(&url) {
                 Ok(()) => tracing::debug!("go to URL {url}"),
                 Err(err) => tracing::error!("failed to open {url:?}: {err}"),
             },
-        }
-        Task::none()
-    }
-
-    /// Called when a window is resized.
-    fn on_window_resize(&mut self, id: window::Id, width: f32, height: f32) {
-        if self.sticky_windows.contains_key(&id) {
-            let minimum = self.config.sticky_window_minimum();
-            match self.try_get_note_mut(id) {
-                Ok(note) => {
-                    note.set_size(
-                        to_usize(width).max(minimum.width),
-                        to_usize(height).max(minimum.height),
-                    );
-                }
-                Err(e) => tracing::error!("failed to update sticky window size: {e}"),
-            }
-        }
-    }
 
-    /// Called when the escape key is pressed.
-    fn on_escape(&mut self) -> Task<cosmic::Action<Self::Message>> {
-        if let Some(window_id) = self.core.focused_window()
-            && let Some(window) = self.sticky_windows.get_mut(&window_id)
-            && let Err(e) = window.finish_edit()
-        {
-            tracing::error!("failed cancelling edit: {e}");
+            Message::OpenPath(path) => match open::that_detached(&path) {
+                Ok(()) => tracing::debug!("open file {path}"),
+                Err(err) => tracing::error!("failed to open {path:?}: {err}"),
+            },
         }
         Task::none()
     }
 
-    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
-        Some(applet::style())
-    }
-}
-
-impl ServiceModel {
     fn on_signal(&mut self, command: &Command) -> Task<cosmic::Action<Message>> {
         tracing::trace!("handling command {command}");
         match command {
@@ -560,8 +1603,27 @@ impl ServiceModel {
                 // nothing to do
             }
 
+            Command::NewNote => {
+                return Task::done(cosmic::Action::App(Message::NoteNew));
+            }
+
+            Command::NewNoteAtCursor => {
+                return Task::done(cosmic::Action::App(Message::NoteNewAtCursor));
+            }
+
+            Command::NewNoteFromTemplate(index) => {
+                return Task::done(cosmic::Action::App(Message::NoteNewFromTemplate(index)));
+            }
+
+            Command::PasteNote => {
+                return Task::done(cosmic::Action::App(Message::PasteNote));
+            }
+
             Command::Quit => {
-                self.on_quit();
+                if let Err(e) = self.on_quit() {
+                    // notify before exit as nobody reads the log
+                    return Self::notify(fl!("save-failed-title"), e, Message::ExitFailed);
+                }
                 return iced::exit();
             }
 
@@ -571,14 +1633,32 @@ impl ServiceModel {
                     // todo: ask to overwrite unsaved notes
                     tracing::error!("drop unsaved changes while loading collection");
                 }
-                self.notes = Self::load_notes_or_default(&self.config.notes);
+                self.notes = Self::load_notes_or_default(self.app_id, &self.config);
+            }
+
+            Command::RevertNotes => {
+                // unlike loading it only discards the changes made since the last saving
+                if self.notes.is_unsaved() {
+                    tracing::info!("revert unsaved changes of notes");
+                    self.notes
+                        .replace(Self::load_notes_or_default(self.app_id, &self.config));
+                    // the edited template and style may be gone with the reverted changes
+                    self.template_editor = None;
+                    let close_edit_style = self
+                        .edit_style
+                        .take()
+                        .map_or_else(Task::none, |(edit_style_id, _)| {
+                            window::close(edit_style_id)
+                        });
+                    return close_edit_style
+                        .chain(cosmic::task::batch(self.spawn_sticky_windows()));
+                }
+                tracing::debug!("nothing to revert, notes are saved");
             }
 
             Command::SaveNotes => {
                 //todo: stop editing all sticky windows or ask user
-                if let Err(e) = self.save_notes() {
-                    tracing::error!("failed saving notes: {e}");
-                }
+                return self.on_save_notes();
             }
 
             Command::ImportNotes => {
@@ -588,14 +1668,42 @@ impl ServiceModel {
                 }
                 let import_file = self.config.import_file.clone();
                 // opposite to other cases return real task instead of none()
-                return cosmic::task::future(Self::import_notes(import_file));
+                return cosmic::task::future(Self::import_notes(
+                    import_file,
+                    self.config.clone(),
+                    Message::LoadNotesCompleted,
+                ));
+            }
+
+            Command::MergeNotes => {
+                return Task::done(cosmic::Action::App(Message::MergeImport));
             }
 
             Command::ExportNotes => {
                 //todo: stop editing all sticky windows (?) or ask user about
-                let export_file = self.config.import_file.clone();
+                let export_file = self.config.export_path();
                 let notes = self.notes.clone();
-                return cosmic::task::future(Self::export_notes(export_file, notes));
+                return cosmic::task::future(Self::export_notes(
+                    export_file,
+                    notes,
+                    ExportFormat::IndicatorStickyNotes,
+                ));
+            }
+
+            Command::PickImportFile => {
+                return Task::done(cosmic::Action::App(Message::PickImportFile));
+            }
+
+            Command::PickKeepImport => {
+                return Task::done(cosmic::Action::App(Message::PickKeepImport));
+            }
+
+            Command::PickValidateFile => {
+                return Task::done(cosmic::Action::App(Message::PickValidateFile));
+            }
+
+            Command::PickExportFile(format) => {
+                return Task::done(cosmic::Action::App(Message::PickExportFile(format)));
             }
 
             Command::ShowAllNotes => {
@@ -607,13 +1715,29 @@ impl ServiceModel {
             }
 
             Command::LockAll => {
-                self.notes.for_each_note_mut(|note| note.set_locking(true));
+                return Task::done(cosmic::Action::App(Message::SetAllLocked(true)));
+            }
+
+            Command::UnlockAll => {
+                return Task::done(cosmic::Action::App(Message::SetAllLocked(false)));
+            }
+
+            Command::StopClickThrough => {
+                return self.on_stop_click_through();
             }
 
             Command::RestoreNotes => {
                 return self.spawn_restore_notes_window();
             }
 
+            Command::ShowHiddenNotes => {
+                return self.spawn_hidden_notes_window();
+            }
+
+            Command::OpenOverview => {
+                return self.spawn_overview_window();
+            }
+
             Command::OpenSettings => {
                 return self.spawn_settings_window();
             }
@@ -649,75 +1773,213 @@ impl ServiceModel {
         Task::none()
     }
 
-    fn on_quit(&mut self) {
-        // save changes if any to persistent storage
-        if self.notes.is_unsaved() {
+    fn on_quit(&mut self) -> Result<(), String> {
+        // warn if deleted notes were dropped
+        let count_deleted = self.notes.iter_deleted_notes().count();
+        if count_deleted > 0 {
+            //todo: what about saving deleted notes too? Maybe with their TTLs
+            tracing::warn!("completely drop some deleted notes on exit: {count_deleted}");
+        }
+        // save changes if any to persistent storage, the stacking order of focused notes as well
+        if self.notes.needs_storing() {
             if let Err(e) = self.save_notes() {
                 tracing::error!("failed saving notes on exit: {e}");
-            } else {
-                tracing::info!("notes collection was saved");
+                return Err(e.to_string());
             }
+            tracing::info!("notes collection was saved");
         } else {
             tracing::info!("notes collection is unchanged, skip saving");
         }
-        // warn if deleted notes were dropped
-        let count_deleted = self.notes.iter_deleted_notes().count();
-        if count_deleted > 0 {
-            //todo: what about saving deleted notes too? Maybe with their TTLs
-            tracing::warn!("completely drop some deleted notes on exit: {count_deleted}");
+        Ok(())
+    }
+
+    fn on_save_notes(&mut self) -> Task<cosmic::Action<Message>> {
+        match self.save_notes() {
+            Ok(()) => {
+                self.save_error = None;
+                Task::none()
+            }
+            Err(e) => {
+                tracing::error!("failed saving notes: {e}");
+                Task::done(cosmic::Action::App(Message::SaveFailed(e.to_string())))
+            }
         }
     }
 
-    fn try_get_note_mut(&mut self, window_id: Id) -> Result<&mut NoteData, String> {
+    // shows the desktop notification, then sends the message
+    fn notify(summary: String, body: String, then: Message) -> Task<cosmic::Action<Message>> {
+        cosmic::task::future(async move {
+            match zbus::Connection::session().await {
+                Ok(connection) => {
+                    if let Err(e) = send_notification(&connection, &summary, &body).await {
+                        tracing::warn!("failed sending notification: {e}");
+                    }
+                }
+                Err(e) => tracing::warn!("failed sending notification: {e}"),
+            }
+            then
+        })
+    }
+
+    fn publish_state(&mut self) {
+        let state = State {
+            save_error: self.save_error.clone().unwrap_or_default(),
+            export_warning: self.export_warning.clone().unwrap_or_default(),
+            ..State::from(&self.notes)
+        };
+        if state != self.state
+            && let Some(context) = &self.state_context
+        {
+            if let Err(e) = state.write_entry(context) {
+                tracing::error!("failed publishing state: {e}");
+            }
+            self.state = state;
+        }
+    }
+
+    fn try_get_note_mut(&mut self, window_id: Id) -> Result<&mut NoteData, WindowRegistryError> {
         self.sticky_windows
-            .get(&window_id)
-            .ok_or_else(|| format!("Sticky window {window_id} is not found"))
-            .and_then(|sticky_window| {
-                self.notes
-                    .try_get_note_mut(&sticky_window.get_note_id())
-                    .map_err(|e| e.to_string())
+            .try_get_note_mut(window_id, &mut self.notes)
+    }
+
+    // the titles show the first line of the note and (optionally) its style name,
+    // so the titles of the changed notes and of the notes of the changed styles are updated
+    fn update_window_titles(&mut self, changes: &[ChangeEvent]) -> Task<cosmic::Action<Message>> {
+        let changed_styles: HashSet<Uuid> = changes
+            .iter()
+            .filter_map(|change| match change {
+                ChangeEvent::StyleChanged(style_id) if self.config.title_show_style => {
+                    Some(*style_id)
+                }
+                _ => None,
+            })
+            .collect();
+        let changed_windows: Vec<(Id, Uuid)> = self
+            .sticky_windows
+            .iter()
+            .map(|(window_id, sticky_window)| (window_id, sticky_window.get_note_id()))
+            .filter(|(_, note_id)| {
+                changes.contains(&ChangeEvent::NoteModified(*note_id))
+                    || self
+                        .notes
+                        .try_get_note(note_id)
+                        .is_ok_and(|note| changed_styles.contains(&note.style()))
             })
+            .collect();
+        let mut tasks = Vec::with_capacity(changed_windows.len());
+        for (window_id, note_id) in changed_windows {
+            if let Ok(title) = self.build_window_title(note_id)
+                && let Some(sticky_window) = self.sticky_windows.get_mut(window_id)
+                && sticky_window.update_title(&title)
+            {
+                tasks.push(self.set_window_title(title, window_id));
+            }
+        }
+        cosmic::task::batch(tasks)
     }
 
-    fn load_notes_or_default(json: &str) -> NotesCollection {
-        if json.is_empty() {
-            NotesCollection::default()
+    fn build_window_title(&self, note_id: Uuid) -> Result<String, NotesCollectionError> {
+        let note = self.notes.try_get_note(&note_id)?;
+        if self.config.title_show_style {
+            self.notes
+                .try_get_note_style(note_id)
+                .map(|style| format!("[{}] {}", style.get_name(), note.get_title()))
         } else {
-            NotesCollection::try_read(json)
-                .map_err(|e| {
-                    tracing::error!(
-                        "failed loading notes from {}/v{}/notes: {e}",
-                        <Self as cosmic::Application>::APP_ID,
-                        Config::VERSION
-                    );
-                })
-                .unwrap_or_default()
+            Ok(note.get_title().to_string())
         }
     }
 
+    fn load_notes_or_default(app_id: &str, config: &Config) -> NotesCollection {
+        if config.notes.is_empty() {
+            NotesCollection::with_default_background(config.new_style_color())
+        } else {
+            // the notes are stored separately from the collection itself
+            let context = cosmic_config::Config::new(app_id, Config::VERSION)
+                .map_err(|e| tracing::error!("failed to access notes: {e}"))
+                .ok();
+            NotesCollection::try_read_parts(&config.notes, |key| {
+                context
+                    .as_ref()
+                    .and_then(|context| context.get::<String>(key).ok())
+            })
+            .map_err(|e| {
+                tracing::error!(
+                    "failed loading notes from {app_id}/v{}/notes: {e}",
+                    Config::VERSION
+                );
+            })
+            .unwrap_or_else(|()| NotesCollection::with_default_background(config.new_style_color()))
+        }
+    }
+
+    // the notes unchanged since the last saving are not rewritten
     fn save_notes(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let json = self.notes.try_write()?;
-        let global_config =
-            cosmic_config::Config::new(<Self as cosmic::Application>::APP_ID, Config::VERSION)?;
+        let parts = self.notes.try_write_parts()?;
+        let global_config = cosmic_config::Config::new(self.app_id, Config::VERSION)?;
         let tx = global_config.transaction();
-        tx.set("notes", json)?;
+        for (key, json) in parts.changed {
+            tx.set(&key, json)?;
+        }
+        for key in parts.removed {
+            tx.set(&key, String::new())?;
+        }
+        // the collection refers to the notes, so it is written last
+        tx.set("notes", parts.index)?;
         tx.commit()?;
         self.notes.commit_changes();
         Ok(())
     }
 
-    async fn import_notes(configured_import_file: String) -> Message {
+    fn save_high_contrast(&mut self, high_contrast: bool) -> Result<(), cosmic_config::Error> {
+        let global_config = cosmic_config::Config::new(self.app_id, Config::VERSION)?;
+        self.config
+            .set_high_contrast(&global_config, high_contrast)?;
+        Ok(())
+    }
+
+    fn save_recent_color(&mut self, color: Color) -> Result<(), cosmic_config::Error> {
+        let global_config = cosmic_config::Config::new(self.app_id, Config::VERSION)?;
+        let recent_colors = self.config.with_recent_color(color);
+        self.config
+            .set_recent_colors(&global_config, recent_colors)?;
+        Ok(())
+    }
+
+    async fn import_notes(
+        configured_import_file: String,
+        config: Config,
+        on_completed: fn(NotesCollection) -> Message,
+    ) -> Message {
         if configured_import_file.is_empty() {
             Message::LoadNotesFailed("No import file is set".to_string())
         } else {
             let import_file_owned = configured_import_file.clone();
+            let default_size = config.new_note_size();
             match tokio::task::spawn_blocking(move || {
-                NotesCollection::try_import(import_file_owned)
+                NotesCollection::try_import(
+                    import_file_owned,
+                    (default_size.width, default_size.height),
+                )
             })
             .await
             {
                 Ok(task) => match task.await {
-                    Ok(v) => Message::LoadNotesCompleted(v),
+                    Ok(mut v) => {
+                        // the imported notes may have sizes making windows unusable
+                        let (min, max) = (
+                            config.sticky_window_minimum(),
+                            config.sticky_window_maximum(),
+                        );
+                        v.sanitize((min.width, min.height), (max.width, max.height));
+                        on_completed(v)
+                    }
+                    Err(crate::Error::Collection(NotesCollectionError::ImportFileMissing(
+                        path,
+                    ))) => {
+                        // it is expected if indicator-stickynotes has never been used
+                        tracing::info!("nothing to import, {path} does not exist");
+                        Message::Ignore
+                    }
                     Err(e) => {
                         let msg =
                             format!("failed reading notes from {configured_import_file}: {e}");
@@ -729,21 +1991,41 @@ impl ServiceModel {
         }
     }
 
-    async fn export_notes(configured_export_file: String, notes: NotesCollection) -> Message {
+    async fn import_keep_notes(path: String) -> Message {
+        match NotesCollection::try_import_keep(&path).await {
+            Ok(imported) => Message::MergeNotesCompleted(imported),
+            Err(e) => Message::LoadNotesFailed(format!("failed importing notes from {path}: {e}")),
+        }
+    }
+
+    async fn check_export_path(export_file: String) -> Message {
+        match tokio::task::spawn_blocking(move || find_unwritable(&export_file)).await {
+            Ok(warning) => Message::ExportPathChecked(warning),
+            Err(e) => {
+                tracing::error!("failed checking export file: {e}");
+                Message::Ignore
+            }
+        }
+    }
+
+    async fn export_notes(
+        configured_export_file: String,
+        notes: NotesCollection,
+        format: ExportFormat,
+    ) -> Message {
         if configured_export_file.is_empty() {
             Message::ExportNotesFailed("No export file is set".to_string())
         } else {
             let export_file_owned = configured_export_file.clone();
             match tokio::task::spawn_blocking(move || {
-                NotesCollection::try_export(export_file_owned, notes)
+                NotesCollection::try_export_as(export_file_owned, notes, format)
             })
             .await
             {
                 Ok(task) => match task.await {
                     Ok(()) => Message::ExportNotesCompleted,
                     Err(e) => {
-                        let msg =
-                            format!("failed reading notes from {configured_export_file}: {e}");
+                        let msg = format!("failed writing notes to {configured_export_file}: {e}");
                         Message::ExportNotesFailed(msg)
                     }
                 },
@@ -752,16 +2034,171 @@ impl ServiceModel {
         }
     }
 
-    fn on_new_note_window(&mut self) -> Task<cosmic::Action<Message>> {
-        let note_id = self.notes.new_note();
-        match self.notes.try_get_note(&note_id) {
+    async fn pick_import_file() -> Message {
+        match file_chooser::open::Dialog::new()
+            .title(fl!("import-from-title"))
+            .open_file()
+            .await
+        {
+            Ok(response) => match response.url().to_file_path() {
+                Ok(path) => Message::ImportFromPath(path.display().to_string()),
+                Err(()) => Message::LoadNotesFailed(format!(
+                    "cannot import from {}: not a local file",
+                    response.url()
+                )),
+            },
+            // user changed their mind, nothing to do
+            Err(file_chooser::Error::Cancelled) => Message::Ignore,
+            Err(e) => Message::LoadNotesFailed(format!("failed choosing file to import: {e}")),
+        }
+    }
+
+    // Google Keep Takeout is the directory of note files
+    async fn pick_keep_import() -> Message {
+        match file_chooser::open::Dialog::new()
+            .title(fl!("import-keep-title"))
+            .open_folder()
+            .await
+        {
+            Ok(response) => match response.url().to_file_path() {
+                Ok(path) => Message::ImportKeepFromPath(path.display().to_string()),
+                Err(()) => Message::LoadNotesFailed(format!(
+                    "cannot import from {}: not a local directory",
+                    response.url()
+                )),
+            },
+            // user changed their mind, nothing to do
+            Err(file_chooser::Error::Cancelled) => Message::Ignore,
+            Err(e) => {
+                Message::LoadNotesFailed(format!("failed choosing Keep notes to import: {e}"))
+            }
+        }
+    }
+
+    async fn pick_validate_file() -> Message {
+        match file_chooser::open::Dialog::new()
+            .title(fl!("validate-file-title"))
+            .open_file()
+            .await
+        {
+            Ok(response) => match response.url().to_file_path() {
+                Ok(path) => Message::ValidateFromPath(path.display().to_string()),
+                Err(()) => Message::LoadNotesFailed(format!(
+                    "cannot validate {}: not a local file",
+                    response.url()
+                )),
+            },
+            // user changed their mind, nothing to do
+            Err(file_chooser::Error::Cancelled) => Message::Ignore,
+            Err(e) => Message::LoadNotesFailed(format!("failed choosing file to validate: {e}")),
+        }
+    }
+
+    // the file is only read, the notes in use are not affected whatever it contains
+    async fn validate_notes_file(path: String) -> Message {
+        let report = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => NotesCollection::validate_json(&content).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        Message::ValidationCompleted(path, report)
+    }
+
+    async fn pick_export_file(format: ExportFormat) -> Message {
+        let extension = format.extension();
+        match file_chooser::save::Dialog::new()
+            .title(fl!("export-to-title"))
+            .file_name(format!("{EXPORT_FILE_STEM}.{extension}"))
+            .filter(
+                file_chooser::FileFilter::new(format!("*.{extension}"))
+                    .glob(format!("*.{extension}")),
+            )
+            .save_file()
+            .await
+        {
+            Ok(response) => match response.url().map(|url| url.to_file_path()) {
+                Some(Ok(path)) => Message::ExportToPath(path.display().to_string(), format),
+                Some(Err(())) | None => {
+                    Message::ExportNotesFailed("cannot export: not a local file".to_string())
+                }
+            },
+            // user changed their mind, nothing to do
+            Err(file_chooser::Error::Cancelled) => Message::Ignore,
+            Err(e) => Message::ExportNotesFailed(format!("failed choosing file to export: {e}")),
+        }
+    }
+
+    // the screen of the recently created sticky window is known by its output name, e.g. "DP-1",
+    // it falls back to the size of the screen if the compositor doesn't report the name or position
+    // of the screen, so the screens of the same size share the default style then
+    fn screen_context(&self) -> Option<String> {
+        self.screen_name.clone().or_else(|| {
+            self.screen_size
+                .map(|size| format!("{}x{}", size.width, size.height))
+        })
+    }
+
+    fn on_new_note_window(
+        &mut self,
+        position: Option<(usize, usize)>,
+    ) -> Task<cosmic::Action<Message>> {
+        let note_id = self.notes.new_note_for(self.screen_context().as_deref());
+        self.spawn_new_note_window(note_id, position)
+    }
+
+    fn on_new_note_from_template(&mut self, index: usize) -> Task<cosmic::Action<Message>> {
+        match self.notes.new_note_from_template(index, &Local::now()) {
+            Ok(note_id) => self.spawn_new_note_window(note_id, None),
+            Err(e) => {
+                tracing::error!("failed to create new note from template: {e}");
+                Task::none()
+            }
+        }
+    }
+
+    fn on_paste_note(&mut self, json: &str) -> Task<cosmic::Action<Message>> {
+        match self.notes.import_single(json) {
+            Ok(note_id) => self.spawn_new_note_window(note_id, None),
+            Err(e) => {
+                tracing::error!("failed to paste note: {e}");
+                Self::notify(fl!("paste-note-failed"), e.to_string(), Message::Ignore)
+            }
+        }
+    }
+
+    // places the new note at the position if any, cascades new notes otherwise
+    fn spawn_new_note_window(
+        &mut self,
+        note_id: Uuid,
+        position: Option<(usize, usize)>,
+    ) -> Task<cosmic::Action<Message>> {
+        let new_note_size = self.config.new_note_size();
+        // shift each next note, so new notes don't hide each other
+        let (left, top) = position.unwrap_or_else(|| {
+            cascade_position(
+                self.notes.get_notes_count().saturating_sub(1),
+                NEW_NOTE_CASCADE_OFFSET,
+                new_note_size,
+                self.screen_size,
+            )
+        });
+        let hide_task = self.hide_notes_over_limit(Some(note_id));
+        match self.notes.try_get_note_mut(&note_id) {
             Ok(note) => {
-                let (window_id, task) =
-                    Self::spawn_sticky_window(note_id, note, self.config.sticky_window_minimum());
-                task.chain(
-                    cosmic::Task::done(Message::NoteEdit(window_id, true))
-                        .map(cosmic::Action::from),
-                )
+                note.set_size(new_note_size.width, new_note_size.height);
+                note.set_position(left, top);
+                let (window_id, task) = Self::spawn_sticky_window(
+                    note_id,
+                    note,
+                    self.config.sticky_window_minimum(),
+                    &mut self.spawning_notes,
+                );
+                cosmic::task::batch([
+                    hide_task,
+                    task.chain(
+                        cosmic::Task::done(Message::NoteEdit(window_id, true))
+                            .map(cosmic::Action::from),
+                    ),
+                ])
             }
             Err(e) => {
                 tracing::error!("failed to create new note: {e}");
@@ -770,12 +2207,68 @@ impl ServiceModel {
         }
     }
 
+    // hides the oldest notes once the shown ones exceed Config::max_live_notes,
+    // the just shown note and edited ones stay shown
+    fn hide_notes_over_limit(
+        &mut self,
+        shown_note_id: Option<Uuid>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(limit) = self.config.live_notes_limit() else {
+            return Task::none();
+        };
+        let keep: Vec<Uuid> = shown_note_id
+            .into_iter()
+            .chain(
+                self.sticky_windows
+                    .iter()
+                    .filter(|(_, w)| w.is_editing())
+                    .map(|(_, w)| w.get_note_id()),
+            )
+            .collect();
+        let hidden = self.notes.hide_oldest_visible(limit, &keep);
+        if hidden.is_empty() {
+            return Task::none();
+        }
+        let mut tasks = Vec::with_capacity(hidden.len() + 1);
+        let mut titles = Vec::with_capacity(hidden.len());
+        for note_id in hidden {
+            if let Some(window_id) = self.sticky_windows.find_window(note_id) {
+                self.sticky_windows.remove(window_id);
+                tasks.push(window::close(window_id));
+            }
+            if let Ok(note) = self.notes.try_get_note(&note_id) {
+                let title = note.get_title().trim();
+                titles.push(if title.is_empty() {
+                    fl!("notes-limit-untitled")
+                } else {
+                    title.to_string()
+                });
+            }
+        }
+        tasks.push(Self::notify(
+            fl!("notes-limit-title"),
+            fl!("notes-limit-hidden", titles = titles.join(", ")),
+            Message::Ignore,
+        ));
+        cosmic::task::batch(tasks)
+    }
+
     fn on_restore_note(&mut self, note_id: Uuid) -> Task<cosmic::Action<Message>> {
+        if self.restore_selection == Some(note_id) {
+            self.restore_selection = None;
+        }
+        // the note may be restored by the undo toast or elsewhere, the toast is useless anyway
+        let close_toast = self.close_undo_toast(|_, deleted_id| deleted_id == note_id);
         match self.notes.try_restore_deleted_note(note_id) {
             Ok(note) => {
-                let (_id, task) =
-                    Self::spawn_sticky_window(note_id, note, self.config.sticky_window_minimum());
-                task
+                let (_id, task) = Self::spawn_sticky_window(
+                    note_id,
+                    note,
+                    self.config.sticky_window_minimum(),
+                    &mut self.spawning_notes,
+                );
+                let hide_task = self.hide_notes_over_limit(Some(note_id));
+                close_toast.chain(cosmic::task::batch([task, hide_task]))
             }
             Err(e) => {
                 tracing::error!("failed to restore note: {e}");
@@ -784,56 +2277,340 @@ impl ServiceModel {
         }
     }
 
-    fn on_change_note_locking(&mut self, window_id: Id, is_on: bool) {
+    fn on_check_reminders(&mut self) -> Task<cosmic::Action<Message>> {
+        let due = self.notes.take_due_reminders(Utc::now());
+        let mut tasks = Vec::with_capacity(due.len() * 2);
+        for note_id in due {
+            let Ok(note) = self.notes.try_get_note(&note_id) else {
+                continue;
+            };
+            let title = note.get_title().to_string();
+            tracing::info!("reminder of note {note_id} ({title}) is due");
+            tasks.push(Self::notify(fl!("reminder-title"), title, Message::Ignore));
+            tasks.push(self.raise_note_window(note_id));
+        }
+        cosmic::task::batch(tasks)
+    }
+
+    // raise the window of the note, spawn it if the note has no window yet,
+    // the hidden note is shown, so other notes may be hidden over the limit
+    fn raise_note_window(&mut self, note_id: Uuid) -> Task<cosmic::Action<Message>> {
+        match self.notes.try_get_note_mut(&note_id) {
+            Ok(note) => note.set_visibility(true),
+            Err(e) => {
+                tracing::error!("failed to raise note: {e}");
+                return Task::none();
+            }
+        }
+        let hide_task = self.hide_notes_over_limit(Some(note_id));
+        let raise_task = if let Some(window_id) = self.sticky_windows.find_window(note_id) {
+            window::gain_focus(window_id)
+        } else if self.spawning_notes.contains(&note_id) {
+            // e.g. the reminder is due at startup, the window is focused once it is created
+            self.focus_on_spawn = Some(note_id);
+            Task::none()
+        } else if let Ok(note) = self.notes.try_get_note(&note_id) {
+            let (_id, task) = Self::spawn_sticky_window(
+                note_id,
+                note,
+                self.config.sticky_window_minimum(),
+                &mut self.spawning_notes,
+            );
+            task
+        } else {
+            Task::none()
+        };
+        cosmic::task::batch([hide_task, raise_task])
+    }
+
+    fn on_monitor_size(&mut self, window_id: Id, size: Size) -> Task<cosmic::Action<Message>> {
+        self.screen_size = Some(size);
+        let task = self.reposition_offscreen_notes();
+        if let Ok(note) = self.sticky_windows.try_get_note(window_id, &self.notes) {
+            let top_left = Point::new(to_f32(note.left()), to_f32(note.top()));
+            self.screen_name = self
+                .outputs
+                .values()
+                .find(|output| output.bounds.is_some_and(|rect| rect.contains(top_left)))
+                .and_then(|output| output.name.clone());
+        }
+        task
+    }
+
+    fn on_output_changed(
+        &mut self,
+        output_id: u32,
+        output: Option<ScreenOutput>,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(output) = output {
+            self.outputs.insert(output_id, output);
+            return Task::none();
+        }
+        self.outputs.remove(&output_id);
+        // notes of the unplugged screen are out of the screens now
+        self.reposition_offscreen_notes()
+    }
+
+    // the bounds of the screens which position is known, the top left one goes first
+    fn known_screen_bounds(&self) -> Vec<Rectangle> {
+        let mut bounds: Vec<Rectangle> = self
+            .outputs
+            .values()
+            .filter_map(|output| output.bounds)
+            .collect();
+        bounds.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        bounds
+    }
+
+    // moves notes out of all the known screens onto the top left screen, so the new position is saved,
+    // nothing is moved if the positions of the screens are unknown
+    fn reposition_offscreen_notes(&mut self) -> Task<cosmic::Action<Message>> {
+        if !self.config.reposition_offscreen {
+            return Task::none();
+        }
+        let moved = self.notes.move_notes_onscreen(&self.known_screen_bounds());
+        cosmic::task::batch(moved.into_iter().filter_map(|(note_id, (left, top))| {
+            self.sticky_windows
+                .find_window(note_id)
+                .map(|window_id| window::move_to(window_id, Point::new(to_f32(left), to_f32(top))))
+        }))
+    }
+
+    fn on_change_note_locking(
+        &mut self,
+        window_id: Id,
+        is_on: bool,
+    ) -> Task<cosmic::Action<Message>> {
         match self.try_get_note_mut(window_id) {
             Ok(note) => {
+                let was_click_through = note.is_click_through();
                 note.set_locking(is_on);
+                if was_click_through && !note.is_click_through() {
+                    return window::disable_mouse_passthrough(window_id);
+                }
             }
             Err(e) => tracing::error!("failed to change note locking: {e}"),
         }
+        Task::none()
+    }
+
+    fn on_bulk_apply(
+        &mut self,
+        note_ids: &[Uuid],
+        action: BulkAction,
+    ) -> Task<cosmic::Action<Message>> {
+        let windows: Vec<Id> = note_ids
+            .iter()
+            .filter_map(|note_id| self.sticky_windows.find_window(*note_id))
+            .collect();
+        match action {
+            BulkAction::Lock(is_on) => {
+                // unlocking stops click-through of notes, so they should intercept clicks again
+                let click_through_windows: Vec<Id> = windows
+                    .into_iter()
+                    .filter(|id| {
+                        self.sticky_windows
+                            .try_get_note(*id, &self.notes)
+                            .is_ok_and(NoteData::is_click_through)
+                    })
+                    .collect();
+                self.notes.bulk_set_locking(note_ids, is_on);
+                if !is_on {
+                    return cosmic::task::batch(
+                        click_through_windows
+                            .into_iter()
+                            .map(window::disable_mouse_passthrough),
+                    );
+                }
+            }
+            BulkAction::SetStyle(style_id) => {
+                if let Err(e) = self.notes.bulk_set_style(note_ids, style_id) {
+                    tracing::error!("failed to set style of selected notes: {e}");
+                }
+            }
+            BulkAction::Delete => {
+                // deleted notes are restorable one by one in the restore window
+                for note_id in note_ids {
+                    self.overview_selection.remove(note_id);
+                }
+                for id in &windows {
+                    self.sticky_windows.remove(*id);
+                }
+                self.notes.bulk_delete(note_ids);
+                return cosmic::task::batch(windows.into_iter().map(window::close));
+            }
+        }
+        Task::none()
+    }
+
+    // the toolbar of click-through notes is unreachable, so they are made clickable all at once
+    fn on_stop_click_through(&mut self) -> Task<cosmic::Action<Message>> {
+        let click_through_windows: Vec<Id> = self
+            .sticky_windows
+            .iter()
+            .filter(|(id, _)| {
+                self.sticky_windows
+                    .try_get_note(*id, &self.notes)
+                    .is_ok_and(NoteData::is_click_through)
+            })
+            .map(|(id, _)| id)
+            .collect();
+        self.notes
+            .for_each_note_mut(|note| note.set_click_through(false));
+        cosmic::task::batch(
+            click_through_windows
+                .into_iter()
+                .map(window::disable_mouse_passthrough),
+        )
+    }
+
+    fn on_change_note_click_through(
+        &mut self,
+        window_id: Id,
+        is_on: bool,
+    ) -> Task<cosmic::Action<Message>> {
+        match self.try_get_note_mut(window_id) {
+            Ok(note) => {
+                note.set_click_through(is_on);
+                if note.is_click_through() {
+                    // the window does not receive mouse events any more
+                    if self.cursor_window == Some(window_id) {
+                        self.cursor_window = None;
+                    }
+                    return window::enable_mouse_passthrough(window_id);
+                }
+                return window::disable_mouse_passthrough(window_id);
+            }
+            Err(e) => tracing::error!("failed to change note click-through: {e}"),
+        }
+        Task::none()
     }
 
     fn on_change_notes_visibility(&mut self, on: bool) -> Task<cosmic::Action<Message>> {
+        // the notes hidden due to no interaction are either shown or hidden for good
+        self.idle_hidden = false;
         self.notes.for_each_note_mut(|note| note.set_visibility(on));
         if on {
-            cosmic::task::batch(self.spawn_sticky_windows())
+            // give the focus back to the note the user worked with before hiding
+            self.focus_on_spawn = self.last_focused_note;
+            let hide_task = self.hide_notes_over_limit(self.last_focused_note);
+            let mut tasks = self.spawn_sticky_windows();
+            tasks.push(hide_task);
+            cosmic::task::batch(tasks)
         } else {
             cosmic::task::batch(self.close_sticky_windows())
         }
     }
 
+    // shows the notes hidden due to no interaction if any
+    fn on_interaction(&mut self) -> Task<cosmic::Action<Message>> {
+        self.last_interaction = Instant::now();
+        if !self.idle_hidden {
+            return Task::none();
+        }
+        tracing::debug!("show notes hidden due to no interaction");
+        cosmic::task::batch(self.spawn_sticky_windows())
+    }
+
+    fn on_check_idle(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(timeout) = self.config.auto_hide_timeout() else {
+            return Task::none();
+        };
+        if self.idle_hidden
+            || self.last_interaction.elapsed() < timeout
+            || self.sticky_windows.iter().next().is_none()
+            || self
+                .sticky_windows
+                .iter()
+                .any(|(_, sticky_window)| sticky_window.is_editing())
+        {
+            return Task::none();
+        }
+        // only the windows are closed, the visibility is not saved as it is
+        tracing::info!("hide notes left without interaction for {timeout:?}");
+        self.idle_hidden = true;
+        cosmic::task::batch(self.close_sticky_windows())
+    }
+
+    // load dictionaries once the spelling check is turned on, drop them once it is off
+    fn update_spell_checker(&mut self) -> Task<cosmic::Action<Message>> {
+        if !self.config.spellcheck {
+            if self.spell_checker.take().is_some() {
+                self.check_spelling_of_edited();
+            }
+        } else if self.spell_checker.is_none() {
+            return cosmic::task::future(Self::load_spell_checker(
+                self.requested_languages.clone(),
+            ));
+        }
+        Task::none()
+    }
+
+    // reading and parsing dictionaries takes a while, so it is not done in update()
+    async fn load_spell_checker(languages: Vec<LanguageIdentifier>) -> Message {
+        match tokio::task::spawn_blocking(move || SpellChecker::load(&languages)).await {
+            Ok(spell_checker) => Message::SpellCheckerLoaded(Arc::new(spell_checker)),
+            Err(e) => {
+                tracing::error!("failed to load dictionaries: {e}");
+                Message::Ignore
+            }
+        }
+    }
+
+    // the notes being edited are marked with the current checker, or unmarked without one
+    fn check_spelling_of_edited(&mut self) {
+        for sticky_window in self.sticky_windows.values_mut() {
+            sticky_window.check_spelling(self.spell_checker.as_deref());
+        }
+    }
+
     fn on_start_edit(&mut self, window_id: Id) {
-        if let Some(sticky_window) = self.sticky_windows.get_mut(&window_id) {
-            if let Ok(note) = self.notes.try_get_note(&sticky_window.get_note_id())
-                && let Err(e) = sticky_window.start_edit(note.get_content())
-            {
-                tracing::error!("[{window_id}] failed to start edit: {e}");
+        match self
+            .sticky_windows
+            .try_get_with_note_mut(window_id, &mut self.notes)
+        {
+            Ok((sticky_window, note)) => {
+                if let Err(e) = sticky_window.start_edit(note.get_content(), note.get_reminder()) {
+                    tracing::error!("[{window_id}] failed to start edit: {e}");
+                } else {
+                    sticky_window.check_spelling(self.spell_checker.as_deref());
+                }
             }
-        } else {
-            tracing::error!("[{window_id}] failed to start edit: sticky window is not found");
+            Err(e) => tracing::error!("[{window_id}] failed to start edit: {e}"),
         }
     }
 
+    // the window title follows the first line of content, see update_window_titles()
     fn on_finish_edit(&mut self, window_id: Id) {
-        if let Some(sticky_window) = self.sticky_windows.get_mut(&window_id) {
-            if let Ok(note) = self.notes.try_get_note_mut(&sticky_window.get_note_id()) {
+        let undo_history_limit = self.config.undo_history_limit;
+        match self
+            .sticky_windows
+            .try_get_with_note_mut(window_id, &mut self.notes)
+        {
+            Ok((sticky_window, note)) => {
+                match sticky_window.get_reminder() {
+                    Ok(Some(reminder)) => note.set_reminder(reminder),
+                    Ok(None) => note.clear_reminder(),
+                    Err(e) => {
+                        // keep editing, the window shows the reminder is invalid
+                        tracing::warn!("[{window_id}] edit is not finished: {e}");
+                        return;
+                    }
+                }
                 match sticky_window.finish_edit() {
-                    Ok(text) => note.set_content(text),
+                    Ok(text) => note.set_content_with_history(text, undo_history_limit),
                     Err(e) => tracing::error!("[{window_id}] failed to finish edit: {e}"),
                 }
             }
-        } else {
-            tracing::error!("[{window_id}] failed to finish edit: sticky window is not found");
+            Err(e) => tracing::error!("[{window_id}] failed to finish edit: {e}"),
         }
     }
 
     fn on_style_selected(&mut self, window_id: Id, style_index: usize) {
-        if let Some(sticky_window) = self.sticky_windows.get_mut(&window_id) {
+        if let Some(sticky_window) = self.sticky_windows.get_mut(window_id) {
             sticky_window.disable_select_style();
-            if let Err(e) = self
-                .notes
-                .try_set_note_style_by_index(sticky_window.get_note_id(), style_index)
-            {
+            let note_id = sticky_window.get_note_id();
+            if let Err(e) = self.notes.try_set_note_style_by_index(note_id, style_index) {
                 tracing::error!("[{window_id}] Failed select style: {e}");
             }
         } else {
@@ -841,8 +2618,43 @@ impl ServiceModel {
         }
     }
 
+    // hides the note keeping it in the collection, "show all" or the restore window brings it back
+    fn on_hide_note(&mut self, id: Id) -> Task<cosmic::Action<Message>> {
+        match self.try_get_note_mut(id) {
+            Ok(note) => {
+                note.set_visibility(false);
+                self.sticky_windows.remove(id);
+                window::close(id)
+            }
+            Err(e) => {
+                tracing::error!("failed to hide note: {e}");
+                Task::none()
+            }
+        }
+    }
+
+    fn on_show_note(&mut self, note_id: Uuid) -> Task<cosmic::Action<Message>> {
+        match self.notes.try_get_note_mut(&note_id) {
+            Ok(note) if !note.is_visible() => {
+                note.set_visibility(true);
+                let (_id, task) = Self::spawn_sticky_window(
+                    note_id,
+                    note,
+                    self.config.sticky_window_minimum(),
+                    &mut self.spawning_notes,
+                );
+                cosmic::task::batch([task, self.hide_notes_over_limit(Some(note_id))])
+            }
+            Ok(_) => Task::none(),
+            Err(e) => {
+                tracing::error!("failed to show note: {e}");
+                Task::none()
+            }
+        }
+    }
+
     fn on_delete_note(&mut self, id: Id) -> Task<cosmic::Action<Message>> {
-        if let Some(sticky_window) = self.sticky_windows.remove(&id) {
+        if let Some(sticky_window) = self.sticky_windows.remove(id) {
             self.notes.delete_note(sticky_window.get_note_id());
             window::close(id)
         } else {
@@ -850,21 +2662,158 @@ impl ServiceModel {
         }
     }
 
+    fn on_copy_note_content(&mut self, id: Id) -> Task<cosmic::Action<Message>> {
+        match self
+            .sticky_windows
+            .try_get_with_note_mut(id, &mut self.notes)
+        {
+            Ok((sticky_window, note)) => {
+                sticky_window.set_copied(true);
+                cosmic::iced::clipboard::write(note.get_content().to_string()).chain(
+                    cosmic::task::future(async move {
+                        tokio::time::sleep(COPIED_FEEDBACK_TIMEOUT).await;
+                        Message::NoteCopiedExpired(id)
+                    }),
+                )
+            }
+            Err(e) => {
+                tracing::error!("[{id}] failed to copy note content: {e}");
+                Task::none()
+            }
+        }
+    }
+
+    fn on_copy_note_as_json(&self, id: Id) -> Task<cosmic::Action<Message>> {
+        let Ok(note_id) = self.sticky_windows.try_get_note_id(id) else {
+            return Task::none();
+        };
+        match self.notes.export_single(&note_id) {
+            Ok(json) => cosmic::iced::clipboard::write(json),
+            Err(e) => {
+                tracing::error!("[{id}] failed to copy note as JSON: {e}");
+                Task::none()
+            }
+        }
+    }
+
+    // deletes the note at once and offers to undo deleting for a while
+    fn on_quick_delete_note(&mut self, id: Id) -> Task<cosmic::Action<Message>> {
+        let Ok(note_id) = self.sticky_windows.try_get_note_id(id) else {
+            return Task::none();
+        };
+        let Ok(note) = self.notes.try_get_note(&note_id) else {
+            return Task::none();
+        };
+        if note.is_locked() {
+            return Task::none();
+        }
+        // show the toast in place of the deleted note
+        let position = Position::Specific(Point::new(to_f32(note.left()), to_f32(note.top())));
+        let (_id, spawn_toast) = window::open(window::Settings {
+            position,
+            size: Size::new(UNDO_TOAST_WIDTH, UNDO_TOAST_HEIGHT),
+            decorations: false,
+            ..Default::default()
+        });
+        self.on_delete_note(id).chain(
+            spawn_toast.map(move |id| cosmic::Action::App(Message::UndoToastCreated(id, note_id))),
+        )
+    }
+
+    fn close_undo_toast<F>(&mut self, matches: F) -> Task<cosmic::Action<Message>>
+    where
+        F: Fn(Id, Uuid) -> bool,
+    {
+        match self.undo_toast {
+            Some((window_id, note_id)) if matches(window_id, note_id) => {
+                self.undo_toast = None;
+                window::close(window_id)
+            }
+            _ => Task::none(),
+        }
+    }
+
     fn on_new_style(&mut self) -> Task<cosmic::Action<Message>> {
         let name = format!(
             "{}-{}",
             fl!("new-style-name"),
             self.notes.get_styles_count()
         );
-        let style_id = self.notes.new_style(name);
+        // the style is saved once the dialog is confirmed with Ok,
+        // the new style starts from the default one, see StyleDuplicate to start from a particular style
+        let style_id = self.notes.new_draft_style(name, None);
+        if self.config.default_color_follows_theme
+            && let Ok(style) = self.notes.try_get_style_mut(&style_id)
+        {
+            style.set_background_color(self.config.new_style_color());
+        }
         // turn off style selectors for each sticky windows
         self.sticky_windows
             .values_mut()
             .for_each(StickyWindow::disable_select_style);
-        self.spawn_edit_style_window(style_id)
+        self.spawn_edit_style_window(style_id, true)
+    }
+
+    fn on_duplicate_style(&mut self, style_id: Uuid) -> Task<cosmic::Action<Message>> {
+        // the copy is saved once the dialog is confirmed with Ok
+        match self
+            .notes
+            .duplicate_style(&style_id, |name| fl!("style-copy-name", name = name))
+        {
+            Ok(copy_id) => {
+                self.sticky_windows
+                    .values_mut()
+                    .for_each(StickyWindow::disable_select_style);
+                self.spawn_edit_style_window(copy_id, true)
+            }
+            Err(e) => {
+                tracing::error!("failed to duplicate style: {e}");
+                Task::none()
+            }
+        }
+    }
+
+    // deleting a style in use requires the confirmation shown in the settings window
+    fn on_delete_style(&mut self, style_id: Uuid) -> Task<cosmic::Action<Message>> {
+        if self.notes.count_notes_with_style(&style_id) == 0 {
+            self.delete_style(style_id);
+            Task::none()
+        } else {
+            self.delete_style_confirm = Some(style_id);
+            self.settings_window_id
+                .map_or_else(Task::none, window::gain_focus)
+        }
+    }
+
+    // the dialog is closed without Ok: the new style is deleted, edits of an existing one are dropped
+    fn discard_edit_style(&mut self, dialog: &EditStyleDialog) {
+        if dialog.is_new() && self.notes.discard_draft_style(dialog.get_id()) {
+            self.reassign_targets.remove(&dialog.get_id());
+            // style indices are changed, so turn off style selectors in all of the sticky windows
+            self.sticky_windows
+                .values_mut()
+                .for_each(StickyWindow::disable_select_style);
+        }
+    }
+
+    fn gc_styles(&mut self) {
+        // the style of the open edit dialog is kept, even the new one not used yet
+        let edited_style = self.edit_style.as_ref().map(|(_, dialog)| dialog.get_id());
+        let removed = self.notes.gc_unreferenced_styles(edited_style);
+        tracing::info!("removed {} unused styles", removed.len());
+        for style_id in &removed {
+            self.reassign_targets.remove(style_id);
+        }
+        if !removed.is_empty() {
+            // style indices are changed, so turn off style selectors in all of the sticky windows
+            self.sticky_windows
+                .values_mut()
+                .for_each(StickyWindow::disable_select_style);
+        }
     }
 
-    fn on_delete_style(&mut self, style_id: Uuid) {
+    fn delete_style(&mut self, style_id: Uuid) {
+        self.reassign_targets.remove(&style_id);
         match self.notes.delete_style(style_id) {
             Ok(()) => {
                 // as default style might be changed turn off style selectors in all of the sticky windows
@@ -878,12 +2827,21 @@ impl ServiceModel {
         }
     }
 
-    fn on_style_updated(&mut self, style_id: Uuid, name: &str, font: Font, bgcolor: Color) {
-        match self.notes.try_get_style_mut(&style_id) {
+    fn on_style_updated(&mut self, dialog: &EditStyleDialog) {
+        let style_id = dialog.get_id();
+        let name =
+            self.notes
+                .validate_style_name(&style_id, dialog.get_name(), &fl!("new-style-name"));
+        match self.notes.rename_style(&style_id, &name) {
             Ok(style) => {
-                style.set_name(name);
-                style.set_font(font);
-                style.set_background_color(bgcolor);
+                style.set_group(Some(dialog.get_group()));
+                style.set_font(dialog.get_font());
+                style.set_background_color(dialog.get_background_color());
+                style.set_text_align(dialog.get_text_align());
+                style.set_background_image(dialog.get_background_image());
+                style.set_corner_radius(dialog.get_corner_radius());
+                style.set_border(dialog.get_border_width(), dialog.get_border_color());
+                self.notes.confirm_style(&style_id);
             }
             Err(e) => tracing::error!("failed to update style: {e}"),
         }
@@ -899,6 +2857,16 @@ impl ServiceModel {
                 if let Some(cursor_id) = self.cursor_window
                     && cursor_id == id
                 {
+                    // double-click on the unlocked note begins edit instead of drag
+                    if let Ok((sticky_window, note)) = self
+                        .sticky_windows
+                        .try_get_with_note_mut(id, &mut self.notes)
+                        && sticky_window.register_click(Instant::now())
+                        && !sticky_window.is_editing()
+                        && !note.is_locked()
+                    {
+                        return Task::done(cosmic::Action::App(Message::NoteEdit(id, true)));
+                    }
                     return self.core.drag(Some(id));
                 }
             }
@@ -914,14 +2882,73 @@ impl ServiceModel {
                         }));
                 }
             }
+            MouseEvent::ButtonPressed(MouseButton::Middle) => {
+                if let Some(sticky_window) = self.sticky_windows.get(id)
+                    && !sticky_window.is_editing()
+                {
+                    return self.on_quick_delete_note(id);
+                }
+            }
             MouseEvent::CursorEntered => {
-                self.cursor_window.replace(id);
+                // click-through notes are never dragged or clicked
+                if !self
+                    .sticky_windows
+                    .try_get_note(id, &self.notes)
+                    .is_ok_and(NoteData::is_click_through)
+                {
+                    self.cursor_window.replace(id);
+                }
+            }
+            MouseEvent::CursorLeft => {
+                self.cursor_position = None;
             }
             _ => {}
         }
         Task::none()
     }
 
+    fn on_key_pressed(&self, id: Id, key: &Key) -> Task<cosmic::Action<Message>> {
+        if self.restore_window_id == Some(id) {
+            let message = match key {
+                Key::Named(NamedKey::ArrowUp) => Some(Message::RestoreSelectMove(-1)),
+                Key::Named(NamedKey::ArrowDown) => Some(Message::RestoreSelectMove(1)),
+                Key::Named(NamedKey::Enter) => Some(Message::RestoreSelected),
+                _ => None,
+            };
+            if let Some(message) = message {
+                return Task::done(cosmic::Action::App(message));
+            }
+        }
+        Task::none()
+    }
+
+    fn on_restore_select_move(&mut self, delta: i32) {
+        // the order of deleted notes is the same as displayed in the restore window
+        let deleted: Vec<Uuid> = self
+            .notes
+            .deleted_notes_sorted(RESTORE_SORT)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        if deleted.is_empty() {
+            self.restore_selection = None;
+            return;
+        }
+        let last = deleted.len() - 1;
+        let step = usize::try_from(delta.unsigned_abs()).unwrap_or(usize::MAX);
+        let index = match self
+            .restore_selection
+            .and_then(|selected| deleted.iter().position(|id| *id == selected))
+        {
+            Some(current) if delta < 0 => current.saturating_sub(step),
+            Some(current) => current.saturating_add(step).min(last),
+            // nothing is selected yet, start from the nearest end
+            None if delta < 0 => last,
+            None => 0,
+        };
+        self.restore_selection = deleted.get(index).copied();
+    }
+
     fn on_window_event(
         &mut self,
         id: Id,
@@ -930,7 +2957,7 @@ impl ServiceModel {
         match event {
             // WindowEvent::Resized(size) => is handled by on_window_resize() override
             WindowEvent::Moved(point) => {
-                if self.sticky_windows.contains_key(&id) {
+                if self.sticky_windows.contains(id) {
                     match self.try_get_note_mut(id) {
                         Ok(note) => {
                             note.set_position(to_usize(point.x), to_usize(point.y));
@@ -939,30 +2966,85 @@ impl ServiceModel {
                     }
                 }
             }
+            // remember the note is on top of others
+            WindowEvent::Focused => {
+                if self.sticky_windows.contains(id) {
+                    match self.try_get_note_mut(id) {
+                        Ok(note) => note.set_last_focused(Utc::now()),
+                        Err(e) => tracing::error!("failed to update sticky window focus: {e}"),
+                    }
+                    self.last_focused_note = self.sticky_windows.try_get_note_id(id).ok();
+                }
+            }
             // do nothing with CloseRequested at the moment:
             // WindowEvent::CloseRequested => {}
             WindowEvent::Closed => {
+                // the closed window must not be dragged or resized on the next mouse event,
+                // the focused window of core is not cleared but it resolves no sticky window any more
+                if self.cursor_window == Some(id) {
+                    self.cursor_window = None;
+                    self.cursor_position = None;
+                }
                 if let Some(window_id) = self.restore_window_id
                     && window_id == id
                 {
                     // restore window has closed, forget its id
                     self.restore_window_id = None;
+                    self.restore_selection = None;
+                } else if let Some(window_id) = self.hidden_window_id
+                    && window_id == id
+                {
+                    self.hidden_window_id = None;
+                } else if let Some(window_id) = self.overview_window_id
+                    && window_id == id
+                {
+                    self.overview_window_id = None;
+                    self.overview_selection.clear();
                 } else if let Some(window_id) = self.settings_window_id
                     && window_id == id
                 {
                     self.settings_window_id = None;
+                    self.reassign_targets.clear();
+                    self.delete_style_confirm = None;
+                    self.gc_styles_confirm = false;
+                    self.template_editor = None;
                 } else if let Some((window_id, _)) = &self.edit_style
                     && *window_id == id
                 {
-                    self.edit_style = None;
+                    // closing the dialog without Ok is the same as Cancel
+                    if let Some((_, dialog)) = self.edit_style.take() {
+                        self.discard_edit_style(&dialog);
+                    }
                 } else if let Some((window_id, _)) = &self.about_window
                     && *window_id == id
                 {
                     self.about_window = None;
+                } else if let Some(window_id) = self.validation_window_id
+                    && window_id == id
+                {
+                    self.validation_window_id = None;
+                    self.validation = None;
+                } else if let Some((window_id, _)) = self.undo_toast
+                    && window_id == id
+                {
+                    self.undo_toast = None;
+                } else if self.sticky_windows.contains(id) {
+                    // the note toolbar forgets the window before closing it, so it is closed by the compositor;
+                    // closing the window once again does nothing
+                    let task = match self.config.on_window_close {
+                        WindowCloseAction::HideNote => self.on_hide_note(id),
+                        WindowCloseAction::DeleteNote => self.on_delete_note(id),
+                    };
+                    // the window is forgotten even if its note is missing
+                    self.sticky_windows.remove(id);
+                    return task;
                 } else if let Some(main_id) = self.core.main_window_id()
                     && main_id == id
                 {
                     return self.close_all_windows();
+                } else {
+                    // e.g. the sticky window closed by the note toolbar has been forgotten already
+                    tracing::debug!("closed window {id} is not tracked");
                 }
             }
             _ => {}
@@ -971,21 +3053,35 @@ impl ServiceModel {
     }
 
     fn spawn_sticky_windows(&mut self) -> Vec<Task<cosmic::Action<Message>>> {
-        let existing_windows = std::mem::take(&mut self.sticky_windows);
-        let mut commands: Vec<_> = existing_windows.into_keys().map(window::close).collect();
-        commands.extend(self.notes.iter_notes_mut().map(|(note_id, note)| {
+        let mut commands: Vec<_> = self
+            .sticky_windows
+            .drain_ids()
+            .into_iter()
+            .map(window::close)
+            .collect();
+        self.spawning_notes.clear();
+        // all the visible notes are shown, including the ones hidden due to no interaction
+        self.idle_hidden = false;
+        // open the recently focused notes the last, so they are on top of others
+        let mut visible_notes: Vec<_> = self.notes.iter_visible_notes().collect();
+        visible_notes.sort_by_key(|(_, note)| note.get_last_focused());
+        let minimum = self.config.sticky_window_minimum();
+        commands.extend(visible_notes.into_iter().map(|(note_id, note)| {
             let (_, spawn_window) =
-                Self::spawn_sticky_window(*note_id, note, self.config.sticky_window_minimum());
+                Self::spawn_sticky_window(*note_id, note, minimum, &mut self.spawning_notes);
             spawn_window
         }));
         commands
     }
 
+    // the note is pending in the spawning set until Message::StickyWindowCreated
     fn spawn_sticky_window(
         note_id: Uuid,
         note: &NoteData,
         minimum: Size<usize>,
+        spawning: &mut HashSet<Uuid>,
     ) -> (Id, Task<cosmic::Action<Message>>) {
+        spawning.insert(note_id);
         let (id, spawn_window) = window::open(window::Settings {
             position: Position::Specific(Point::new(to_f32(note.left()), to_f32(note.top()))),
             size: Size::new(
@@ -993,6 +3089,13 @@ impl ServiceModel {
                 to_f32(note.height().max(minimum.height)),
             ),
             decorations: false,
+            // let the note opacity show what is under the window
+            transparent: true,
+            level: if note.is_always_on_top() {
+                window::Level::AlwaysOnTop
+            } else {
+                window::Level::Normal
+            },
             ..Default::default()
         });
         (
@@ -1016,6 +3119,34 @@ impl ServiceModel {
         }
     }
 
+    fn spawn_hidden_notes_window(&self) -> Task<cosmic::Action<Message>> {
+        let (_id, spawn_window) = window::open(window::Settings {
+            size: self.config.restore_notes_size(),
+            ..Default::default()
+        });
+        let task = spawn_window.map(|id| cosmic::Action::App(Message::HiddenWindowCreated(id)));
+        if let Some(existing_window_id) = self.hidden_window_id {
+            tracing::debug!("force closing existing 'hidden notes' window");
+            window::close(existing_window_id).chain(task)
+        } else {
+            task
+        }
+    }
+
+    fn spawn_overview_window(&mut self) -> Task<cosmic::Action<Message>> {
+        let (_id, spawn_window) = window::open(window::Settings {
+            size: self.config.overview_size(),
+            ..Default::default()
+        });
+        let task = spawn_window.map(|id| cosmic::Action::App(Message::OverviewWindowCreated(id)));
+        if let Some(existing_window_id) = self.overview_window_id.take() {
+            tracing::debug!("force closing existing 'overview' window");
+            window::close(existing_window_id).chain(task)
+        } else {
+            task
+        }
+    }
+
     fn spawn_settings_window(&mut self) -> Task<cosmic::Action<Message>> {
         let (_id, spawn_window) = window::open(window::Settings::default());
         let task = spawn_window.map(|id| cosmic::Action::App(Message::SettingsWindowCreated(id)));
@@ -1041,15 +3172,35 @@ impl ServiceModel {
         }
     }
 
-    fn spawn_edit_style_window(&mut self, style_id: Uuid) -> Task<cosmic::Action<Message>> {
+    fn spawn_validation_window(&mut self) -> Task<cosmic::Action<Message>> {
+        let (_id, spawn_window) = window::open(window::Settings {
+            size: self.config.restore_notes_size(),
+            ..Default::default()
+        });
+        let task = spawn_window.map(|id| cosmic::Action::App(Message::ValidationWindowCreated(id)));
+        if let Some(existing_window_id) = self.validation_window_id.take() {
+            tracing::debug!("force closing existing 'validation' window");
+            window::close(existing_window_id).chain(task)
+        } else {
+            task
+        }
+    }
+
+    fn spawn_edit_style_window(
+        &mut self,
+        style_id: Uuid,
+        is_new: bool,
+    ) -> Task<cosmic::Action<Message>> {
         let (_id, spawn_window) = window::open(window::Settings {
             size: self.config.edit_style_size(),
             ..Default::default()
         });
-        let task = spawn_window
-            .map(move |id| cosmic::Action::App(Message::EditStyleWindowCreated(id, style_id)));
-        if let Some((existing_window_id, _edit_style)) = std::mem::take(&mut self.edit_style) {
+        let task = spawn_window.map(move |id| {
+            cosmic::Action::App(Message::EditStyleWindowCreated(id, style_id, is_new))
+        });
+        if let Some((existing_window_id, edit_style)) = std::mem::take(&mut self.edit_style) {
             tracing::debug!("force closing existing 'edit style' window");
+            self.discard_edit_style(&edit_style);
             window::close(existing_window_id).chain(task)
         } else {
             task
@@ -1057,9 +3208,9 @@ impl ServiceModel {
     }
 
     fn close_sticky_windows(&mut self) -> Vec<Task<cosmic::Action<Message>>> {
-        let existing_windows = std::mem::take(&mut self.sticky_windows);
-        existing_windows
-            .into_keys()
+        self.sticky_windows
+            .drain_ids()
+            .into_iter()
             .map(window::close)
             .collect::<Vec<Task<cosmic::Action<Message>>>>()
     }
@@ -1069,15 +3220,114 @@ impl ServiceModel {
         if let Some(restore_id) = self.restore_window_id.take() {
             commands.push(window::close(restore_id));
         }
+        if let Some(hidden_id) = self.hidden_window_id.take() {
+            commands.push(window::close(hidden_id));
+        }
+        if let Some(overview_id) = self.overview_window_id.take() {
+            commands.push(window::close(overview_id));
+        }
         if let Some(settings_id) = self.settings_window_id.take() {
             commands.push(window::close(settings_id));
         }
         if let Some((about_id, _)) = self.about_window.take() {
             commands.push(window::close(about_id));
         }
+        if let Some(validation_id) = self.validation_window_id.take() {
+            commands.push(window::close(validation_id));
+        }
         if let Some((edit_style_id, _)) = self.edit_style.take() {
             commands.push(window::close(edit_style_id));
         }
         cosmic::task::batch(commands)
     }
 }
+
+// libcosmic serves DBus activation under the constant app id, so the instance serves it under its own one
+fn dbus_activation_subscription(app_id: &'static str) -> Subscription<Message> {
+    Subscription::run_with_id(
+        app_id,
+        iced::stream::channel(10, move |mut output| async move {
+            let mut activation = dbus_activation::DbusActivation::new();
+            let mut requests = activation.rx();
+            let path = format!("/{}", app_id.replace('.', "/"));
+            let connection = match zbus::connection::Builder::session()
+                .and_then(|builder| builder.name(app_id))
+                .and_then(|builder| builder.serve_at(path, activation))
+            {
+                Ok(builder) => builder.build().await,
+                Err(e) => Err(e),
+            };
+            match connection {
+                // the connection serves requests as long as it exists
+                Ok(_connection) => {
+                    while let Some(request) = requests.next().await {
+                        if output.send(Message::DbusActivation(request)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                Err(e) => {
+                    if let Err(e) = output.send(Message::DbusServeFailed(e.to_string())).await {
+                        tracing::error!("failed reporting DBus failure: {e}");
+                    }
+                }
+            }
+        }),
+    )
+}
+
+// the directory of the file has to be writable as well as the file itself if it exists,
+// the permission bits don't tell that for another owner, so the writing is tried
+fn find_unwritable(file: &str) -> Option<String> {
+    let path = Path::new(file);
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    match std::fs::metadata(dir) {
+        Err(e) => return Some(format!("{}: {e}", dir.display())),
+        Ok(metadata) if !metadata.is_dir() => {
+            return Some(format!("{} is not a directory", dir.display()));
+        }
+        Ok(_) => {}
+    }
+    let probe = dir.join(format!(".sticky-notes-{}.tmp", Uuid::new_v4()));
+    if let Err(e) = std::fs::File::create_new(&probe) {
+        return Some(format!("{} is not writable: {e}", dir.display()));
+    }
+    if let Err(e) = std::fs::remove_file(&probe) {
+        tracing::warn!("failed removing {}: {e}", probe.display());
+    }
+    // opening for appending doesn't change the existing file
+    match std::fs::OpenOptions::new().append(true).open(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Some(format!("{file} is not writable: {e}"))
+        }
+        _ => None,
+    }
+}
+
+// the event loop of the service takes the model, so the code to exit with is kept aside
+static EXIT_CODE: AtomicI32 = AtomicI32::new(0);
+
+/// The code the service process exits with once `cosmic::app::run()` returns,
+/// it isn't 0 if saving notes on exit has failed
+#[must_use]
+pub fn exit_code() -> i32 {
+    EXIT_CODE.load(Ordering::Relaxed)
+}
+
+// the cursor moves are reported not more often than CURSOR_TRACK_PERIOD,
+// the subscription has no state, so the time of the recent report is kept aside
+fn cursor_track_due() -> bool {
+    static REPORTED_AT: Mutex<Option<Instant>> = Mutex::new(None);
+    let Ok(mut reported_at) = REPORTED_AT.lock() else {
+        return true;
+    };
+    let now = Instant::now();
+    if reported_at.is_some_and(|at| now.duration_since(at) < CURSOR_TRACK_PERIOD) {
+        return false;
+    }
+    *reported_at = Some(now);
+    true
+}
@@ -1,15 +1,36 @@
-use super::{service::Message, styles_view::build_styles_list_view};
-use crate::{fl, icons::IconSet, notes::NotesCollection};
+use super::{
+    service::Message, styles_view::build_styles_list_view,
+    templates_view::build_templates_list_view,
+};
+use crate::{
+    fl,
+    icons::IconSet,
+    notes::{CollectionStats, NoteStyle, NotesCollection},
+};
+use chrono::{DateTime, Local, Utc};
 use cosmic::prelude::*;
 use cosmic::{
     iced::{Alignment, Length},
     widget,
 };
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+#[allow(clippy::too_many_arguments)]
 pub fn build_settings_view<'a>(
     notes: &'a NotesCollection,
     icons: &IconSet,
     icon_size: u16,
+    high_contrast: bool,
+    screen_context: Option<&str>,
+    reassign_targets: &HashMap<Uuid, usize>,
+    collapsed_groups: &HashSet<String>,
+    delete_style_confirm: Option<Uuid>,
+    gc_styles_confirm: bool,
+    edited_style: Option<Uuid>,
+    template_editor: Option<(usize, &'a widget::text_editor::Content)>,
 ) -> Element<'a, Message> {
     let styles = notes.get_style_names();
     if styles.is_empty() {
@@ -20,8 +41,38 @@ pub fn build_settings_view<'a>(
             .height(Length::Fill)
             .into();
     }
+    if let Some(style_id) = delete_style_confirm {
+        return build_delete_style_confirm(notes, style_id);
+    }
+    let unused_styles = notes.unreferenced_styles(edited_style);
+    if gc_styles_confirm && !unused_styles.is_empty() {
+        return build_gc_styles_confirm(notes, &unused_styles);
+    }
     let default_style_index = notes.try_get_default_style_index().ok();
-    widget::column::with_capacity(4)
+    // the screen may use its own default style, the global one is used otherwise
+    let screen_default_style = screen_context.map(|context| {
+        let screen_style_index = notes.get_default_style_index_for(context);
+        widget::row::with_capacity(3)
+            .spacing(cosmic::theme::spacing().space_m)
+            .align_y(Alignment::Center)
+            .push(widget::text(fl!(
+                "select-screen-default-style",
+                screen = context
+            )))
+            .push(
+                widget::dropdown(
+                    styles.clone(),
+                    screen_style_index,
+                    Message::SetScreenDefaultStyle,
+                )
+                .placeholder(fl!("screen-default-style-global")),
+            )
+            .push(
+                widget::button::text(fl!("reset-screen-default-style"))
+                    .on_press_maybe(screen_style_index.map(|_| Message::ResetScreenDefaultStyle)),
+            )
+    });
+    widget::column::with_capacity(11)
         .spacing(cosmic::theme::spacing().space_s)
         .width(Length::Fill)
         .height(Length::Fill)
@@ -35,10 +86,116 @@ pub fn build_settings_view<'a>(
                     widget::dropdown(styles, default_style_index, move |index| {
                         Message::SetDefaultStyle(index)
                     })
-                    .placeholder("Choose a style..."),
+                    .placeholder(fl!("choose-style")),
                 ),
         )
+        .push_maybe(screen_default_style)
+        .push(
+            widget::row::with_capacity(2)
+                .spacing(cosmic::theme::spacing().space_m)
+                .align_y(Alignment::Center)
+                .push(widget::text(fl!("high-contrast")))
+                .push(widget::toggler(high_contrast).on_toggle(Message::SetHighContrast)),
+        )
+        .push(build_stats_view(&notes.stats()))
+        .push(widget::divider::horizontal::light())
+        .push(build_templates_list_view(
+            notes,
+            icons,
+            icon_size,
+            template_editor,
+        ))
+        .push(widget::divider::horizontal::light())
         .push(widget::button::text(fl!("create-new-style")).on_press(Message::StyleNew))
-        .push(build_styles_list_view(notes, icons, icon_size))
+        .push(
+            widget::button::text(fl!("gc-styles"))
+                .on_press_maybe((!unused_styles.is_empty()).then_some(Message::StylesGc)),
+        )
+        .push(build_styles_list_view(
+            notes,
+            icons,
+            icon_size,
+            reassign_targets,
+            collapsed_groups,
+        ))
+        .into()
+}
+
+fn build_gc_styles_confirm<'a>(
+    notes: &NotesCollection,
+    unused_styles: &[Uuid],
+) -> Element<'a, Message> {
+    let names = unused_styles
+        .iter()
+        .filter_map(|style_id| notes.try_get_style(style_id).ok())
+        .map(NoteStyle::get_display_name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    widget::dialog()
+        .title(fl!("gc-styles-title"))
+        .body(fl!(
+            "gc-styles-confirm",
+            count = unused_styles.len(),
+            names = names
+        ))
+        .primary_action(
+            widget::button::destructive(fl!("delete-style-ok")).on_press(Message::StylesGcConfirm),
+        )
+        .secondary_action(
+            widget::button::text(fl!("edit-style-cancel")).on_press(Message::StylesGcCancel),
+        )
+        .into()
+}
+
+fn build_stats_view<'a>(stats: &CollectionStats) -> Element<'a, Message> {
+    let format_time = |time: Option<DateTime<Utc>>| {
+        time.map(|time| {
+            DateTime::<Local>::from(time)
+                .format(TIMESTAMP_FORMAT)
+                .to_string()
+        })
+    };
+    let modified = format_time(stats.oldest_modified)
+        .zip(format_time(stats.newest_modified))
+        .map(|(oldest, newest)| {
+            widget::text::caption(fl!("stats-modified", oldest = oldest, newest = newest))
+        });
+    widget::column::with_capacity(5)
+        .push(widget::text::heading(fl!("stats-title")))
+        .push(widget::text::caption(fl!(
+            "stats-notes",
+            count = stats.notes_count,
+            deleted = stats.deleted_count
+        )))
+        .push(widget::text::caption(fl!(
+            "stats-styles",
+            count = stats.styles_count
+        )))
+        .push(widget::text::caption(fl!(
+            "stats-chars",
+            count = stats.total_chars
+        )))
+        .push_maybe(modified)
+        .into()
+}
+
+fn build_delete_style_confirm(notes: &NotesCollection, style_id: Uuid) -> Element<'_, Message> {
+    let name = notes
+        .try_get_style(&style_id)
+        .map(|style| style.get_name().to_string())
+        .unwrap_or_default();
+    widget::dialog()
+        .title(fl!("delete-style-title", name = name))
+        .body(fl!(
+            "delete-style-confirm",
+            count = notes.count_notes_with_style(&style_id)
+        ))
+        .primary_action(
+            widget::button::destructive(fl!("delete-style-ok"))
+                .on_press(Message::StyleDeleteConfirm(style_id)),
+        )
+        .secondary_action(
+            widget::button::text(fl!("edit-style-cancel")).on_press(Message::StyleDeleteCancel),
+        )
         .into()
 }
@@ -0,0 +1,261 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use cosmic::{
+    Theme,
+    font::Font,
+    iced::advanced::text::{Highlighter, highlighter::Format},
+};
+use i18n_embed::unic_langid::LanguageIdentifier;
+use spellbook::Dictionary;
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+// where hunspell dictionaries are installed by distributions
+const DICTIONARY_DIRS: [&str; 3] = [
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+];
+
+/// The misspelled word as the line index and the byte range in the line
+pub type Misspelling = (usize, Range<usize>);
+
+#[derive(Debug, Error)]
+pub enum SpellCheckError {
+    #[error("Failed reading dictionary {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("Failed parsing dictionary {0}: {1}")]
+    Parse(PathBuf, String),
+}
+
+pub struct SpellChecker {
+    dictionaries: Vec<Dictionary>,
+}
+
+impl std::fmt::Debug for SpellChecker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpellChecker")
+            .field("dictionaries", &self.dictionaries.len())
+            .finish()
+    }
+}
+
+impl SpellChecker {
+    /// Load the installed dictionaries of the requested languages, the languages without one are skipped
+    pub fn load(languages: &[LanguageIdentifier]) -> Self {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for path in languages.iter().filter_map(find_dictionary) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        let dictionaries: Vec<Dictionary> = paths
+            .iter()
+            .filter_map(|path| {
+                load_dictionary(path)
+                    .map_err(|e| tracing::error!("{e}"))
+                    .ok()
+            })
+            .collect();
+        if dictionaries.is_empty() {
+            tracing::warn!("no dictionary is found for {languages:?}, spelling is not checked");
+        } else {
+            tracing::debug!("loaded dictionaries {paths:?}");
+        }
+        Self { dictionaries }
+    }
+
+    /// Get the words unknown to all of the dictionaries
+    pub fn misspelled(&self, text: &str) -> Vec<Misspelling> {
+        if self.dictionaries.is_empty() {
+            return Vec::new();
+        }
+        text.lines()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                words(line)
+                    .into_iter()
+                    .filter(|range| {
+                        let word = &line[range.clone()];
+                        !self.dictionaries.iter().any(|dict| dict.check(word))
+                    })
+                    .map(move |range| (index, range))
+            })
+            .collect()
+    }
+}
+
+// the dictionary of the language and region if any, otherwise any dictionary of the language
+fn find_dictionary(language: &LanguageIdentifier) -> Option<PathBuf> {
+    let lang = language.language.as_str();
+    let mut names: Vec<String> = Vec::with_capacity(2);
+    if let Some(region) = language.region {
+        names.push(format!("{lang}_{}", region.as_str()));
+    }
+    names.push(lang.to_string());
+    let dirs = DICTIONARY_DIRS.iter().map(Path::new);
+    names
+        .iter()
+        .find_map(|name| {
+            dirs.clone()
+                .map(|dir| dir.join(name))
+                .find(|path| path.with_extension("dic").is_file())
+        })
+        .or_else(|| {
+            let prefix = format!("{lang}_");
+            dirs.clone()
+                .filter_map(|dir| std::fs::read_dir(dir).ok())
+                .flat_map(|entries| entries.filter_map(Result::ok).map(|entry| entry.path()))
+                .find(|path| {
+                    path.extension().is_some_and(|ext| ext == "dic")
+                        && path
+                            .file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .is_some_and(|stem| stem.starts_with(&prefix))
+                })
+                .map(|path| path.with_extension(""))
+        })
+}
+
+fn load_dictionary(path: &Path) -> Result<Dictionary, SpellCheckError> {
+    let read = |ext: &str| {
+        let file = path.with_extension(ext);
+        std::fs::read_to_string(&file).map_err(|e| SpellCheckError::Read(file, e))
+    };
+    let aff = read("aff")?;
+    let dic = read("dic")?;
+    Dictionary::new(&aff, &dic)
+        .map_err(|e| SpellCheckError::Parse(path.to_path_buf(), e.to_string()))
+}
+
+// byte ranges of the words in the line, links, e-mails and words with digits are not checked
+fn words(line: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut offset = 0;
+    for chunk in line.split_inclusive(char::is_whitespace) {
+        let chunk_offset = offset;
+        offset += chunk.len();
+        if chunk.contains("://") || chunk.contains('@') {
+            continue;
+        }
+        let mut word: Option<(usize, usize, bool)> = None; // start, end, has digits
+        for (index, c) in chunk.char_indices() {
+            if c.is_alphanumeric() {
+                let (_, end, has_digits) =
+                    word.get_or_insert((chunk_offset + index, chunk_offset + index, false));
+                *end = chunk_offset + index + c.len_utf8();
+                *has_digits |= c.is_numeric();
+            } else if c == '\'' && word.is_some() {
+                // apostrophe inside of the word, e.g. "don't"
+            } else if let Some((start, end, has_digits)) = word.take()
+                && !has_digits
+            {
+                words.push(start..end);
+            }
+        }
+        if let Some((start, end, false)) = word {
+            words.push(start..end);
+        }
+    }
+    words.retain(|range| line[range.clone()].chars().nth(1).is_some());
+    words
+}
+
+/// Marks the misspelled words in the text editor, see `SpellChecker::misspelled()`
+pub struct MisspellingHighlighter {
+    misspelled: Vec<Misspelling>,
+    current_line: usize,
+}
+
+impl Highlighter for MisspellingHighlighter {
+    type Settings = Vec<Misspelling>;
+    type Highlight = ();
+    type Iterator<'a> = std::vec::IntoIter<(Range<usize>, ())>;
+
+    fn new(settings: &Self::Settings) -> Self {
+        Self {
+            misspelled: settings.clone(),
+            current_line: 0,
+        }
+    }
+
+    fn update(&mut self, new_settings: &Self::Settings) {
+        self.misspelled.clone_from(new_settings);
+        self.current_line = 0;
+    }
+
+    fn change_line(&mut self, line: usize) {
+        self.current_line = line;
+    }
+
+    fn highlight_line(&mut self, _line: &str) -> Self::Iterator<'_> {
+        let line = self.current_line;
+        self.current_line += 1;
+        self.misspelled
+            .iter()
+            .filter(|(index, _)| *index == line)
+            .map(|(_, range)| (range.clone(), ()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn current_line(&self) -> usize {
+        self.current_line
+    }
+}
+
+// the text editor formats spans by the font and color only, so the misspelled words are colored
+pub fn misspelling_format(_highlight: &(), theme: &Theme) -> Format<Font> {
+    Format {
+        color: Some(theme.cosmic().destructive_color().into()),
+        font: None,
+    }
+}
+
+#[cfg(test)]
+fn word_texts(line: &str) -> Vec<&str> {
+    words(line).into_iter().map(|range| &line[range]).collect()
+}
+
+#[test]
+fn words_are_split_by_spaces_and_punctuation() {
+    assert_eq!(
+        word_texts("Hello, world! (a test)"),
+        vec!["Hello", "world", "test"]
+    );
+    assert!(word_texts("").is_empty());
+}
+
+#[test]
+fn words_skip_links_and_emails() {
+    assert_eq!(
+        word_texts("see https://example.com/page or mail me@example.com now"),
+        vec!["see", "or", "mail", "now"]
+    );
+}
+
+#[test]
+fn words_skip_words_with_digits() {
+    assert_eq!(
+        word_texts("meet at 10am in room B12 today"),
+        vec!["meet", "at", "in", "room", "today"]
+    );
+}
+
+#[test]
+fn words_keep_inner_apostrophes() {
+    assert_eq!(
+        word_texts("don't 'quote' it's"),
+        vec!["don't", "quote", "it's"]
+    );
+}
+
+#[test]
+fn words_are_byte_ranges_of_multibyte_text() {
+    let line = "Привет, мир — ёж";
+    assert_eq!(words(line), vec![0..12, 14..20, 25..29]);
+    assert_eq!(word_texts(line), vec!["Привет", "мир", "ёж"]);
+}
@@ -1,47 +1,100 @@
 use super::{
     service::Message,
-    utils::{cosmic_font, with_background},
+    spellcheck::{Misspelling, MisspellingHighlighter, SpellChecker, misspelling_format},
+    utils::{
+        HIGH_CONTRAST_MIN_FONT_SIZE, cosmic_font, text_alignment, with_note_background,
+        with_opacity,
+    },
 };
 use crate::{
     fl,
     icons::IconSet,
-    notes::{NoteStyle, NotesCollection},
+    notes::{NotesCollection, TextAlign},
 };
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
 use cosmic::prelude::*;
 use cosmic::{
-    iced::{Color, Length, window::Id},
-    widget::{self, text_editor::Action},
+    iced::{
+        Alignment, Color, Length,
+        keyboard::{Key, key::Named},
+        window::Id,
+    },
+    widget::{
+        self,
+        text_editor::{Action, Binding, KeyPress},
+    },
 };
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use uuid::Uuid;
 
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
+const REMINDER_INPUT_WIDTH: f32 = 140.0;
+const MIN_ICON_SIZE: u16 = 8;
+const MAX_ICON_SIZE: u16 = 64;
+const ICON_SIZE_STEP: u16 = 2;
+// the note is never dimmed completely, so it can be found on the screen
+const MIN_OPACITY: f32 = 0.2;
+const OPACITY_STEP: f32 = 0.05;
+const OPACITY_SLIDER_WIDTH: f32 = 80.0;
+// the second click within the period after the first one is a double-click
+const DOUBLE_CLICK_THRESHOLD: Duration = Duration::from_millis(400);
+
 #[derive(Debug, Error)]
 pub enum StickyWindowError {
     #[error("already in edit mode")]
     AlreadyEditing,
     #[error("not in edit mode")]
     EditingIsOff,
+    #[error("find & replace is off")]
+    ReplaceIsOff,
+    #[error("invalid reminder {0}, expected format is YYYY-MM-DD HH:MM")]
+    InvalidReminder(String),
 }
 
 pub struct StickyWindow {
     note_id: Uuid,
+    // the recently set window title, so it is not set again unchanged
+    title: String,
     edit_context: Option<EditContext>,
     style_names: Option<Vec<String>>,
     icon_size: u16,
+    // the time of the recent click which may begin a double-click
+    last_click: Option<Instant>,
+    // the content has been copied recently, the copy button shows the check mark for a while
+    is_copied: bool,
 }
 
 struct EditContext {
     /// currently edited content
     content: widget::text_editor::Content,
+    /// currently edited reminder in TIMESTAMP_FORMAT, empty if none
+    reminder: String,
+    /// find & replace row is shown if any
+    replace: Option<ReplaceContext>,
+    /// file path or URI to attach
+    attachment: String,
+    /// words of the content unknown to the spell checker
+    misspelled: Vec<Misspelling>,
+}
+
+#[derive(Default)]
+struct ReplaceContext {
+    find: String,
+    replace: String,
+    case_sensitive: bool,
 }
 
 impl StickyWindow {
     pub fn new(note_id: Uuid, icon_size: u16) -> Self {
         Self {
             note_id,
+            title: String::new(),
             edit_context: None,
             style_names: None,
             icon_size,
+            last_click: None,
+            is_copied: false,
         }
     }
 
@@ -49,17 +102,140 @@ impl StickyWindow {
         self.note_id
     }
 
-    pub fn start_edit(&mut self, init_content: &str) -> Result<(), StickyWindowError> {
+    /// Remember the window title, returns false if the title is the same already
+    pub fn update_title(&mut self, title: &str) -> bool {
+        if self.title == title {
+            return false;
+        }
+        title.clone_into(&mut self.title);
+        true
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.edit_context.is_some()
+    }
+
+    /// Register the click at the moment `now`, returns true if the click completes a double-click
+    pub fn register_click(&mut self, now: Instant) -> bool {
+        let is_double_click = self
+            .last_click
+            .is_some_and(|last| now.saturating_duration_since(last) <= DOUBLE_CLICK_THRESHOLD);
+        // the third click begins a new double-click
+        self.last_click = (!is_double_click).then_some(now);
+        is_double_click
+    }
+
+    pub fn start_edit(
+        &mut self,
+        init_content: &str,
+        init_reminder: Option<DateTime<Local>>,
+    ) -> Result<(), StickyWindowError> {
         if self.edit_context.is_some() {
             Err(StickyWindowError::AlreadyEditing)
         } else {
             self.edit_context = Some(EditContext {
                 content: widget::text_editor::Content::with_text(init_content),
+                reminder: init_reminder
+                    .map(|reminder| reminder.format(TIMESTAMP_FORMAT).to_string())
+                    .unwrap_or_default(),
+                replace: None,
+                attachment: String::new(),
+                misspelled: Vec::new(),
             });
             Ok(())
         }
     }
 
+    pub fn update_attachment(&mut self, attachment: String) -> Result<(), StickyWindowError> {
+        self.edit_context
+            .as_mut()
+            .map(|context| context.attachment = attachment)
+            .ok_or(StickyWindowError::EditingIsOff)
+    }
+
+    /// Takes the entered attachment clearing the input
+    pub fn take_attachment(&mut self) -> Result<String, StickyWindowError> {
+        self.edit_context
+            .as_mut()
+            .map(|context| std::mem::take(&mut context.attachment).trim().to_string())
+            .ok_or(StickyWindowError::EditingIsOff)
+    }
+
+    /// Show or hide the find & replace row
+    pub fn toggle_replace(&mut self) -> Result<(), StickyWindowError> {
+        self.edit_context
+            .as_mut()
+            .map(|context| {
+                context.replace = match context.replace {
+                    Some(_) => None,
+                    None => Some(ReplaceContext::default()),
+                };
+            })
+            .ok_or(StickyWindowError::EditingIsOff)
+    }
+
+    pub fn update_find(&mut self, find: String) -> Result<(), StickyWindowError> {
+        self.try_get_replace_mut()
+            .map(|context| context.find = find)
+    }
+
+    pub fn update_replace(&mut self, replace: String) -> Result<(), StickyWindowError> {
+        self.try_get_replace_mut()
+            .map(|context| context.replace = replace)
+    }
+
+    pub fn set_case_sensitive(&mut self, on: bool) -> Result<(), StickyWindowError> {
+        self.try_get_replace_mut()
+            .map(|context| context.case_sensitive = on)
+    }
+
+    /// Replaces all occurrences of `find` in the edited content, returns the number of replacements
+    pub fn replace_all(&mut self, find: &str, replace: &str) -> Result<usize, StickyWindowError> {
+        let case_sensitive = self.try_get_replace_mut()?.case_sensitive;
+        let context = self
+            .edit_context
+            .as_mut()
+            .ok_or(StickyWindowError::EditingIsOff)?;
+        let (text, count) = replace_all(&context.content.text(), find, replace, case_sensitive);
+        if count > 0 {
+            context.content = widget::text_editor::Content::with_text(&text);
+        }
+        Ok(count)
+    }
+
+    fn try_get_replace_mut(&mut self) -> Result<&mut ReplaceContext, StickyWindowError> {
+        self.edit_context
+            .as_mut()
+            .ok_or(StickyWindowError::EditingIsOff)?
+            .replace
+            .as_mut()
+            .ok_or(StickyWindowError::ReplaceIsOff)
+    }
+
+    pub fn update_reminder(&mut self, reminder: String) -> Result<(), StickyWindowError> {
+        self.edit_context
+            .as_mut()
+            .map(|context| context.reminder = reminder)
+            .ok_or(StickyWindowError::EditingIsOff)
+    }
+
+    /// Parses the edited reminder, empty one means no reminder
+    pub fn get_reminder(&self) -> Result<Option<DateTime<Utc>>, StickyWindowError> {
+        let context = self
+            .edit_context
+            .as_ref()
+            .ok_or(StickyWindowError::EditingIsOff)?;
+        let text = context.reminder.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        NaiveDateTime::parse_from_str(text, TIMESTAMP_FORMAT)
+            .ok()
+            .and_then(|local| Local.from_local_datetime(&local).earliest())
+            .map(|local| Some(local.with_timezone(&Utc)))
+            .ok_or_else(|| StickyWindowError::InvalidReminder(text.to_string()))
+    }
+
     pub fn finish_edit(&mut self) -> Result<String, StickyWindowError> {
         self.edit_context
             .take()
@@ -67,6 +243,14 @@ impl StickyWindow {
             .ok_or(StickyWindowError::EditingIsOff)
     }
 
+    /// Leave edit mode discarding the edited content
+    pub fn cancel_edit(&mut self) -> Result<(), StickyWindowError> {
+        self.edit_context
+            .take()
+            .map(|_| ())
+            .ok_or(StickyWindowError::EditingIsOff)
+    }
+
     pub fn do_edit_action(&mut self, action: Action) -> Result<(), StickyWindowError> {
         self.edit_context
             .as_mut()
@@ -74,6 +258,29 @@ impl StickyWindow {
             .ok_or(StickyWindowError::EditingIsOff)
     }
 
+    /// Find the misspelled words in the edited content, nothing is marked without the checker
+    pub fn check_spelling(&mut self, checker: Option<&SpellChecker>) {
+        if let Some(context) = self.edit_context.as_mut() {
+            context.misspelled = checker
+                .map(|checker| checker.misspelled(&context.content.text()))
+                .unwrap_or_default();
+        }
+    }
+
+    pub fn get_icon_size(&self) -> u16 {
+        self.icon_size
+    }
+
+    /// Set the toolbar icon size limited to the supported range, returns the applied size
+    pub fn set_icon_size(&mut self, icon_size: u16) -> u16 {
+        self.icon_size = icon_size.clamp(MIN_ICON_SIZE, MAX_ICON_SIZE);
+        self.icon_size
+    }
+
+    pub fn set_copied(&mut self, on: bool) {
+        self.is_copied = on;
+    }
+
     pub fn allow_select_style(&mut self, style_names: Vec<String>) {
         self.style_names = Some(style_names);
     }
@@ -82,54 +289,158 @@ impl StickyWindow {
         self.style_names = None;
     }
 
+    // borderless window has no decorations to resize it, so provide the grip in the bottom-right corner
+    fn build_resize_grip<'a>(&self, window_id: Id, icons: &IconSet) -> Element<'a, Message> {
+        widget::row::with_capacity(2)
+            .push(widget::horizontal_space().width(Length::Fill))
+            .push(
+                widget::mouse_area(widget::icon(icons.resize()).size(self.icon_size))
+                    .on_press(Message::NoteResizeStart(window_id)),
+            )
+            .into()
+    }
+
     #[allow(clippy::too_many_lines)]
     pub fn build_view<'a>(
         &'a self,
         window_id: Id,
         notes: &'a NotesCollection,
         icons: &IconSet,
+        high_contrast: bool,
     ) -> Element<'a, Message> {
         if let Some(edit_context) = &self.edit_context {
-            let bgcolor = notes
-                .try_get_note_style(self.get_note_id())
-                .map_or(Color::WHITE, NoteStyle::get_background_color);
+            let note_style = notes.try_get_note_with_style(&self.note_id).ok();
+            let bgcolor = note_style.map_or(Color::WHITE, |(note, style)| {
+                with_opacity(style.get_background_color(), note.get_opacity())
+            });
+            let text_align =
+                note_style.map_or(TextAlign::default(), |(_, style)| style.get_text_align());
 
-            let note_toolbar = widget::row::with_capacity(1).push(
-                icons
-                    .checked()
-                    .apply(widget::button::icon)
-                    .icon_size(self.icon_size)
-                    .on_press(Message::NoteEdit(window_id, false))
-                    .width(Length::Shrink),
-            );
+            // the note is not saved with the invalid reminder, the user either fixes or clears it
+            let reminder_error = self
+                .get_reminder()
+                .is_err()
+                .then(|| widget::text::caption(fl!("reminder-invalid")));
+
+            let mut note_toolbar = widget::row::with_capacity(4)
+                .spacing(cosmic::theme::spacing().space_s)
+                .align_y(Alignment::Center)
+                .push(
+                    icons
+                        .checked()
+                        .apply(widget::button::icon)
+                        .icon_size(self.icon_size)
+                        .on_press_maybe(
+                            reminder_error
+                                .is_none()
+                                .then_some(Message::NoteEdit(window_id, false)),
+                        )
+                        .width(Length::Shrink),
+                )
+                .push(
+                    widget::button::text(fl!("find-replace"))
+                        .on_press(Message::NoteReplaceToggle(window_id)),
+                )
+                .push(
+                    widget::text_input(fl!("reminder-hint"), &edit_context.reminder)
+                        .on_input(move |reminder| Message::NoteReminderInput(window_id, reminder))
+                        .width(Length::Fixed(REMINDER_INPUT_WIDTH)),
+                );
+            if let Ok(note) = notes.try_get_note(&self.note_id) {
+                note_toolbar = note_toolbar.push(widget::text::caption(fl!(
+                    "note-timestamps",
+                    created = note.get_created().format(TIMESTAMP_FORMAT).to_string(),
+                    modified = note.get_modified().format(TIMESTAMP_FORMAT).to_string()
+                )));
+            }
 
             let note_content = widget::container(
                 widget::text_editor(&edit_context.content)
                     .on_action(move |act| Message::Edit(window_id, act))
-                    .height(Length::Fill),
+                    .key_binding(move |key_press| finish_edit_binding(window_id, key_press))
+                    .height(Length::Fill)
+                    .align_x(text_alignment(text_align, &edit_context.content.text()))
+                    .highlight_with::<MisspellingHighlighter>(
+                        edit_context.misspelled.clone(),
+                        misspelling_format,
+                    ),
             )
             .width(Length::Fill)
             .height(Length::Fill);
 
-            with_background(
-                widget::column::with_capacity(2)
+            let replace_row = edit_context.replace.as_ref().map(|context| {
+                widget::row::with_capacity(4)
+                    .spacing(cosmic::theme::spacing().space_s)
+                    .align_y(Alignment::Center)
+                    .push(
+                        widget::text_input(fl!("find-hint"), &context.find)
+                            .on_input(move |find| Message::NoteFindInput(window_id, find)),
+                    )
+                    .push(
+                        widget::text_input(fl!("replace-hint"), &context.replace)
+                            .on_input(move |replace| Message::NoteReplaceInput(window_id, replace)),
+                    )
+                    .push(
+                        widget::checkbox(fl!("match-case"), context.case_sensitive)
+                            .on_toggle(move |on| Message::NoteReplaceCaseSensitive(window_id, on)),
+                    )
+                    .push(widget::button::text(fl!("replace-all")).on_press_maybe(
+                        (!context.find.is_empty()).then(|| {
+                            Message::NoteReplaceAll(
+                                window_id,
+                                context.find.clone(),
+                                context.replace.clone(),
+                            )
+                        }),
+                    ))
+            });
+
+            let mut attachments_row = widget::row::with_capacity(3)
+                .spacing(cosmic::theme::spacing().space_s)
+                .align_y(Alignment::Center)
+                .push(
+                    widget::text_input(fl!("attachment-hint"), &edit_context.attachment).on_input(
+                        move |attachment| Message::NoteAttachmentInput(window_id, attachment),
+                    ),
+                )
+                .push(
+                    widget::button::text(fl!("attach")).on_press_maybe(
+                        (!edit_context.attachment.trim().is_empty())
+                            .then_some(Message::NoteAttachmentAdd(window_id)),
+                    ),
+                );
+            if let Ok(note) = notes.try_get_note(&self.note_id) {
+                for (index, attachment) in note.get_attachments().iter().enumerate() {
+                    attachments_row = attachments_row.push(
+                        widget::button::text(format!("{} ✕", attachment_name(attachment)))
+                            .on_press(Message::NoteAttachmentRemove(window_id, index)),
+                    );
+                }
+            }
+
+            with_note_background(
+                widget::column::with_capacity(6)
                     .push(note_toolbar)
+                    .push_maybe(reminder_error)
+                    .push_maybe(replace_row)
+                    .push(attachments_row)
                     .push(note_content)
+                    .push(self.build_resize_grip(window_id, icons))
                     .into(),
                 bgcolor,
+                note_style.map(|(_, style)| style),
+                high_contrast,
             )
-        } else if let Ok(note) = notes.try_get_note(&self.note_id)
-            && let Ok(style) = notes.try_get_style(&note.style())
-        {
+        } else if let Ok((note, style)) = notes.try_get_note_with_style(&self.note_id) {
             let is_locked = note.is_locked();
 
-            let mut note_toolbar = widget::row::with_capacity(7)
+            let mut note_toolbar = widget::row::with_capacity(14)
                 .spacing(cosmic::theme::spacing().space_s)
                 .push(
                     if is_locked {
-                        icons.unlock()
-                    } else {
                         icons.lock()
+                    } else {
+                        icons.unlock()
                     }
                     .apply(widget::button::icon)
                     .icon_size(self.icon_size)
@@ -145,6 +456,21 @@ impl StickyWindow {
                         .on_press(Message::NoteEdit(window_id, true))
                         .width(Length::Shrink),
                 );
+                // the previous contents are kept until the notes are saved
+                let has_history = note.get_history_len() > 0;
+                note_toolbar = note_toolbar
+                    .push(
+                        icons
+                            .undo()
+                            .apply(widget::button::icon)
+                            .icon_size(self.icon_size)
+                            .on_press_maybe(has_history.then_some(Message::NoteUndo(window_id)))
+                            .width(Length::Shrink),
+                    )
+                    .push_maybe(has_history.then(|| {
+                        widget::button::text(fl!("clear-history"))
+                            .on_press(Message::NoteClearHistory(window_id))
+                    }));
                 if let Some(styles) = &self.style_names {
                     // add style pick list
                     note_toolbar = note_toolbar.push(
@@ -169,16 +495,73 @@ impl StickyWindow {
                             .width(Length::Shrink),
                     );
                 }
+                note_toolbar = note_toolbar
+                    .push(
+                        icons
+                            .hide()
+                            .apply(widget::button::icon)
+                            .icon_size(self.icon_size)
+                            .on_press(Message::NoteHide(window_id))
+                            .width(Length::Shrink),
+                    )
+                    .push(
+                        icons
+                            .delete()
+                            .apply(widget::button::icon)
+                            .icon_size(self.icon_size)
+                            .on_press(Message::NoteDelete(window_id))
+                            .width(Length::Shrink),
+                    )
+                    .push(widget::button::text("-").on_press_maybe(
+                        (self.icon_size > MIN_ICON_SIZE).then_some(Message::NoteIconSize(
+                            window_id,
+                            self.icon_size.saturating_sub(ICON_SIZE_STEP),
+                        )),
+                    ))
+                    .push(widget::button::text("+").on_press_maybe(
+                        (self.icon_size < MAX_ICON_SIZE).then_some(Message::NoteIconSize(
+                            window_id,
+                            self.icon_size.saturating_add(ICON_SIZE_STEP),
+                        )),
+                    ))
+                    .push(
+                        widget::slider(
+                            MIN_OPACITY..=1.0,
+                            note.get_opacity().max(MIN_OPACITY),
+                            move |opacity| Message::NoteOpacity(window_id, opacity),
+                        )
+                        .step(OPACITY_STEP)
+                        .width(Length::Fixed(OPACITY_SLIDER_WIDTH)),
+                    );
+            }
+            if is_locked {
+                // there is no way back by click, the note gets clickable again when unlocked from the applet
                 note_toolbar = note_toolbar.push(
                     icons
-                        .delete()
+                        .click_through()
                         .apply(widget::button::icon)
                         .icon_size(self.icon_size)
-                        .on_press(Message::NoteDelete(window_id))
+                        .on_press(Message::NoteClickThrough(window_id, true))
                         .width(Length::Shrink),
                 );
             }
+            // copying doesn't modify the note, so it is available for the locked one as well
             note_toolbar = note_toolbar
+                .push(
+                    if self.is_copied {
+                        icons.checked()
+                    } else {
+                        icons.copy()
+                    }
+                    .apply(widget::button::icon)
+                    .icon_size(self.icon_size)
+                    .on_press(Message::NoteCopyContent(window_id))
+                    .width(Length::Shrink),
+                )
+                .push(
+                    widget::button::text(fl!("copy-as-json"))
+                        .on_press(Message::NoteCopyAsJson(window_id)),
+                )
                 .push(widget::horizontal_space().width(Length::Fill))
                 .push(
                     icons
@@ -189,25 +572,151 @@ impl StickyWindow {
                         .width(Length::Shrink),
                 );
 
+            let font_size = if high_contrast {
+                style.get_font().size.max(HIGH_CONTRAST_MIN_FONT_SIZE)
+            } else {
+                style.get_font().size
+            };
+            let attachments = (!note.get_attachments().is_empty()).then(|| {
+                widget::row::with_children(
+                    note.get_attachments()
+                        .iter()
+                        .map(|attachment| {
+                            widget::button::link(format!("📎 {}", attachment_name(attachment)))
+                                .on_press(open_attachment(attachment))
+                                .into()
+                        })
+                        .collect(),
+                )
+                .spacing(cosmic::theme::spacing().space_s)
+            });
             let note_content = widget::column::with_capacity(2)
                 .width(Length::Fill)
                 .height(Length::Fill)
                 .push(
                     widget::text(note.get_content())
-                        .font(cosmic_font(style.get_font().style))
-                        .size(style.get_font().size),
-                );
+                        .font(cosmic_font(style.get_font()))
+                        .size(font_size)
+                        .align_x(text_alignment(style.get_text_align(), note.get_content()))
+                        .width(Length::Fill),
+                )
+                .push_maybe(attachments);
 
-            with_background(
-                widget::column::with_capacity(2)
+            with_note_background(
+                widget::column::with_capacity(3)
                     .push(note_toolbar)
                     .push(note_content)
+                    .push(self.build_resize_grip(window_id, icons))
                     .into(),
-                style.get_background_color(),
+                with_opacity(style.get_background_color(), note.get_opacity()),
+                Some(style),
+                high_contrast,
             )
         } else {
-            // build problem view
-            widget::text("problem-text").into()
+            self.build_problem_view(window_id, notes, icons)
         }
     }
+
+    // the note is missing or neither its style nor the default one exists
+    fn build_problem_view<'a>(
+        &self,
+        window_id: Id,
+        notes: &NotesCollection,
+        icons: &IconSet,
+    ) -> Element<'a, Message> {
+        let note_exists = notes.try_get_note(&self.note_id).is_ok();
+        let description = if note_exists {
+            fl!("problem-style-missing")
+        } else {
+            fl!("problem-note-missing")
+        };
+        let default_style_index = notes.try_get_default_style_index().ok();
+        // the default style can be assigned to the existing note only
+        let reset_style = note_exists.then(|| {
+            widget::button::text(fl!("problem-reset-style")).on_press_maybe(
+                default_style_index.map(|index| Message::NoteStyleSelected(window_id, index)),
+            )
+        });
+        let card = widget::column::with_capacity(4)
+            .spacing(cosmic::theme::spacing().space_s)
+            .padding(cosmic::theme::spacing().space_s)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .push(widget::text::heading(fl!("problem-title")))
+            .push(widget::text(description))
+            .push(widget::text::caption(fl!("problem-text")))
+            .push_maybe(reset_style);
+        widget::column::with_capacity(2)
+            .push(card)
+            .push(self.build_resize_grip(window_id, icons))
+            .into()
+    }
+}
+
+// Ctrl+Enter saves the content and finishes editing as the "checked" button does
+fn finish_edit_binding(window_id: Id, key_press: KeyPress) -> Option<Binding<Message>> {
+    if key_press.modifiers.control() && key_press.key == Key::Named(Named::Enter) {
+        Some(Binding::Custom(Message::NoteEdit(window_id, false)))
+    } else {
+        Binding::from_key_press(key_press)
+    }
+}
+
+// replaces all occurrences of `find`, returns the result and the number of replacements
+fn replace_all(text: &str, find: &str, replace: &str, case_sensitive: bool) -> (String, usize) {
+    if find.is_empty() {
+        return (text.to_string(), 0);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut rest = text;
+    while let Some((start, end)) = find_in(rest, find, case_sensitive) {
+        result.push_str(&rest[..start]);
+        result.push_str(replace);
+        rest = &rest[end..];
+        count += 1;
+    }
+    result.push_str(rest);
+    (result, count)
+}
+
+// the byte range of the first occurrence of `needle`
+fn find_in(haystack: &str, needle: &str, case_sensitive: bool) -> Option<(usize, usize)> {
+    if case_sensitive {
+        return haystack
+            .find(needle)
+            .map(|start| (start, start + needle.len()));
+    }
+    // lowercase may differ in length, so compare char by char
+    haystack.char_indices().find_map(|(start, _)| {
+        let mut end = start;
+        let mut chars = haystack[start..].chars();
+        needle
+            .chars()
+            .all(|n| {
+                chars.next().is_some_and(|h| {
+                    end += h.len_utf8();
+                    h.to_lowercase().eq(n.to_lowercase())
+                })
+            })
+            .then_some((start, end))
+    })
+}
+
+// short name of the attached file or URI
+fn attachment_name(attachment: &str) -> &str {
+    attachment
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or(attachment)
+}
+
+fn open_attachment(attachment: &str) -> Message {
+    if attachment.contains("://") {
+        Message::OpenUrl(attachment.to_string())
+    } else {
+        Message::OpenPath(attachment.to_string())
+    }
 }
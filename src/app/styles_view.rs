@@ -12,27 +12,68 @@ use cosmic::{
     iced::{Length, widget::keyed_column},
     widget,
 };
+use std::collections::{BTreeMap, HashMap, HashSet};
 use uuid::Uuid;
 
 pub fn build_styles_list_view<'a>(
     notes: &'a NotesCollection,
     icons: &IconSet,
     icon_size: u16,
+    reassign_targets: &HashMap<Uuid, usize>,
+    collapsed_groups: &HashSet<String>,
 ) -> Element<'a, Message> {
+    let style_names = notes.get_style_names();
+    // the last style cannot be deleted
+    let can_delete = notes.get_styles_count() > 1;
+    // ungrouped styles (None) are sorted first
+    let mut groups: BTreeMap<Option<&str>, Vec<(&Uuid, &NoteStyle)>> = BTreeMap::new();
+    for (style_id, style) in notes.iter_styles() {
+        groups
+            .entry(style.get_group())
+            .or_default()
+            .push((style_id, style));
+    }
+    let mut styles_list =
+        widget::column::with_capacity(2 * groups.len()).spacing(cosmic::theme::spacing().space_s);
+    for (group, styles) in groups {
+        if let Some(group) = group {
+            let is_collapsed = collapsed_groups.contains(group);
+            styles_list = styles_list.push(
+                widget::button::text(fl!(
+                    "style-group-header",
+                    group = group,
+                    count = styles.len()
+                ))
+                .on_press(Message::StyleGroupToggle(group.to_string())),
+            );
+            if is_collapsed {
+                continue;
+            }
+        }
+        styles_list =
+            styles_list.push(keyed_column(styles.into_iter().map(|(style_id, style)| {
+                (
+                    *style_id,
+                    build_style_list_item(
+                        *style_id,
+                        style,
+                        notes,
+                        icons,
+                        icon_size,
+                        &style_names,
+                        reassign_targets.get(style_id).copied(),
+                        can_delete,
+                    ),
+                )
+            })));
+    }
     widget::column::with_capacity(2)
         .spacing(cosmic::theme::spacing().space_m)
         .push(widget::text(fl!("styles-list-description")))
         .push(
-            widget::scrollable(keyed_column(notes.iter_styles().map(
-                |(style_id, style)| {
-                    (
-                        *style_id,
-                        build_style_list_item(*style_id, style, icons, icon_size),
-                    )
-                },
-            )))
-            .width(Length::Fill)
-            .height(Length::Fill),
+            widget::scrollable(styles_list)
+                .width(Length::Fill)
+                .height(Length::Fill),
         )
         .width(Length::Fill)
         .height(Length::Fill)
@@ -42,39 +83,66 @@ pub fn build_styles_list_view<'a>(
 fn build_style_list_item<'a>(
     style_id: Uuid,
     style: &'a NoteStyle,
+    notes: &NotesCollection,
     icons: &IconSet,
     icon_size: u16,
+    style_names: &[String],
+    reassign_target: Option<usize>,
+    can_delete: bool,
 ) -> Element<'a, Message> {
-    let child = widget::row::with_capacity(3)
-        .spacing(cosmic::theme::spacing().space_s)
-        .width(Length::Fill)
-        .push(
-            widget::text(format!(
-                "{}, {}: {}",
-                style.get_name(),
-                fl!("view-style-font"),
-                style.get_font().style
+    let usage = match notes.count_notes_with_style(&style_id) {
+        0 => fl!("style-unused"),
+        count => fl!("style-used-by", count = count),
+    };
+    let child =
+        widget::row::with_capacity(7)
+            .spacing(cosmic::theme::spacing().space_s)
+            .width(Length::Fill)
+            .push(
+                widget::text(format!(
+                    "{}, {}: {}",
+                    style.get_name(),
+                    fl!("view-style-font"),
+                    style.get_font().style
+                ))
+                .font(cosmic_font(style.get_font()))
+                .size(style.get_font().size)
+                .width(Length::Fill),
+            )
+            .push(widget::text::caption(usage))
+            .push(
+                widget::dropdown(style_names.to_vec(), reassign_target, move |index| {
+                    Message::ReassignStyleTarget(style_id, index)
+                })
+                .placeholder(fl!("reassign-style-target")),
+            )
+            .push(widget::button::text(fl!("reassign-style")).on_press_maybe(
+                reassign_target.map(|index| Message::ReassignStyle(style_id, index)),
             ))
-            .font(cosmic_font(style.get_font().style))
-            .size(style.get_font().size)
-            .width(Length::Fill),
-        )
-        .push(
-            icons
-                .edit()
-                .apply(widget::button::icon)
-                .icon_size(icon_size)
-                .on_press(Message::StyleEdit(style_id))
-                .width(Length::Shrink),
-        )
-        .push(
-            icons
-                .delete()
-                .apply(widget::button::icon)
-                .icon_size(icon_size)
-                .on_press(Message::StyleDelete(style_id))
-                .width(Length::Shrink),
-        )
-        .into();
+            .push(
+                icons
+                    .edit()
+                    .apply(widget::button::icon)
+                    .icon_size(icon_size)
+                    .on_press(Message::StyleEdit(style_id))
+                    .width(Length::Shrink),
+            )
+            .push(
+                icons
+                    .copy()
+                    .apply(widget::button::icon)
+                    .icon_size(icon_size)
+                    .on_press(Message::StyleDuplicate(style_id))
+                    .width(Length::Shrink),
+            )
+            .push(
+                icons
+                    .delete()
+                    .apply(widget::button::icon)
+                    .icon_size(icon_size)
+                    .on_press_maybe(can_delete.then_some(Message::StyleDelete(style_id)))
+                    .width(Length::Shrink),
+            )
+            .into();
     with_background(child, style.get_background_color())
 }
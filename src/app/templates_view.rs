@@ -0,0 +1,115 @@
+use super::service::Message;
+use crate::{
+    fl,
+    icons::IconSet,
+    notes::{NoteTemplate, NotesCollection},
+};
+use cosmic::prelude::*;
+use cosmic::{
+    iced::{Alignment, Length},
+    widget,
+};
+
+// enough for a few lines of the template content, the longer content is scrolled
+const TEMPLATE_EDITOR_HEIGHT: f32 = 120.0;
+
+// the content of the template having `template_editor` index is edited in the multiline editor
+pub fn build_templates_list_view<'a>(
+    notes: &'a NotesCollection,
+    icons: &IconSet,
+    icon_size: u16,
+    template_editor: Option<(usize, &'a widget::text_editor::Content)>,
+) -> Element<'a, Message> {
+    let style_names = notes.get_style_names();
+    widget::column::with_capacity(3)
+        .spacing(cosmic::theme::spacing().space_s)
+        .push(widget::text::heading(fl!("templates-title")))
+        .push(widget::text::caption(fl!("templates-description")))
+        .extend(
+            notes
+                .templates()
+                .iter()
+                .enumerate()
+                .map(|(index, template)| {
+                    let content = template_editor
+                        .and_then(|(edited, content)| (edited == index).then_some(content));
+                    build_template_list_item(
+                        index,
+                        template,
+                        content,
+                        notes,
+                        icons,
+                        icon_size,
+                        &style_names,
+                    )
+                }),
+        )
+        .push(widget::button::text(fl!("create-new-template")).on_press(Message::TemplateNew))
+        .width(Length::Fill)
+        .into()
+}
+
+fn build_template_list_item<'a>(
+    index: usize,
+    template: &'a NoteTemplate,
+    edited_content: Option<&'a widget::text_editor::Content>,
+    notes: &NotesCollection,
+    icons: &IconSet,
+    icon_size: u16,
+    style_names: &[String],
+) -> Element<'a, Message> {
+    let style_index = notes
+        .iter_styles()
+        .position(|(style_id, _)| *style_id == template.style());
+    // the first line of the content is shown until the content is edited
+    let content_line = template.get_content().lines().next().unwrap_or_default();
+    let row = widget::row::with_capacity(5)
+        .spacing(cosmic::theme::spacing().space_s)
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .push(
+            widget::text_input(fl!("template-name-hint"), template.get_name())
+                .on_input(move |name| Message::TemplateNameInput(index, name))
+                .width(Length::FillPortion(1)),
+        )
+        .push(
+            widget::text::caption(if content_line.is_empty() {
+                fl!("template-content-hint")
+            } else {
+                content_line.to_string()
+            })
+            .width(Length::FillPortion(2)),
+        )
+        .push(
+            icons
+                .edit()
+                .apply(widget::button::icon)
+                .icon_size(icon_size)
+                .on_press(Message::TemplateEditContent(index))
+                .width(Length::Shrink),
+        )
+        .push(
+            widget::dropdown(style_names.to_vec(), style_index, move |style_index| {
+                Message::TemplateStyleSelected(index, style_index)
+            })
+            .placeholder(fl!("template-style")),
+        )
+        .push(
+            icons
+                .delete()
+                .apply(widget::button::icon)
+                .icon_size(icon_size)
+                .on_press(Message::TemplateDelete(index))
+                .width(Length::Shrink),
+        );
+    widget::column::with_capacity(2)
+        .spacing(cosmic::theme::spacing().space_xxs)
+        .push(row)
+        .push_maybe(edited_content.map(|content| {
+            widget::text_editor(content)
+                .placeholder(fl!("template-content-hint"))
+                .on_action(Message::TemplateContentEdit)
+                .height(Length::Fixed(TEMPLATE_EDITOR_HEIGHT))
+        }))
+        .into()
+}
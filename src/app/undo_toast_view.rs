@@ -0,0 +1,24 @@
+use super::service::Message;
+use crate::{fl, notes::NotesCollection};
+use cosmic::prelude::*;
+use cosmic::{
+    iced::{Alignment, Length},
+    widget,
+};
+use uuid::Uuid;
+
+/// The short-lived popup offering to restore just deleted note
+pub fn build_undo_toast_view(notes: &NotesCollection, note_id: Uuid) -> Element<'_, Message> {
+    let title = notes
+        .iter_deleted_notes()
+        .find_map(|(id, note)| (*id == note_id).then(|| note.get_title()))
+        .unwrap_or_default();
+    widget::row::with_capacity(2)
+        .spacing(cosmic::theme::spacing().space_s)
+        .align_y(Alignment::Center)
+        .push(widget::text(fl!("note-deleted", title = title)).width(Length::Fill))
+        .push(widget::button::suggested(fl!("undo")).on_press(Message::NoteRestore(note_id)))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
@@ -1,12 +1,38 @@
 use super::service::Message;
-use crate::notes::FontStyle;
+use crate::notes::{self, FontStyle, NoteStyle, TextAlign};
 use cosmic::prelude::*;
 use cosmic::{
     font::{self, Font},
-    iced::{self, Color},
+    iced::{
+        self, Color, ContentFit, Length, Size, advanced::graphics::text::font_system,
+        alignment::Horizontal, font::Family,
+    },
     widget,
 };
 use palette::Srgba;
+use std::{
+    path::Path,
+    sync::{LazyLock, PoisonError},
+};
+
+// the minimum font size of notes in high contrast mode
+pub const HIGH_CONTRAST_MIN_FONT_SIZE: u16 = 18;
+
+// font family names must live as long as the application, so only the installed families are used
+static FONT_FAMILIES: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let mut font_system = font_system()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    let mut families: Vec<String> = font_system
+        .raw()
+        .db()
+        .faces()
+        .flat_map(|face| face.families.iter().map(|(name, _)| name.clone()))
+        .collect();
+    families.sort_unstable();
+    families.dedup();
+    families
+});
 
 #[inline]
 #[must_use]
@@ -22,22 +48,140 @@ pub const fn to_f32(v: usize) -> f32 {
     v as f32
 }
 
+// the number of cascade steps if the screen size is unknown
+const DEF_CASCADE_STEPS: usize = 10;
+
+/// The position of the `index`-th note in the cascade shifted by `step` right and down.
+/// The cascade starts over from the top left corner when the note would cross the screen edge.
+#[must_use]
+pub fn cascade_position(
+    index: usize,
+    step: usize,
+    note_size: Size<usize>,
+    screen: Option<Size>,
+) -> (usize, usize) {
+    if step == 0 {
+        return (0, 0);
+    }
+    let steps = screen.map_or(DEF_CASCADE_STEPS, |screen| {
+        let free_width = to_usize(screen.width).saturating_sub(note_size.width);
+        let free_height = to_usize(screen.height).saturating_sub(note_size.height);
+        free_width.min(free_height) / step
+    });
+    let offset = (index % (steps + 1)) * step;
+    (offset, offset)
+}
+
 #[inline]
 pub const fn text_color() -> Srgba {
     Srgba::new(0.08, 0.08, 0.08, 1.0)
 }
 
+#[inline]
+pub const fn high_contrast_text_color() -> Srgba {
+    Srgba::new(1.0, 1.0, 1.0, 1.0)
+}
+
+#[inline]
+pub const fn high_contrast_background_color() -> Srgba {
+    Srgba::new(0.0, 0.0, 0.0, 1.0)
+}
+
+/// The radius of note corners unless the style defines its own one
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn theme_corner_radius() -> u16 {
+    cosmic::theme::active().cosmic().corner_radii.radius_s[0] as u16
+}
+
+/// Multiplies the color alpha by the note opacity
+#[must_use]
+pub fn with_opacity(color: Color, opacity: f32) -> Color {
+    Color {
+        a: color.a * opacity,
+        ..color
+    }
+}
+
 pub fn with_background(child: Element<'_, Message>, bgcolor: Color) -> Element<'_, Message> {
+    with_colors(child, bgcolor, Color::from(text_color()), None)
+}
+
+/// Ignores the style colors, image and border if `high_contrast` is on, the saved style stays unchanged
+pub fn with_note_background<'a>(
+    child: Element<'a, Message>,
+    bgcolor: Color,
+    style: Option<&NoteStyle>,
+    high_contrast: bool,
+) -> Element<'a, Message> {
+    if high_contrast {
+        with_colors(
+            child,
+            Color::from(high_contrast_background_color()),
+            Color::from(high_contrast_text_color()),
+            None,
+        )
+    } else {
+        with_colors(
+            with_background_image(child, style.and_then(NoteStyle::get_found_background_image)),
+            bgcolor,
+            Color::from(text_color()),
+            style,
+        )
+    }
+}
+
+// layers the image under the child, the image is skipped if the file is missing,
+// so the background color is displayed instead, see NoteStyle::get_found_background_image()
+fn with_background_image<'a>(
+    child: Element<'a, Message>,
+    background_image: Option<&str>,
+) -> Element<'a, Message> {
+    let Some(path) = background_image else {
+        return child;
+    };
+    let is_svg = Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    let image: Element<'a, Message> = if is_svg {
+        widget::svg(widget::svg::Handle::from_path(path))
+            .content_fit(ContentFit::Cover)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    } else {
+        widget::image(widget::image::Handle::from_path(path))
+            .content_fit(ContentFit::Cover)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    };
+    iced::widget::Stack::new().push(image).push(child).into()
+}
+
+// the corners and border follow the style if any, otherwise the theme corners are drawn without border
+fn with_colors<'a>(
+    child: Element<'a, Message>,
+    bgcolor: Color,
+    fgcolor: Color,
+    style: Option<&NoteStyle>,
+) -> Element<'a, Message> {
+    let corner_radius = style.and_then(NoteStyle::get_corner_radius);
+    let border_width = style.map_or(0, NoteStyle::get_border_width);
+    let border_color = style.map_or(Color::TRANSPARENT, NoteStyle::get_border_color);
     widget::container(child)
         .class(cosmic::style::Container::custom(move |theme: &Theme| {
             let cosmic = theme.cosmic();
             iced::widget::container::Style {
-                icon_color: Some(Color::from(text_color())),
-                text_color: Some(Color::from(text_color())),
+                icon_color: Some(fgcolor),
+                text_color: Some(fgcolor),
                 background: Some(iced::Background::Color(bgcolor)),
                 border: iced::Border {
-                    radius: cosmic.corner_radii.radius_s.into(),
-                    ..Default::default()
+                    radius: corner_radius.map_or(cosmic.corner_radii.radius_s.into(), |radius| {
+                        f32::from(radius).into()
+                    }),
+                    width: f32::from(border_width),
+                    color: border_color,
                 },
                 shadow: iced::Shadow::default(),
             }
@@ -46,12 +190,49 @@ pub fn with_background(child: Element<'_, Message>, bgcolor: Color) -> Element<'
         .into()
 }
 
-pub fn cosmic_font(font_style: FontStyle) -> Font {
-    match font_style {
+pub fn cosmic_font(note_font: &notes::Font) -> Font {
+    let font = match note_font.style {
         FontStyle::Default => font::default(),
         FontStyle::Light => font::light(),
         FontStyle::Semibold => font::semibold(),
         FontStyle::Bold => font::bold(),
         FontStyle::Monospace => font::mono(),
+    };
+    if let Some(family) = note_font.family.as_deref().and_then(find_font_family) {
+        Font {
+            family: Family::Name(family),
+            ..font
+        }
+    } else {
+        font
+    }
+}
+
+// the start of right-to-left text is on the right, the first letter tells the text direction
+pub fn text_alignment(text_align: TextAlign, text: &str) -> Horizontal {
+    match (text_align, is_right_to_left(text)) {
+        (TextAlign::Start, false) | (TextAlign::End, true) => Horizontal::Left,
+        (TextAlign::Center, _) => Horizontal::Center,
+        (TextAlign::End, false) | (TextAlign::Start, true) => Horizontal::Right,
     }
 }
+
+// Hebrew, Arabic, Syriac, Thaana, N'Ko and other right-to-left scripts with their presentation forms
+fn is_right_to_left(text: &str) -> bool {
+    text.chars().find(|c| c.is_alphabetic()).is_some_and(|c| {
+        matches!(c,
+                '\u{0590}'..='\u{08FF}'
+                | '\u{FB1D}'..='\u{FDFF}'
+                | '\u{FE70}'..='\u{FEFF}'
+                | '\u{10800}'..='\u{10FFF}'
+                | '\u{1E800}'..='\u{1EFFF}')
+    })
+}
+
+// the family is not installed while its name is being typed, the font of the style is used then
+fn find_font_family(family: &str) -> Option<&'static str> {
+    FONT_FAMILIES
+        .iter()
+        .find(|installed| installed.eq_ignore_ascii_case(family))
+        .map(String::as_str)
+}
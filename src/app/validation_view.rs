@@ -0,0 +1,44 @@
+use super::service::Message;
+use crate::{fl, notes::ValidationReport};
+use cosmic::iced::Length;
+use cosmic::prelude::*;
+use cosmic::widget;
+
+pub fn build_validation_view<'a>(
+    path: &'a str,
+    report: &'a Result<ValidationReport, String>,
+) -> Element<'a, Message> {
+    let header = widget::text::heading(path);
+    let content: Element<'a, Message> = match report {
+        Ok(report) => widget::column::with_capacity(report.unknown_styles.len() + 3)
+            .spacing(cosmic::theme::spacing().space_xxs)
+            .push(widget::text(fl!(
+                "validation-counts",
+                notes = report.notes_count,
+                styles = report.styles_count
+            )))
+            .push_maybe((!report.has_issues()).then(|| widget::text(fl!("validation-no-issues"))))
+            .push_maybe(
+                report
+                    .missing_default_style
+                    .then(|| widget::text(fl!("validation-missing-default-style"))),
+            )
+            .extend(report.unknown_styles.iter().map(|(note_id, style_id)| {
+                widget::text(fl!(
+                    "validation-unknown-style",
+                    note = note_id.to_string(),
+                    style = style_id.to_string()
+                ))
+                .into()
+            }))
+            .into(),
+        Err(e) => widget::text(fl!("validation-failed", error = e.as_str())).into(),
+    };
+    widget::column::with_capacity(2)
+        .spacing(cosmic::theme::spacing().space_m)
+        .push(header)
+        .push(widget::scrollable(content).width(Length::Fill))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
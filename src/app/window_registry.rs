@@ -0,0 +1,103 @@
+use super::sticky_window::StickyWindow;
+use crate::notes::{NoteData, NotesCollection, NotesCollectionError};
+use cosmic::iced::window::Id;
+use std::collections::HashMap;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum WindowRegistryError {
+    #[error("sticky window {0} is not found")]
+    WindowNotFound(Id),
+    #[error("{0}")]
+    Note(#[from] NotesCollectionError),
+}
+
+/// Keeps the open sticky windows and resolves window id into the note displayed by the window
+#[derive(Default)]
+pub struct WindowRegistry {
+    windows: HashMap<Id, StickyWindow>,
+}
+
+impl WindowRegistry {
+    pub fn insert(&mut self, id: Id, sticky_window: StickyWindow) {
+        self.windows.insert(id, sticky_window);
+    }
+
+    pub fn remove(&mut self, id: Id) -> Option<StickyWindow> {
+        self.windows.remove(&id)
+    }
+
+    /// Forget all of the windows returning their ids to close them
+    pub fn drain_ids(&mut self) -> Vec<Id> {
+        self.windows.drain().map(|(id, _)| id).collect()
+    }
+
+    pub fn contains(&self, id: Id) -> bool {
+        self.windows.contains_key(&id)
+    }
+
+    pub fn get(&self, id: Id) -> Option<&StickyWindow> {
+        self.windows.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: Id) -> Option<&mut StickyWindow> {
+        self.windows.get_mut(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &StickyWindow)> {
+        self.windows
+            .iter()
+            .map(|(id, sticky_window)| (*id, sticky_window))
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut StickyWindow> {
+        self.windows.values_mut()
+    }
+
+    /// Find the window displaying the note if any
+    pub fn find_window(&self, note_id: Uuid) -> Option<Id> {
+        self.windows
+            .iter()
+            .find_map(|(id, sticky_window)| (sticky_window.get_note_id() == note_id).then_some(*id))
+    }
+
+    pub fn try_get_note_id(&self, id: Id) -> Result<Uuid, WindowRegistryError> {
+        self.windows
+            .get(&id)
+            .map(StickyWindow::get_note_id)
+            .ok_or(WindowRegistryError::WindowNotFound(id))
+    }
+
+    pub fn try_get_note<'a>(
+        &self,
+        id: Id,
+        notes: &'a NotesCollection,
+    ) -> Result<&'a NoteData, WindowRegistryError> {
+        let note_id = self.try_get_note_id(id)?;
+        Ok(notes.try_get_note(&note_id)?)
+    }
+
+    pub fn try_get_note_mut<'a>(
+        &self,
+        id: Id,
+        notes: &'a mut NotesCollection,
+    ) -> Result<&'a mut NoteData, WindowRegistryError> {
+        let note_id = self.try_get_note_id(id)?;
+        Ok(notes.try_get_note_mut(&note_id)?)
+    }
+
+    /// Resolve both the window and its note to change them together
+    pub fn try_get_with_note_mut<'a>(
+        &'a mut self,
+        id: Id,
+        notes: &'a mut NotesCollection,
+    ) -> Result<(&'a mut StickyWindow, &'a mut NoteData), WindowRegistryError> {
+        let sticky_window = self
+            .windows
+            .get_mut(&id)
+            .ok_or(WindowRegistryError::WindowNotFound(id))?;
+        let note = notes.try_get_note_mut(&sticky_window.get_note_id())?;
+        Ok((sticky_window, note))
+    }
+}
@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use sticky_notes::{app::AppletModel, i18n};
+use sticky_notes::{
+    app::{AppletFlags, AppletModel, app_id},
+    i18n,
+};
 
 fn main() -> cosmic::iced::Result {
     tracing_subscriber::fmt::init();
@@ -11,6 +14,9 @@ fn main() -> cosmic::iced::Result {
     // Enable localizations to be applied.
     i18n::init(&requested_languages);
 
-    // Starts the application's event loop with `()` as the application's flags.
-    cosmic::applet::run::<AppletModel>(())
+    // Starts the application's event loop, the applet talks to the service of the same app id
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    cosmic::applet::run::<AppletModel>(AppletFlags {
+        app_id: app_id(&args),
+    })
 }
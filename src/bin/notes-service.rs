@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use sticky_notes::{
-    app::{ServiceFlags, ServiceModel},
-    i18n,
+    app::{ServiceFlags, ServiceModel, app_id, exit_code, try_run_headless_export},
+    fl, i18n,
 };
 
 fn main() -> cosmic::iced::Result {
@@ -11,9 +11,24 @@ fn main() -> cosmic::iced::Result {
     // Get the system's preferred languages.
     let requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
 
-    // Enable localizations to be applied.
+    // Enable localizations to be applied, the headless export reports errors to the user too.
     i18n::init(&requested_languages);
 
+    // Export notes and exit without GUI if requested, e.g. for backups from cron
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let app_id = app_id(&args);
+    match try_run_headless_export(&args, app_id) {
+        Some(Ok(count)) => {
+            tracing::info!("exported {count} notes");
+            std::process::exit(0);
+        }
+        Some(Err(e)) => {
+            eprintln!("{}", fl!("headless-export-failed", error = e.to_string()));
+            std::process::exit(1);
+        }
+        None => {}
+    }
+
     // Settings for configuring the application window and iced runtime.
     let settings = cosmic::app::Settings::default()
         .size_limits(
@@ -25,7 +40,20 @@ fn main() -> cosmic::iced::Result {
         .exit_on_close(false)
         .no_main_window(true);
 
-    // Starts the application's event loop with `()` as the application's flags.
-    //cosmic::app::run::<ServiceModel>(settings, ServiceFlags)
-    cosmic::app::run_single_instance::<ServiceModel>(settings, ServiceFlags)
+    // Starts the application's event loop, the service serves DBus activation under its own app id,
+    // so the single instance mode of libcosmic bound to the constant app id is not used
+    let result = cosmic::app::run::<ServiceModel>(
+        settings,
+        ServiceFlags {
+            requested_languages,
+            app_id,
+        },
+    );
+
+    // the service has exited normally, though it may have failed saving notes on exit
+    let code = exit_code();
+    if code != 0 {
+        std::process::exit(code);
+    }
+    result
 }
@@ -1,23 +1,51 @@
 // SPDX-License-Identifier: MPL-2.0
+use std::{ops::RangeInclusive, time::Duration};
+
 use cosmic::{
     cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry},
-    iced::Size,
+    iced::{Color, Size},
 };
 
 use crate::app::to_f32;
 
 const DEF_DATA_FILE: &str = ".config/indicator-stickynotes";
+const DEF_EXPORT_FILE: &str = "indicator-stickynotes-export";
 const DEF_SERVICE_BIN: &str = "/usr/local/bin/notes-service";
 const ICON_SIZE: u16 = 16;
+// muted enough to not dazzle on dark desktop but still readable with dark note text
+const DARK_THEME_NOTE_COLOR: Color = Color::from_rgb(0.62, 0.64, 0.68);
+const MIN_FONT_SIZE: u16 = 6;
+const MAX_FONT_SIZE: u16 = 72;
+const MAX_RECENT_COLORS: usize = 10;
+
+/// What happens to the note if its window is closed by the compositor rather than by the note toolbar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum WindowCloseAction {
+    #[default]
+    HideNote,
+    DeleteNote,
+}
 
-#[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq)]
+/// The group of actions in the applet popup which may be collapsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum MenuSection {
+    Notes,
+    File,
+    View,
+    App,
+}
+
+#[derive(Debug, Clone, CosmicConfigEntry, PartialEq)]
 #[version = 1]
 pub struct Config {
     pub import_file: String,
+    pub export_file: String,
     pub notes: String,
     pub service_bin: String,
     pub restore_notes_width: usize,
     pub restore_notes_height: usize,
+    pub overview_width: usize,
+    pub overview_height: usize,
     pub edit_style_width: usize,
     pub edit_style_height: usize,
     pub about_width: usize,
@@ -25,34 +53,139 @@ pub struct Config {
     pub toolbar_icon_size: u16,
     pub note_min_width: usize,
     pub note_min_height: usize,
+    pub note_max_width: usize,
+    pub note_max_height: usize,
+    pub new_note_width: usize,
+    pub new_note_height: usize,
+    pub title_show_style: bool,
+    pub reposition_offscreen: bool,
+    pub font_size_min: u16,
+    pub font_size_max: u16,
+    pub high_contrast: bool,
+    pub default_color_follows_theme: bool,
+    pub notify_on_service_start: bool,
+    pub undo_history_limit: usize,
+    pub recent_colors: Vec<[f32; 3]>,
+    pub on_window_close: WindowCloseAction,
+    pub prune_empty_on_load: bool,
+    pub merge_styles_by_name: bool,
+    pub collapsed_menu_sections: Vec<MenuSection>,
+    pub auto_hide_after_mins: u32,
+    pub spellcheck: bool,
+    pub max_live_notes: usize,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            import_file: dirs_next::home_dir().map_or_else(
-                || DEF_DATA_FILE.to_string(),
-                |mut home| {
-                    home.push(DEF_DATA_FILE);
-                    home.display().to_string()
-                },
-            ),
+            import_file: in_home_dir(DEF_DATA_FILE),
+            export_file: in_home_dir(DEF_EXPORT_FILE),
             notes: String::new(),
             service_bin: DEF_SERVICE_BIN.to_string(),
             restore_notes_width: 480,
             restore_notes_height: 400,
+            overview_width: 720,
+            overview_height: 540,
             edit_style_width: 480,
             edit_style_height: 800,
             about_width: 480,
             about_height: 840,
             note_min_width: 64,
             note_min_height: 64,
+            note_max_width: 4096,
+            note_max_height: 4096,
+            new_note_width: 400,
+            new_note_height: 300,
             toolbar_icon_size: ICON_SIZE,
+            title_show_style: false,
+            reposition_offscreen: true,
+            font_size_min: MIN_FONT_SIZE,
+            font_size_max: MAX_FONT_SIZE,
+            high_contrast: false,
+            default_color_follows_theme: false,
+            notify_on_service_start: true,
+            undo_history_limit: 20,
+            recent_colors: Vec::new(),
+            on_window_close: WindowCloseAction::default(),
+            prune_empty_on_load: false,
+            merge_styles_by_name: false,
+            collapsed_menu_sections: Vec::new(),
+            auto_hide_after_mins: 0,
+            spellcheck: false,
+            max_live_notes: 0,
         }
     }
 }
 
 impl Config {
+    /// The background colors recently chosen for styles, the most recent is the first one
+    #[must_use]
+    pub fn recent_colors(&self) -> Vec<Color> {
+        self.recent_colors
+            .iter()
+            .map(|rgb| Color::from(*rgb))
+            .collect()
+    }
+
+    /// Get recent colors with the color put first, the oldest ones are dropped to fit the limit
+    #[must_use]
+    pub fn with_recent_color(&self, color: Color) -> Vec<[f32; 3]> {
+        let rgb = [color.r, color.g, color.b];
+        std::iter::once(rgb)
+            .chain(
+                self.recent_colors
+                    .iter()
+                    .copied()
+                    .filter(|recent| *recent != rgb),
+            )
+            .take(MAX_RECENT_COLORS)
+            .collect()
+    }
+
+    /// How long notes stay shown without interaction, they are never hidden if not set
+    #[must_use]
+    pub fn auto_hide_timeout(&self) -> Option<Duration> {
+        (self.auto_hide_after_mins > 0)
+            .then(|| Duration::from_secs(u64::from(self.auto_hide_after_mins) * 60))
+    }
+
+    /// How many notes may be shown at once, the oldest ones are hidden to fit, unlimited if not set
+    #[must_use]
+    pub fn live_notes_limit(&self) -> Option<usize> {
+        (self.max_live_notes > 0).then_some(self.max_live_notes)
+    }
+
+    #[must_use]
+    pub fn is_menu_section_collapsed(&self, section: MenuSection) -> bool {
+        self.collapsed_menu_sections.contains(&section)
+    }
+
+    /// Get collapsed sections of the applet popup with the section collapsed or expanded
+    #[must_use]
+    pub fn with_menu_section_toggled(&self, section: MenuSection) -> Vec<MenuSection> {
+        if self.is_menu_section_collapsed(section) {
+            self.collapsed_menu_sections
+                .iter()
+                .copied()
+                .filter(|collapsed| *collapsed != section)
+                .collect()
+        } else {
+            let mut collapsed = self.collapsed_menu_sections.clone();
+            collapsed.push(section);
+            collapsed
+        }
+    }
+
+    /// The file to export notes into, falls back to the default one if not set
+    #[must_use]
+    pub fn export_path(&self) -> String {
+        if self.export_file.is_empty() {
+            in_home_dir(DEF_EXPORT_FILE)
+        } else {
+            self.export_file.clone()
+        }
+    }
+
     #[must_use]
     pub fn restore_notes_size(&self) -> Size {
         Size::new(
@@ -61,6 +194,11 @@ impl Config {
         )
     }
 
+    #[must_use]
+    pub fn overview_size(&self) -> Size {
+        Size::new(to_f32(self.overview_width), to_f32(self.overview_height))
+    }
+
     #[must_use]
     pub fn edit_style_size(&self) -> Size {
         Size::new(
@@ -74,8 +212,48 @@ impl Config {
         Size::new(to_f32(self.about_width), to_f32(self.about_height))
     }
 
+    /// The background of the new note styles, depends on the system theme if configured
+    #[must_use]
+    pub fn new_style_color(&self) -> Color {
+        if self.default_color_follows_theme && cosmic::theme::is_dark() {
+            DARK_THEME_NOTE_COLOR
+        } else {
+            Color::WHITE
+        }
+    }
+
+    /// The range of font sizes allowed in note styles, the bounds are swapped if misconfigured
+    #[must_use]
+    pub fn font_size_range(&self) -> RangeInclusive<u16> {
+        self.font_size_min.min(self.font_size_max)..=self.font_size_max.max(self.font_size_min)
+    }
+
+    /// The size of new notes, it is never less than the minimum size of sticky window
+    #[must_use]
+    pub fn new_note_size(&self) -> Size<usize> {
+        Size::new(
+            self.new_note_width.max(self.note_min_width),
+            self.new_note_height.max(self.note_min_height),
+        )
+    }
+
     #[must_use]
     pub fn sticky_window_minimum(&self) -> Size<usize> {
         Size::new(self.note_min_width, self.note_min_height)
     }
+
+    #[must_use]
+    pub fn sticky_window_maximum(&self) -> Size<usize> {
+        Size::new(self.note_max_width, self.note_max_height)
+    }
+}
+
+fn in_home_dir(relative_path: &str) -> String {
+    dirs_next::home_dir().map_or_else(
+        || relative_path.to_string(),
+        |mut home| {
+            home.push(relative_path);
+            home.display().to_string()
+        },
+    )
 }
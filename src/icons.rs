@@ -5,6 +5,8 @@ pub mod inner {
     use cosmic::widget::icon::{self, Handle};
 
     const ICON_NOTES: &[u8] = include_bytes!("../resources/icons/hicolor/scalable/apps/notes.svg");
+    const ICON_NOTES_OFFLINE: &[u8] =
+        include_bytes!("../resources/icons/hicolor/scalable/status/notes-offline.svg");
     const ICON_UNLOCKED: &[u8] =
         include_bytes!("../resources/icons/mono/scalable/changes-allow-symbolic.svg");
     const ICON_LOCKED: &[u8] =
@@ -20,9 +22,20 @@ pub mod inner {
         include_bytes!("../resources/icons/mono/scalable/edit-undo-symbolic.svg");
     const ICON_CHECKED: &[u8] =
         include_bytes!("../resources/icons/mono/scalable/checkbox-checked-symbolic.svg");
+    const ICON_RESIZE: &[u8] =
+        include_bytes!("../resources/icons/mono/scalable/resize-corner-symbolic.svg");
+    const ICON_COPY: &[u8] =
+        include_bytes!("../resources/icons/mono/scalable/edit-copy-symbolic.svg");
+    const ICON_CLICK_THROUGH: &[u8] =
+        include_bytes!("../resources/icons/mono/scalable/input-mouse-symbolic.svg");
+    const ICON_HIDE: &[u8] =
+        include_bytes!("../resources/icons/mono/scalable/view-conceal-symbolic.svg");
+    const ICON_SHOW: &[u8] =
+        include_bytes!("../resources/icons/mono/scalable/view-reveal-symbolic.svg");
 
     pub struct IconSet {
         pub notes: Handle,
+        pub notes_offline: Handle,
         pub lock: Handle,
         pub unlock: Handle,
         pub edit: Handle,
@@ -31,20 +44,31 @@ pub mod inner {
         pub delete: Handle,
         pub undo: Handle,
         pub checked: Handle,
+        pub resize: Handle,
+        pub copy: Handle,
+        pub click_through: Handle,
+        pub hide: Handle,
+        pub show: Handle,
     }
 
     impl IconSet {
         pub fn new() -> Self {
             Self {
                 notes: icon::from_svg_bytes(ICON_NOTES),
-                lock: icon::from_svg_bytes(ICON_UNLOCKED),
-                unlock: icon::from_svg_bytes(ICON_LOCKED),
+                notes_offline: icon::from_svg_bytes(ICON_NOTES_OFFLINE),
+                lock: icon::from_svg_bytes(ICON_LOCKED),
+                unlock: icon::from_svg_bytes(ICON_UNLOCKED),
                 edit: icon::from_svg_bytes(ICON_EDIT),
                 down: icon::from_svg_bytes(ICON_DOWN),
                 create: icon::from_svg_bytes(ICON_NEW),
                 delete: icon::from_svg_bytes(ICON_DELETE),
                 undo: icon::from_svg_bytes(ICON_UNDO),
                 checked: icon::from_svg_bytes(ICON_CHECKED),
+                resize: icon::from_svg_bytes(ICON_RESIZE),
+                copy: icon::from_svg_bytes(ICON_COPY),
+                click_through: icon::from_svg_bytes(ICON_CLICK_THROUGH),
+                hide: icon::from_svg_bytes(ICON_HIDE),
+                show: icon::from_svg_bytes(ICON_SHOW),
             }
         }
     }
@@ -56,6 +80,11 @@ mod inner {
     use cosmic::widget::icon::{self, Handle};
 
     const ICON_NOTES: &[u8] = include_bytes!("../resources/icons/hicolor/scalable/apps/notes.svg");
+    const ICON_NOTES_OFFLINE: &[u8] =
+        include_bytes!("../resources/icons/hicolor/scalable/status/notes-offline.svg");
+    // there is no well-known XDG icon for the resize grip
+    const ICON_RESIZE: &[u8] =
+        include_bytes!("../resources/icons/mono/scalable/resize-corner-symbolic.svg");
 
     pub const XDG_UNLOCKED: &str = "changes-allow-symbolic";
     pub const XDG_LOCKED: &str = "changes-prevent-symbolic";
@@ -65,9 +94,14 @@ mod inner {
     pub const XDG_DOWN: &str = "pan-down-symbolic";
     pub const XDG_UNDO: &str = "edit-undo-symbolic";
     pub const XDG_CHECKED: &str = "checkbox-checked-symbolic";
+    pub const XDG_COPY: &str = "edit-copy-symbolic";
+    pub const XDG_CLICK_THROUGH: &str = "input-mouse-symbolic";
+    pub const XDG_HIDE: &str = "view-conceal-symbolic";
+    pub const XDG_SHOW: &str = "view-reveal-symbolic";
 
     pub struct IconSet {
         pub notes: Handle,
+        pub notes_offline: Handle,
         pub lock: Handle,
         pub unlock: Handle,
         pub edit: Handle,
@@ -76,20 +110,31 @@ mod inner {
         pub delete: Handle,
         pub undo: Handle,
         pub checked: Handle,
+        pub resize: Handle,
+        pub copy: Handle,
+        pub click_through: Handle,
+        pub hide: Handle,
+        pub show: Handle,
     }
 
     impl IconSet {
         pub fn new() -> Self {
             Self {
                 notes: icon::from_svg_bytes(ICON_NOTES),
-                lock: icon::from_name(XDG_UNLOCKED).into(),
-                unlock: icon::from_name(XDG_LOCKED).into(),
+                notes_offline: icon::from_svg_bytes(ICON_NOTES_OFFLINE),
+                lock: icon::from_name(XDG_LOCKED).into(),
+                unlock: icon::from_name(XDG_UNLOCKED).into(),
                 edit: icon::from_name(XDG_EDIT).into(),
                 down: icon::from_name(XDG_DOWN).into(),
                 create: icon::from_name(XDG_NEW).into(),
                 delete: icon::from_name(XDG_DELETE).into(),
                 undo: icon::from_name(XDG_UNDO).into(),
                 checked: icon::from_name(XDG_CHECKED).into(),
+                resize: icon::from_svg_bytes(ICON_RESIZE),
+                copy: icon::from_name(XDG_COPY).into(),
+                click_through: icon::from_name(XDG_CLICK_THROUGH).into(),
+                hide: icon::from_name(XDG_HIDE).into(),
+                show: icon::from_name(XDG_SHOW).into(),
             }
         }
     }
@@ -117,6 +162,10 @@ impl IconSet {
         self.inner.notes.clone()
     }
 
+    pub fn notes_offline(&self) -> Handle {
+        self.inner.notes_offline.clone()
+    }
+
     pub fn lock(&self) -> Handle {
         self.inner.lock.clone()
     }
@@ -148,4 +197,24 @@ impl IconSet {
     pub fn checked(&self) -> Handle {
         self.inner.checked.clone()
     }
+
+    pub fn resize(&self) -> Handle {
+        self.inner.resize.clone()
+    }
+
+    pub fn copy(&self) -> Handle {
+        self.inner.copy.clone()
+    }
+
+    pub fn click_through(&self) -> Handle {
+        self.inner.click_through.clone()
+    }
+
+    pub fn hide(&self) -> Handle {
+        self.inner.hide.clone()
+    }
+
+    pub fn show(&self) -> Handle {
+        self.inner.show.clone()
+    }
 }
@@ -6,3 +6,6 @@ pub mod i18n;
 pub mod icons;
 #[allow(dead_code)]
 pub mod notes;
+pub mod state;
+
+pub use notes::{Error, Result};
@@ -1,11 +1,23 @@
-pub use collection::NotesCollection;
+pub use collection::{
+    ChangeEvent, CollectionParts, CollectionStats, MergeStrategy, NoteSort, NotesCollection,
+    NotesCollectionError, StyleMatch, ValidationReport,
+};
+pub use error::{Error, Result};
+pub use export_format::ExportFormat;
+pub use import_keep::KeepImportError;
+pub use indicator_stickynotes::IndicatorStickyNotesError;
 pub use note_data::NoteData;
-pub use note_style::{Font, FontStyle, NoteStyle};
+pub use note_style::{Font, FontStyle, NoteStyle, TextAlign};
+pub use note_template::NoteTemplate;
 
 mod collection;
+mod error;
+mod export_format;
+mod import_keep;
 mod indicator_stickynotes;
 mod note_data;
 mod note_style;
+mod note_template;
 
 const DEF_NOTE_STYLE_NAME: &str = "White";
 const DEF_NOTE_FONT_SIZE: u16 = 12;
@@ -13,5 +25,12 @@ const EMPTY_TITLE: &str = "<Empty>";
 const NO_TITLE: &str = "Untitled";
 const NO_CONTENT: &str = "click inside to begin edit the content";
 const MAX_TITLE_CHARS: usize = 12;
+const ELLIPSIS: char = '…';
 const DEF_NOTE_WIDTH: usize = 400;
 const DEF_NOTE_HEIGHT: usize = 300;
+const DEF_NOTE_OPACITY: f32 = 1.0;
+// the shift of the each next imported note position having no position of its own
+const IMPORT_CASCADE_OFFSET: usize = 24;
+const IMPORT_CASCADE_STEPS: usize = 10;
+// the imported note position is kept within the range, the rest is done by repositioning offscreen notes
+const MAX_NOTE_POSITION: usize = 16384;
@@ -1,25 +1,30 @@
 use std::{
+    borrow::Cow,
     collections::{
-        HashMap,
-        hash_map::{Iter, IterMut},
+        HashMap, HashSet,
+        hash_map::{Entry, Iter, IterMut},
     },
     path::Path,
+    sync::LazyLock,
 };
 
 use super::{
-    Font, NoteData, NoteStyle,
+    DEF_NOTE_HEIGHT, DEF_NOTE_STYLE_NAME, DEF_NOTE_WIDTH, ExportFormat, Font,
+    IMPORT_CASCADE_OFFSET, IMPORT_CASCADE_STEPS, MAX_NOTE_POSITION, NoteData, NoteStyle,
+    NoteTemplate, export_format,
+    import_keep::{self, KeepImportError, try_import_keep_notes},
     indicator_stickynotes::{
         CategoryProperties as StickyNotesCategoryProperties,
         GlobalProperties as StickyNotesGlobalProperties,
         IndicatorStickyNotesError as StickyNotesError, Note as StickyNotesNote,
         NoteProperties as StickyNotesNoteProperties, NotesDatabase as StickyNotesDatabase,
-        parse_font, serialize_font, try_export_indicator_stickynotes,
-        try_import_indicator_stickynotes,
+        parse_font, serialize_font, try_import_indicator_stickynotes,
     },
 };
+use chrono::{DateTime, Local, Utc};
 use cosmic::{
     cosmic_theme::palette::{Hsv, Srgb, convert::FromColorUnclamped as _, rgb::Rgb},
-    iced::Color,
+    iced::{Color, Point, Rectangle},
 };
 use thiserror::Error;
 use uuid::Uuid;
@@ -28,13 +33,22 @@ use uuid::Uuid;
 pub enum NotesCollectionError {
     // Failed reading source file
     #[error("Failed importing notes: {0}")]
-    Import(StickyNotesError),
-    // Failed writing export file
+    Import(#[source] StickyNotesError),
+    // Source file does not exist, so there is nothing to import
+    #[error("Nothing to import: {0} does not exist")]
+    ImportFileMissing(String),
+    // Failed reading Google Keep Takeout
+    #[error("Failed importing Google Keep notes: {0}")]
+    ImportKeep(#[source] KeepImportError),
+    // Failed building indicator-stickynotes content
     #[error("Failed exporting notes: {0}")]
-    Export(StickyNotesError),
+    Export(#[source] StickyNotesError),
+    // Failed writing export file
+    #[error("Failed writing exported notes: {0}")]
+    Write(#[source] std::io::Error),
     // Failed parsing input text
     #[error("Failed parsing notes: {0}")]
-    Json(serde_json::Error),
+    Json(#[source] serde_json::Error),
     // must not delete the last (and default) style
     #[error("Cannot delete the last style")]
     DeleteLastStyle,
@@ -44,21 +58,184 @@ pub enum NotesCollectionError {
     StyleIndexNotFound(usize),
     #[error("Note {0} is not found")]
     NoteNotFound(Uuid),
+    #[error("Template index {0} not found")]
+    TemplateIndexNotFound(usize),
+}
+
+/// How to resolve the conflict when both collections being merged contain the same note
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    KeepMine,
+    KeepTheirs,
+    KeepNewer,
+}
+
+/// How to find out the incoming style is already known when collections are merged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StyleMatch {
+    /// The style with the same uuid is the same style if it looks the same
+    #[default]
+    ById,
+    /// Besides, the style with the same name (ignoring case) and color is the same style,
+    /// so styles are not duplicated on import from another machine
+    ByName,
+}
+
+/// What has changed in the collection since the observer took the changes last time,
+/// see `NotesCollection::take_change_log()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeEvent {
+    NoteAdded(Uuid),
+    NoteModified(Uuid),
+    NoteDeleted(Uuid),
+    /// The style has been added, modified or deleted
+    StyleChanged(Uuid),
+}
+
+/// The order to list notes in, the notes are compared by id if they are equal otherwise
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NoteSort {
+    /// Alphabetically by title ignoring case
+    TitleAsc,
+    /// The recently modified first
+    #[default]
+    ModifiedDesc,
+    /// The recently created first
+    CreatedDesc,
+}
+
+impl NoteSort {
+    fn sort<'a>(
+        self,
+        notes: impl Iterator<Item = (&'a Uuid, &'a NoteData)>,
+    ) -> Vec<(Uuid, &'a NoteData)> {
+        let mut sorted: Vec<(Uuid, &NoteData)> = notes.map(|(id, note)| (*id, note)).collect();
+        match self {
+            NoteSort::TitleAsc => {
+                sorted.sort_by_cached_key(|(id, note)| (note.get_title().to_lowercase(), *id));
+            }
+            NoteSort::ModifiedDesc => sorted.sort_by(|(a_id, a), (b_id, b)| {
+                b.get_modified().cmp(&a.get_modified()).then(a_id.cmp(b_id))
+            }),
+            NoteSort::CreatedDesc => sorted.sort_by(|(a_id, a), (b_id, b)| {
+                b.get_created().cmp(&a.get_created()).then(a_id.cmp(b_id))
+            }),
+        }
+        sorted
+    }
+}
+
+/// Aggregate figures describing the collection
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionStats {
+    pub notes_count: usize,
+    pub deleted_count: usize,
+    pub styles_count: usize,
+    /// The number of characters in content of all live notes
+    pub total_chars: usize,
+    /// The earliest modification time among live notes
+    pub oldest_modified: Option<DateTime<Utc>>,
+    /// The latest modification time among live notes
+    pub newest_modified: Option<DateTime<Utc>>,
+}
+
+/// What reading the notes file would repair, see `NotesCollection::validate_json()`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub notes_count: usize,
+    pub styles_count: usize,
+    /// The default style refers nothing, so another one becomes default
+    pub missing_default_style: bool,
+    /// The notes referring nonexistent styles as (note id, style id), they get the default style
+    pub unknown_styles: Vec<(Uuid, Uuid)>,
+}
+
+impl ValidationReport {
+    #[must_use]
+    pub fn has_issues(&self) -> bool {
+        self.missing_default_style || !self.unknown_styles.is_empty()
+    }
+}
+
+/// The collection to be stored as separate entries, so a save rewrites the changed notes only
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionParts {
+    /// The collection without notes listing the ids of notes stored separately
+    pub index: String,
+    /// The notes (key, JSON) changed since the last save
+    pub changed: Vec<(String, String)>,
+    /// The keys of notes gone since the last save, cosmic-config can't delete keys, so they are blanked
+    pub removed: Vec<String>,
+}
+
+// the stored collection, the notes saved as a whole by earlier versions are inline and no ids are listed
+#[derive(serde::Deserialize, serde::Serialize)]
+struct CollectionIndex {
+    #[serde(flatten)]
+    collection: NotesCollection,
+    #[serde(default)]
+    note_ids: Vec<Uuid>,
+}
+
+// the look of the note listed while neither its style nor the default one exists
+static BUILTIN_STYLE: LazyLock<NoteStyle> = LazyLock::new(NoteStyle::default);
+
+// the note shared as a self-contained text, see NotesCollection::export_single(),
+// the state private to the user (attachments, reminder, locking and so on) is not shared
+#[derive(serde::Deserialize, serde::Serialize)]
+struct SharedNote {
+    content: String,
+    style: NoteStyle,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
 pub struct NotesCollection {
+    #[serde(default)]
     notes: HashMap<Uuid, NoteData>,
+    #[serde(default)]
     styles: HashMap<Uuid, NoteStyle>,
+    // missing or unknown default style is replaced on read, see try_read()
+    #[serde(default)]
     default_style: Uuid,
+    // default styles overriding the global one in some contexts (e.g. on particular screen)
+    #[serde(default)]
+    context_default_styles: HashMap<String, Uuid>,
+    // the templates of new notes in the order they are offered
+    #[serde(default)]
+    templates: Vec<NoteTemplate>,
     #[serde(skip)]
     is_dirty: bool,
     #[serde[skip]]
     deleted_notes: HashMap<Uuid, NoteData>,
+    // new styles are not saved until they are confirmed, see try_write()
+    #[serde(skip)]
+    draft_styles: HashSet<Uuid>,
+    // notes stored unchanged by the last save, see try_write_parts()
+    #[serde(skip)]
+    stored_notes: HashSet<Uuid>,
+    // notes stored before the collection was replaced, to be removed on the next save
+    #[serde(skip)]
+    removed_notes: HashSet<Uuid>,
+    // the changes not taken by an observer yet, see take_change_log()
+    #[serde(skip)]
+    change_log: Vec<ChangeEvent>,
+    // the notes and styles handed out to be changed with their revisions at that time,
+    // the changed ones are logged once the log is taken
+    #[serde(skip)]
+    lent_notes: HashMap<Uuid, u64>,
+    #[serde(skip)]
+    lent_styles: HashMap<Uuid, u64>,
 }
 
 impl From<StickyNotesDatabase> for NotesCollection {
     fn from(value: StickyNotesDatabase) -> Self {
+        Self::from_import(value, (DEF_NOTE_WIDTH, DEF_NOTE_HEIGHT))
+    }
+}
+
+impl NotesCollection {
+    /// Convert the imported notes, the `default_size` replaces the missing size of a note
+    fn from_import(value: StickyNotesDatabase, default_size: (usize, usize)) -> Self {
         // import notes data
         let notes = value
             .notes
@@ -66,7 +243,7 @@ impl From<StickyNotesDatabase> for NotesCollection {
             .map(|src| {
                 (
                     src.uuid,
-                    NoteData::new_from_import(src, value.properties.all_visible),
+                    NoteData::new_from_import(src, value.properties.all_visible, default_size),
                 )
             })
             .collect();
@@ -99,8 +276,16 @@ impl From<StickyNotesDatabase> for NotesCollection {
             notes,
             styles,
             default_style: value.properties.default_cat,
-            is_dirty: true,                // not saved yet
-            deleted_notes: HashMap::new(), // no deleted yet
+            context_default_styles: HashMap::new(), // not supported by indicator-stickynotes
+            templates: Vec::new(),                  // not supported by indicator-stickynotes
+            is_dirty: true,                         // not saved yet
+            deleted_notes: HashMap::new(),          // no deleted yet
+            draft_styles: HashSet::new(),
+            stored_notes: HashSet::new(),
+            removed_notes: HashSet::new(),
+            change_log: Vec::new(),
+            lent_notes: HashMap::new(),
+            lent_styles: HashMap::new(),
         };
         // ensure default_style is correct
         instance.ensure_default_style();
@@ -108,11 +293,61 @@ impl From<StickyNotesDatabase> for NotesCollection {
     }
 }
 
+impl From<Vec<import_keep::Note>> for NotesCollection {
+    fn from(value: Vec<import_keep::Note>) -> Self {
+        // a style per Google Keep color
+        let mut color_styles: HashMap<String, Uuid> = HashMap::new();
+        let mut styles = HashMap::new();
+        let mut notes = HashMap::new();
+        let mut deleted_notes = HashMap::new();
+        for (index, src) in value.iter().enumerate() {
+            let style_id = *color_styles.entry(src.color.clone()).or_insert_with(|| {
+                let style_id = Uuid::new_v4();
+                styles.insert(
+                    style_id,
+                    NoteStyle::new(
+                        format!("Keep {}", src.color.to_lowercase()),
+                        Font::default(),
+                        import_keep::keep_color(&src.color),
+                    ),
+                );
+                style_id
+            });
+            let offset = (index % IMPORT_CASCADE_STEPS) * IMPORT_CASCADE_OFFSET;
+            let note = NoteData::new_from_keep(src, style_id, (offset, offset));
+            // trashed notes can be restored as deleted ones
+            if src.is_trashed {
+                deleted_notes.insert(Uuid::new_v4(), note);
+            } else {
+                notes.insert(Uuid::new_v4(), note);
+            }
+        }
+        let mut instance = Self {
+            notes,
+            styles,
+            default_style: color_styles.get("DEFAULT").copied().unwrap_or_default(),
+            context_default_styles: HashMap::new(), // not supported by Google Keep
+            templates: Vec::new(),                  // not supported by Google Keep
+            is_dirty: true,                         // not saved yet
+            deleted_notes,
+            draft_styles: HashSet::new(),
+            stored_notes: HashSet::new(),
+            removed_notes: HashSet::new(),
+            change_log: Vec::new(),
+            lent_notes: HashMap::new(),
+            lent_styles: HashMap::new(),
+        };
+        instance.ensure_default_style();
+        instance
+    }
+}
+
 impl From<NotesCollection> for StickyNotesDatabase {
     fn from(value: NotesCollection) -> Self {
         let notes = value
             .notes
             .into_iter()
+            // indicator-stickynotes has neither attachments nor icon size overrides, so they are dropped
             .map(|(note_id, note)| StickyNotesNote {
                 uuid: note_id,
                 body: note.get_content().to_string(),
@@ -154,40 +389,427 @@ impl From<NotesCollection> for StickyNotesDatabase {
 
 #[allow(clippy::missing_errors_doc)]
 impl NotesCollection {
+    // Build collection programmatically
+
+    /// Create collection having the only (default) style and no notes
+    #[must_use]
+    pub fn with_style(name: String, font: Font, bgcolor: Color) -> (Self, Uuid) {
+        let style_id = Uuid::new_v4();
+        let instance = Self {
+            notes: HashMap::new(),
+            styles: HashMap::from_iter([(style_id, NoteStyle::new(name, font, bgcolor))]),
+            default_style: style_id,
+            context_default_styles: HashMap::new(),
+            templates: Vec::new(),
+            is_dirty: false,
+            deleted_notes: HashMap::new(),
+            draft_styles: HashSet::new(),
+            stored_notes: HashSet::new(),
+            removed_notes: HashSet::new(),
+            change_log: Vec::new(),
+            lent_notes: HashMap::new(),
+            lent_styles: HashMap::new(),
+        };
+        (instance, style_id)
+    }
+
+    /// Add note with the content using existing style
+    pub fn add_note_with_style(
+        &mut self,
+        content: String,
+        style_id: Uuid,
+    ) -> Result<Uuid, NotesCollectionError> {
+        if !self.styles.contains_key(&style_id) {
+            return Err(NotesCollectionError::StyleNotFound(style_id));
+        }
+        let id = Uuid::new_v4();
+        let mut note = NoteData::new(style_id);
+        note.set_content(content);
+        self.notes.insert(id, note);
+        self.log_change(ChangeEvent::NoteAdded(id));
+        Ok(id)
+    }
+
     // Import/export/save/load
 
+    /// Import indicator-stickynotes data file, the `default_size` replaces the missing size of a note
     pub async fn try_import<P: AsRef<Path> + std::fmt::Debug>(
         data_file: P,
-    ) -> Result<Self, NotesCollectionError> {
+        default_size: (usize, usize),
+    ) -> crate::Result<Self> {
         try_import_indicator_stickynotes(data_file)
+            .await
+            .map(|imported| Self::from_import(imported, default_size))
+            .map_err(|e| match e {
+                StickyNotesError::FileMissing(path) => {
+                    NotesCollectionError::ImportFileMissing(path).into()
+                }
+                e => NotesCollectionError::Import(e).into(),
+            })
+    }
+
+    /// Import Google Keep Takeout: either the directory of notes or a single note file
+    pub async fn try_import_keep<P: AsRef<Path> + std::fmt::Debug>(path: P) -> crate::Result<Self> {
+        try_import_keep_notes(path)
             .await
             .map(Into::into)
-            .map_err(NotesCollectionError::Import)
+            .map_err(|e| match e {
+                KeepImportError::FileMissing(path) => {
+                    NotesCollectionError::ImportFileMissing(path).into()
+                }
+                e => NotesCollectionError::ImportKeep(e).into(),
+            })
+    }
+
+    /// Correct the geometry of the notes making windows unusable (e.g. imported 1x1 or 30000x30000),
+    /// returns the number of corrected notes
+    pub fn sanitize(&mut self, min_size: (usize, usize), max_size: (usize, usize)) -> usize {
+        let max_size = (max_size.0.max(min_size.0), max_size.1.max(min_size.1));
+        let mut corrected = 0;
+        for (note_id, note) in &mut self.notes {
+            let size = (
+                note.width().clamp(min_size.0, max_size.0),
+                note.height().clamp(min_size.1, max_size.1),
+            );
+            let position = (
+                note.left().min(MAX_NOTE_POSITION),
+                note.top().min(MAX_NOTE_POSITION),
+            );
+            if size != (note.width(), note.height()) || position != (note.left(), note.top()) {
+                tracing::warn!(
+                    "note {note_id} geometry {:?} at {:?} is corrected to {size:?} at {position:?}",
+                    (note.width(), note.height()),
+                    (note.left(), note.top())
+                );
+                note.set_size(size.0, size.1);
+                note.set_position(position.0, position.1);
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(*note_id));
+                corrected += 1;
+            }
+        }
+        corrected
     }
 
-    pub async fn try_export<P: AsRef<Path> + std::fmt::Debug>(
+    pub async fn try_export_as<P: AsRef<Path> + std::fmt::Debug>(
         data_file: P,
         notes: NotesCollection,
-    ) -> Result<(), NotesCollectionError> {
-        try_export_indicator_stickynotes(data_file, notes.into())
+        format: ExportFormat,
+    ) -> crate::Result<()> {
+        let content = notes.try_format(format)?;
+        tokio::fs::write(data_file, content)
             .await
-            .map_err(NotesCollectionError::Export)
+            .map_err(|e| NotesCollectionError::Write(e).into())
+    }
+
+    /// The content of the file the collection is exported to in the format
+    pub fn try_format(&self, format: ExportFormat) -> crate::Result<Vec<u8>> {
+        match format {
+            ExportFormat::IndicatorStickyNotes => StickyNotesDatabase::from(self.clone())
+                .try_export()
+                .map_err(|e| NotesCollectionError::Export(e).into()),
+            ExportFormat::NativeJson => self.try_write().map(String::into_bytes),
+            ExportFormat::Markdown => Ok(export_format::to_markdown(self).into_bytes()),
+            ExportFormat::PlainText => Ok(export_format::to_plain_text(self).into_bytes()),
+        }
+    }
+
+    /// Parse the collection saved by this or any earlier version.
+    /// Missing fields are defaulted, the default style and the styles of notes are repaired if they refer nothing.
+    pub fn try_read(input: &str) -> crate::Result<Self> {
+        let mut instance: Self = serde_json::from_str(input).map_err(NotesCollectionError::Json)?;
+        instance.ensure_default_style();
+        instance.ensure_notes_styles();
+        Ok(instance)
+    }
+
+    /// Parse the collection as `try_read()` does, but report what is to be repaired instead of repairing it
+    pub fn validate_json(input: &str) -> crate::Result<ValidationReport> {
+        let instance: Self = serde_json::from_str(input).map_err(NotesCollectionError::Json)?;
+        Ok(instance.validate())
+    }
+
+    /// Finds out if notes refer to missing styles or the default style is missing
+    #[must_use]
+    pub fn validate(&self) -> ValidationReport {
+        let mut unknown_styles: Vec<(Uuid, Uuid)> = self
+            .notes
+            .iter()
+            .filter(|(_, note)| !self.styles.contains_key(&note.style()))
+            .map(|(note_id, note)| (*note_id, note.style()))
+            .collect();
+        unknown_styles.sort_unstable();
+        ValidationReport {
+            notes_count: self.notes.len(),
+            styles_count: self.styles.len(),
+            missing_default_style: !self.styles.contains_key(&self.default_style),
+            unknown_styles,
+        }
+    }
+
+    /// Serializes the collection without draft styles, notes of those styles get the default one
+    pub fn try_write(&self) -> crate::Result<String> {
+        serde_json::to_string(&self.confirmed()).map_err(|e| NotesCollectionError::Json(e).into())
+    }
+
+    /// The key to store the note under
+    #[must_use]
+    pub fn note_key(note_id: &Uuid) -> String {
+        format!("note-{note_id}")
+    }
+
+    /// Parse the collection stored by `try_write_parts()`, the `read_note` gets the note by its key.
+    /// The collection saved as a whole by earlier versions is read as well
+    pub fn try_read_parts<F>(index: &str, read_note: F) -> crate::Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let CollectionIndex {
+            collection: mut instance,
+            note_ids,
+        } = serde_json::from_str(index).map_err(NotesCollectionError::Json)?;
+        for note_id in note_ids {
+            let key = Self::note_key(&note_id);
+            match read_note(&key).map(|json| serde_json::from_str::<NoteData>(&json)) {
+                Some(Ok(note)) => {
+                    instance.notes.insert(note_id, note);
+                    instance.stored_notes.insert(note_id);
+                }
+                Some(Err(e)) => tracing::error!("failed reading note {key}: {e}"),
+                None => tracing::error!("note {key} is missing"),
+            }
+        }
+        instance.ensure_default_style();
+        instance.ensure_notes_styles();
+        Ok(instance)
+    }
+
+    /// Serialize the collection to be stored as separate entries, the notes stored unchanged
+    /// by the last save are skipped. Call `commit_changes()` once the parts are stored
+    pub fn try_write_parts(&self) -> crate::Result<CollectionParts> {
+        let confirmed = self.confirmed();
+        let changed = confirmed
+            .notes
+            .iter()
+            .filter(|(note_id, note)| note.needs_storing() || !self.stored_notes.contains(note_id))
+            .map(|(note_id, note)| {
+                serde_json::to_string(note).map(|json| (Self::note_key(note_id), json))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(NotesCollectionError::Json)?;
+        let removed = self
+            .stored_notes
+            .union(&self.removed_notes)
+            .filter(|note_id| !confirmed.notes.contains_key(note_id))
+            .map(Self::note_key)
+            .collect();
+        let mut note_ids: Vec<Uuid> = confirmed.notes.keys().copied().collect();
+        note_ids.sort_unstable();
+        let index = CollectionIndex {
+            collection: Self {
+                notes: HashMap::new(),
+                styles: confirmed.styles.clone(),
+                default_style: confirmed.default_style,
+                context_default_styles: confirmed.context_default_styles.clone(),
+                templates: confirmed.templates.clone(),
+                is_dirty: false,
+                deleted_notes: HashMap::new(),
+                draft_styles: HashSet::new(),
+                stored_notes: HashSet::new(),
+                removed_notes: HashSet::new(),
+                change_log: Vec::new(),
+                lent_notes: HashMap::new(),
+                lent_styles: HashMap::new(),
+            },
+            note_ids,
+        };
+        Ok(CollectionParts {
+            index: serde_json::to_string(&index).map_err(NotesCollectionError::Json)?,
+            changed,
+            removed,
+        })
+    }
+
+    // the collection as it is to be saved, i.e. without draft styles
+    fn confirmed(&self) -> Cow<'_, Self> {
+        if self.draft_styles.is_empty() {
+            Cow::Borrowed(self)
+        } else {
+            let mut confirmed = self.clone();
+            for style_id in &self.draft_styles {
+                if let Err(e) = confirmed.delete_style(*style_id) {
+                    tracing::warn!("draft style is written: {e}");
+                }
+            }
+            Cow::Owned(confirmed)
+        }
     }
 
-    pub fn try_read(input: &str) -> Result<Self, NotesCollectionError> {
-        serde_json::from_str(input).map_err(NotesCollectionError::Json)
+    /// Serialize the note content along with its style to share it, e.g. via clipboard
+    pub fn export_single(&self, note_id: &Uuid) -> crate::Result<String> {
+        let (note, style) = self.try_get_note_with_style(note_id)?;
+        let shared = SharedNote {
+            content: note.get_content().to_string(),
+            style: style.clone(),
+        };
+        serde_json::to_string_pretty(&shared).map_err(|e| NotesCollectionError::Json(e).into())
+    }
+
+    /// Add the shared note as a new one, see `export_single()`. The style having the same name and color
+    /// is used if any, otherwise the shared style is added
+    pub fn import_single(&mut self, json: &str) -> crate::Result<Uuid> {
+        let SharedNote { content, style } =
+            serde_json::from_str(json).map_err(NotesCollectionError::Json)?;
+        let style_id = if let Some(existing_id) = self.find_same_named_style(&style) {
+            existing_id
+        } else {
+            let style_id = Uuid::new_v4();
+            self.styles.insert(style_id, style);
+            self.log_change(ChangeEvent::StyleChanged(style_id));
+            style_id
+        };
+        let note_id = self.add_note_with_style(content, style_id)?;
+        self.is_dirty = true;
+        Ok(note_id)
     }
 
-    pub fn try_write(&self) -> Result<String, NotesCollectionError> {
-        serde_json::to_string(self).map_err(NotesCollectionError::Json)
+    /// Replace the collection, e.g. with the imported one, the notes stored before are removed on save
+    pub fn replace(&mut self, other: NotesCollection) {
+        let mut removed_notes = std::mem::take(&mut self.removed_notes);
+        removed_notes.extend(self.stored_notes.drain());
+        let mut change_log = std::mem::take(&mut self.change_log);
+        change_log.extend(
+            self.notes
+                .keys()
+                .filter(|note_id| !other.notes.contains_key(note_id))
+                .map(|note_id| ChangeEvent::NoteDeleted(*note_id)),
+        );
+        change_log.extend(other.notes.keys().map(|note_id| {
+            if self.notes.contains_key(note_id) {
+                ChangeEvent::NoteModified(*note_id)
+            } else {
+                ChangeEvent::NoteAdded(*note_id)
+            }
+        }));
+        let style_ids: HashSet<Uuid> = self
+            .styles
+            .keys()
+            .chain(other.styles.keys())
+            .copied()
+            .collect();
+        change_log.extend(style_ids.into_iter().map(ChangeEvent::StyleChanged));
+        *self = other;
+        removed_notes.retain(|note_id| !self.notes.contains_key(note_id));
+        self.removed_notes = removed_notes;
+        self.change_log = change_log;
     }
 
     // Collection as itself
 
+    /// Merge other collection into this one without losing existing notes
+    pub fn merge(
+        &mut self,
+        other: NotesCollection,
+        on_conflict: MergeStrategy,
+        style_match: StyleMatch,
+    ) {
+        // merge styles, remap incoming style if its uuid is occupied by a different style
+        let mut remapped_styles = HashMap::new();
+        for (style_id, style) in other.styles {
+            let is_occupied = match self.styles.get(&style_id) {
+                Some(existing) if existing.looks_like(&style) => continue,
+                existing => existing.is_some(),
+            };
+            let same_named = match style_match {
+                StyleMatch::ById => None,
+                StyleMatch::ByName => self.find_same_named_style(&style),
+            };
+            if let Some(existing_id) = same_named {
+                tracing::debug!("merge: remap style {style_id} to same named {existing_id}");
+                remapped_styles.insert(style_id, existing_id);
+            } else if is_occupied {
+                let new_style_id = Uuid::new_v4();
+                tracing::debug!("merge: remap style {style_id} to {new_style_id}");
+                self.styles.insert(new_style_id, style);
+                remapped_styles.insert(style_id, new_style_id);
+                self.log_change(ChangeEvent::StyleChanged(new_style_id));
+                self.is_dirty = true;
+            } else {
+                self.styles.insert(style_id, style);
+                self.log_change(ChangeEvent::StyleChanged(style_id));
+                self.is_dirty = true;
+            }
+        }
+        // merge templates, the one having the same name is kept
+        for mut template in other.templates {
+            if self
+                .templates
+                .iter()
+                .any(|existing| existing.get_name() == template.get_name())
+            {
+                continue;
+            }
+            if let Some(style_id) = remapped_styles.get(&template.style()) {
+                template.set_style(*style_id);
+            }
+            self.templates.push(template);
+            self.is_dirty = true;
+        }
+        // merge notes
+        for (note_id, mut note) in other.notes {
+            if let Some(style_id) = remapped_styles.get(&note.style()) {
+                note.set_style(*style_id);
+            } else if !self.styles.contains_key(&note.style()) {
+                note.set_style(self.default_style);
+            }
+            match self.notes.entry(note_id) {
+                Entry::Vacant(entry) => {
+                    entry.insert(note);
+                    log_change(&mut self.change_log, ChangeEvent::NoteAdded(note_id));
+                    self.is_dirty = true;
+                }
+                Entry::Occupied(mut entry) => {
+                    let keep_theirs = match on_conflict {
+                        MergeStrategy::KeepMine => false,
+                        MergeStrategy::KeepTheirs => true,
+                        MergeStrategy::KeepNewer => {
+                            note.get_modified() > entry.get().get_modified()
+                        }
+                    };
+                    if keep_theirs {
+                        tracing::debug!("merge: replace note {note_id}");
+                        entry.insert(note);
+                        self.stored_notes.remove(&note_id);
+                        log_change(&mut self.change_log, ChangeEvent::NoteModified(note_id));
+                        self.is_dirty = true;
+                    }
+                }
+            }
+        }
+    }
+
+    // the style having the same name ignoring case and the same color
+    fn find_same_named_style(&self, style: &NoteStyle) -> Option<Uuid> {
+        let name = style.get_name().to_lowercase();
+        self.styles
+            .iter()
+            .find(|(_, existing)| {
+                existing.get_name().to_lowercase() == name
+                    && existing.get_background_color() == style.get_background_color()
+            })
+            .map(|(style_id, _)| *style_id)
+    }
+
     pub fn is_unsaved(&self) -> bool {
         self.is_dirty
             || self.notes.values().any(NoteData::is_changed)
             || self.styles.values().any(NoteStyle::is_changed)
+            || self.templates.iter().any(NoteTemplate::is_changed)
+    }
+
+    /// There are unsaved changes or notes are focused since they are stored
+    #[must_use]
+    pub fn needs_storing(&self) -> bool {
+        self.is_unsaved() || self.notes.values().any(NoteData::needs_storing)
     }
 
     // test if collection looks like instantiated by default()
@@ -196,13 +818,96 @@ impl NotesCollection {
         self.notes.len() <= 1 && self.styles.len() <= 1
     }
 
+    /// There are no live notes, deleted ones are not taken into account
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    #[must_use]
+    pub fn has_visible_notes(&self) -> bool {
+        self.notes.values().any(NoteData::is_visible)
+    }
+
+    #[must_use]
+    pub fn has_hidden_notes(&self) -> bool {
+        self.notes.values().any(|note| !note.is_visible())
+    }
+
+    #[must_use]
+    pub fn has_deleted_notes(&self) -> bool {
+        !self.deleted_notes.is_empty()
+    }
+
+    /// There are live notes and all of them are locked
+    #[must_use]
+    pub fn all_locked(&self) -> bool {
+        !self.notes.is_empty() && self.notes.values().all(NoteData::is_locked)
+    }
+
+    #[must_use]
+    pub fn any_unlocked(&self) -> bool {
+        self.notes.values().any(|note| !note.is_locked())
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> CollectionStats {
+        let modified = self.notes.values().map(|note| note.get_modified().to_utc());
+        CollectionStats {
+            notes_count: self.notes.len(),
+            deleted_count: self.deleted_notes.len(),
+            styles_count: self.styles.len(),
+            total_chars: self
+                .notes
+                .values()
+                .map(|note| note.get_content().chars().count())
+                .sum(),
+            oldest_modified: modified.clone().min(),
+            newest_modified: modified.max(),
+        }
+    }
+
     pub fn commit_changes(&mut self) {
+        // notes of draft styles are stored with the default style, so they are to be stored again
+        self.stored_notes = self
+            .notes
+            .iter()
+            .filter(|(_, note)| !self.draft_styles.contains(&note.style()))
+            .map(|(note_id, _)| *note_id)
+            .collect();
+        self.removed_notes.clear();
         self.notes.values_mut().for_each(NoteData::commit);
         self.styles.values_mut().for_each(NoteStyle::commit);
+        self.templates.iter_mut().for_each(NoteTemplate::commit);
         self.is_dirty = false;
         tracing::debug!("saved collection: no changes for now");
     }
 
+    /// Take the changes made since the last call, so an observer may refresh or save
+    /// just the changed notes and styles instead of scanning the whole collection.
+    /// Saving doesn't affect the log, see `is_unsaved()` for the unsaved changes.
+    pub fn take_change_log(&mut self) -> Vec<ChangeEvent> {
+        for (note_id, revision) in std::mem::take(&mut self.lent_notes) {
+            if self
+                .notes
+                .get(&note_id)
+                .is_some_and(|note| note.revision() != revision)
+            {
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(note_id));
+            }
+        }
+        for (style_id, revision) in std::mem::take(&mut self.lent_styles) {
+            if self
+                .styles
+                .get(&style_id)
+                .is_some_and(|style| style.revision() != revision)
+            {
+                log_change(&mut self.change_log, ChangeEvent::StyleChanged(style_id));
+            }
+        }
+        std::mem::take(&mut self.change_log)
+    }
+
     // operations with notes
 
     #[must_use]
@@ -220,16 +925,25 @@ impl NotesCollection {
         &mut self,
         note_id: &Uuid,
     ) -> Result<&mut NoteData, NotesCollectionError> {
-        self.notes
+        let note = self
+            .notes
             .get_mut(note_id)
-            .ok_or(NotesCollectionError::NoteNotFound(*note_id))
+            .ok_or(NotesCollectionError::NoteNotFound(*note_id))?;
+        self.lent_notes.entry(*note_id).or_insert(note.revision());
+        Ok(note)
     }
 
     pub fn for_each_note_mut<F>(&mut self, f: F)
     where
         F: Fn(&mut NoteData),
     {
-        self.notes.values_mut().for_each(f);
+        for (note_id, note) in &mut self.notes {
+            let revision = note.revision();
+            f(note);
+            if note.revision() != revision {
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(*note_id));
+            }
+        }
     }
 
     #[must_use]
@@ -238,81 +952,289 @@ impl NotesCollection {
     }
 
     pub fn iter_notes_mut(&mut self) -> IterMut<'_, Uuid, NoteData> {
+        for (note_id, note) in &self.notes {
+            self.lent_notes.entry(*note_id).or_insert(note.revision());
+        }
         self.notes.iter_mut()
     }
 
-    #[must_use]
-    pub fn iter_deleted_notes(&self) -> Iter<'_, Uuid, NoteData> {
-        self.deleted_notes.iter()
+    /// Iterate notes along with their styles, a note of the deleted style gets the default one,
+    /// or the built-in look if the default style is missing as well, so no note is skipped
+    pub fn iter_notes_with_style(&self) -> impl Iterator<Item = (Uuid, &NoteData, &NoteStyle)> {
+        self.notes
+            .iter()
+            .map(|(note_id, note)| (*note_id, note, self.resolve_style_or_builtin(note)))
     }
 
-    pub fn new_note(&mut self) -> Uuid {
-        let id = Uuid::new_v4();
-        self.notes.insert(id, NoteData::new(self.default_style));
-        id
+    /// Get notes which top left corner is out of all of the bounds
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn notes_offscreen(&self, bounds: &[Rectangle]) -> Vec<Uuid> {
+        self.notes
+            .iter()
+            .filter(|(_, note)| {
+                let top_left = Point::new(note.left() as f32, note.top() as f32);
+                !bounds.iter().any(|rect| rect.contains(top_left))
+            })
+            .map(|(note_id, _)| *note_id)
+            .collect()
     }
 
-    pub fn delete_note(&mut self, note_id: Uuid) {
-        if let Some((id, note)) = self.notes.remove_entry(&note_id) {
-            tracing::debug!(
-                "(*) unsaved collection: deleted note {note_id} ({})",
-                note.get_title()
-            );
-            self.is_dirty = true;
-            self.deleted_notes.insert(id, note);
+    /// Move notes which top left corner is out of all of the bounds into the first of them,
+    /// get the moved notes along with their new positions
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn move_notes_onscreen(&mut self, bounds: &[Rectangle]) -> Vec<(Uuid, (usize, usize))> {
+        let Some(screen) = bounds.first() else {
+            // nothing is known about the screens, so nothing is off-screen
+            return Vec::new();
+        };
+        let (screen_left, screen_top) = (screen.x.max(0.0) as usize, screen.y.max(0.0) as usize);
+        let screen_right = (screen.x + screen.width).max(0.0) as usize;
+        let screen_bottom = (screen.y + screen.height).max(0.0) as usize;
+        let mut moved = Vec::new();
+        for note_id in self.notes_offscreen(bounds) {
+            if let Some(note) = self.notes.get_mut(&note_id) {
+                let left = note
+                    .left()
+                    .min(screen_right.saturating_sub(note.width()))
+                    .max(screen_left);
+                let top = note
+                    .top()
+                    .min(screen_bottom.saturating_sub(note.height()))
+                    .max(screen_top);
+                tracing::warn!(
+                    "note {note_id} at ({}, {}) is off-screen, move it to ({left}, {top})",
+                    note.left(),
+                    note.top()
+                );
+                note.set_position(left, top);
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(note_id));
+                moved.push((note_id, (left, top)));
+            }
         }
+        moved
     }
 
-    pub fn try_restore_deleted_note(
-        &mut self,
-        note_id: Uuid,
-    ) -> Result<&NoteData, NotesCollectionError> {
-        if let Some((id, note)) = self.deleted_notes.remove_entry(&note_id) {
-            tracing::debug!(
-                "(*) unsaved collection: restored note {note_id} ({})",
-                note.get_title()
-            );
-            self.is_dirty = true;
-            self.notes.insert(id, note);
-            self.notes
-                .get(&id)
-                .ok_or(NotesCollectionError::NoteNotFound(note_id))
-        } else {
-            Err(NotesCollectionError::NoteNotFound(note_id))
-        }
+    pub fn iter_visible_notes(&self) -> impl Iterator<Item = (&Uuid, &NoteData)> {
+        self.notes.iter().filter(|(_, note)| note.is_visible())
     }
 
-    // operations with styles
+    pub fn iter_hidden_notes(&self) -> impl Iterator<Item = (&Uuid, &NoteData)> {
+        self.notes.iter().filter(|(_, note)| !note.is_visible())
+    }
 
     #[must_use]
-    pub fn get_styles_count(&self) -> usize {
-        self.styles.len()
+    pub fn iter_deleted_notes(&self) -> Iter<'_, Uuid, NoteData> {
+        self.deleted_notes.iter()
     }
 
+    /// Get the notes in the stable order to display them in lists
     #[must_use]
-    pub fn iter_styles(&self) -> Iter<'_, Uuid, NoteStyle> {
-        self.styles.iter()
+    pub fn notes_sorted(&self, by: NoteSort) -> Vec<(Uuid, &NoteData)> {
+        by.sort(self.notes.iter())
     }
 
+    /// Get the deleted notes in the stable order to display them in lists
     #[must_use]
-    pub fn get_style_names(&self) -> Vec<String> {
-        self.styles
-            .values()
-            .map(|style| style.get_name().to_string())
-            .collect()
+    pub fn deleted_notes_sorted(&self, by: NoteSort) -> Vec<(Uuid, &NoteData)> {
+        by.sort(self.deleted_notes.iter())
     }
 
-    pub fn try_get_default_style(&self) -> Result<&NoteStyle, NotesCollectionError> {
-        self.styles
-            .get(&self.default_style)
-            .ok_or(NotesCollectionError::StyleNotFound(self.default_style))
+    /// Get the notes with their styles in the stable order, see `iter_notes_with_style()`
+    #[must_use]
+    pub fn notes_with_style_sorted(&self, by: NoteSort) -> Vec<(Uuid, &NoteData, &NoteStyle)> {
+        self.with_styles(by.sort(self.notes.iter()))
     }
 
-    pub fn try_get_default_style_index(&self) -> Result<usize, NotesCollectionError> {
-        self.styles
-            .keys()
-            .enumerate()
-            .find_map(|(index, style_id)| (*style_id == self.default_style).then_some(index))
+    /// Get the deleted notes with their styles in the stable order
+    #[must_use]
+    pub fn deleted_notes_with_style_sorted(
+        &self,
+        by: NoteSort,
+    ) -> Vec<(Uuid, &NoteData, &NoteStyle)> {
+        self.with_styles(by.sort(self.deleted_notes.iter()))
+    }
+
+    fn with_styles<'a>(
+        &'a self,
+        notes: Vec<(Uuid, &'a NoteData)>,
+    ) -> Vec<(Uuid, &'a NoteData, &'a NoteStyle)> {
+        notes
+            .into_iter()
+            .map(|(note_id, note)| (note_id, note, self.resolve_style_or_builtin(note)))
+            .collect()
+    }
+
+    pub fn new_note(&mut self) -> Uuid {
+        self.new_note_for(None)
+    }
+
+    /// Create new note using default style of the context if any
+    pub fn new_note_for(&mut self, context: Option<&str>) -> Uuid {
+        let id = Uuid::new_v4();
+        self.notes
+            .insert(id, NoteData::new(self.default_style_for(context)));
+        self.log_change(ChangeEvent::NoteAdded(id));
+        id
+    }
+
+    /// Create new note having the expanded content and the style of the template,
+    /// the default style is used if the style of template is missing
+    pub fn new_note_from_template(
+        &mut self,
+        index: usize,
+        now: &DateTime<Local>,
+    ) -> Result<Uuid, NotesCollectionError> {
+        let template = self.try_get_template(index)?;
+        let content = template.expand(now);
+        let style_id = if self.styles.contains_key(&template.style()) {
+            template.style()
+        } else {
+            self.default_style
+        };
+        self.add_note_with_style(content, style_id)
+    }
+
+    /// Hide the least recently modified notes until no more than `max_visible` notes are shown,
+    /// the `keep` notes stay shown, returns the hidden notes, nothing is deleted
+    pub fn hide_oldest_visible(&mut self, max_visible: usize, keep: &[Uuid]) -> Vec<Uuid> {
+        let visible_count = self.iter_visible_notes().count();
+        let mut candidates: Vec<(Uuid, DateTime<Local>)> = self
+            .iter_visible_notes()
+            .filter(|(note_id, _)| !keep.contains(note_id))
+            .map(|(note_id, note)| (*note_id, note.get_modified()))
+            .collect();
+        candidates.sort_by(|(a_id, a), (b_id, b)| a.cmp(b).then(a_id.cmp(b_id)));
+        candidates.truncate(visible_count.saturating_sub(max_visible));
+        let hidden: Vec<Uuid> = candidates.into_iter().map(|(note_id, _)| note_id).collect();
+        for note_id in &hidden {
+            if let Some(note) = self.notes.get_mut(note_id) {
+                note.set_visibility(false);
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(*note_id));
+            }
+        }
+        if !hidden.is_empty() {
+            tracing::debug!("hid {} notes over the limit of {max_visible}", hidden.len());
+        }
+        hidden
+    }
+
+    /// Drops notes left blank since they were created, returns the number of dropped notes
+    pub fn prune_empty_notes(&mut self) -> usize {
+        let count = self.notes.len();
+        self.notes.retain(|note_id, note| {
+            let is_abandoned = note.is_abandoned();
+            if is_abandoned {
+                tracing::debug!("(*) unsaved collection: pruned empty note {note_id}");
+                log_change(&mut self.change_log, ChangeEvent::NoteDeleted(*note_id));
+            }
+            !is_abandoned
+        });
+        let pruned = count - self.notes.len();
+        if pruned > 0 {
+            self.is_dirty = true;
+        }
+        pruned
+    }
+
+    pub fn delete_note(&mut self, note_id: Uuid) {
+        if let Some((id, note)) = self.notes.remove_entry(&note_id) {
+            tracing::debug!(
+                "(*) unsaved collection: deleted note {note_id} ({})",
+                note.get_title()
+            );
+            self.is_dirty = true;
+            self.deleted_notes.insert(id, note);
+            self.log_change(ChangeEvent::NoteDeleted(id));
+        }
+    }
+
+    /// Delete the notes at once, they are restorable as deleted one by one
+    pub fn bulk_delete(&mut self, note_ids: &[Uuid]) {
+        for note_id in note_ids {
+            self.delete_note(*note_id);
+        }
+    }
+
+    /// Lock or unlock the notes at once, unknown notes are skipped
+    pub fn bulk_set_locking(&mut self, note_ids: &[Uuid], is_locked: bool) {
+        for note_id in note_ids {
+            if let Some(note) = self.notes.get_mut(note_id) {
+                note.set_locking(is_locked);
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(*note_id));
+            }
+        }
+    }
+
+    /// Apply the style to the notes at once, unknown notes are skipped
+    pub fn bulk_set_style(
+        &mut self,
+        note_ids: &[Uuid],
+        style_id: Uuid,
+    ) -> Result<(), NotesCollectionError> {
+        if !self.styles.contains_key(&style_id) {
+            return Err(NotesCollectionError::StyleNotFound(style_id));
+        }
+        for note_id in note_ids {
+            if let Some(note) = self.notes.get_mut(note_id) {
+                note.set_style(style_id);
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(*note_id));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn try_restore_deleted_note(
+        &mut self,
+        note_id: Uuid,
+    ) -> Result<&NoteData, NotesCollectionError> {
+        if let Some((id, note)) = self.deleted_notes.remove_entry(&note_id) {
+            tracing::debug!(
+                "(*) unsaved collection: restored note {note_id} ({})",
+                note.get_title()
+            );
+            self.is_dirty = true;
+            self.notes.insert(id, note);
+            self.log_change(ChangeEvent::NoteAdded(id));
+            self.notes
+                .get(&id)
+                .ok_or(NotesCollectionError::NoteNotFound(note_id))
+        } else {
+            Err(NotesCollectionError::NoteNotFound(note_id))
+        }
+    }
+
+    // operations with styles
+
+    #[must_use]
+    pub fn get_styles_count(&self) -> usize {
+        self.styles.len()
+    }
+
+    #[must_use]
+    pub fn iter_styles(&self) -> Iter<'_, Uuid, NoteStyle> {
+        self.styles.iter()
+    }
+
+    #[must_use]
+    pub fn get_style_names(&self) -> Vec<String> {
+        self.styles
+            .values()
+            .map(NoteStyle::get_display_name)
+            .collect()
+    }
+
+    pub fn try_get_default_style(&self) -> Result<&NoteStyle, NotesCollectionError> {
+        self.styles
+            .get(&self.default_style)
+            .ok_or(NotesCollectionError::StyleNotFound(self.default_style))
+    }
+
+    pub fn try_get_default_style_index(&self) -> Result<usize, NotesCollectionError> {
+        self.styles
+            .keys()
+            .enumerate()
+            .find_map(|(index, style_id)| (*style_id == self.default_style).then_some(index))
             .ok_or(NotesCollectionError::StyleNotFound(self.default_style))
     }
 
@@ -337,6 +1259,61 @@ impl NotesCollection {
             .ok_or(NotesCollectionError::StyleIndexNotFound(style_index))
     }
 
+    /// Get default style of the context falling back to the global default style
+    #[must_use]
+    pub fn default_style_for(&self, context: Option<&str>) -> Uuid {
+        context
+            .and_then(|key| self.context_default_styles.get(key))
+            .copied()
+            .unwrap_or(self.default_style)
+    }
+
+    pub fn try_set_default_style_for(
+        &mut self,
+        context: &str,
+        style_id: Uuid,
+    ) -> Result<(), NotesCollectionError> {
+        if !self.styles.contains_key(&style_id) {
+            return Err(NotesCollectionError::StyleNotFound(style_id));
+        }
+        if self
+            .context_default_styles
+            .insert(context.to_string(), style_id)
+            != Some(style_id)
+        {
+            tracing::debug!("(*) unsaved collection: set default style {style_id} for {context}");
+            self.is_dirty = true;
+        }
+        Ok(())
+    }
+
+    /// Get the index of the default style of the context, None if the context uses the global one
+    #[must_use]
+    pub fn get_default_style_index_for(&self, context: &str) -> Option<usize> {
+        let style_id = self.context_default_styles.get(context)?;
+        self.styles.keys().position(|id| id == style_id)
+    }
+
+    pub fn try_set_default_style_for_by_index(
+        &mut self,
+        context: &str,
+        style_index: usize,
+    ) -> Result<(), NotesCollectionError> {
+        let style_id = *self
+            .styles
+            .keys()
+            .nth(style_index)
+            .ok_or(NotesCollectionError::StyleIndexNotFound(style_index))?;
+        self.try_set_default_style_for(context, style_id)
+    }
+
+    pub fn reset_default_style_for(&mut self, context: &str) {
+        if self.context_default_styles.remove(context).is_some() {
+            tracing::debug!("(*) unsaved collection: reset default style for {context}");
+            self.is_dirty = true;
+        }
+    }
+
     pub fn try_get_style(&self, style_id: &Uuid) -> Result<&NoteStyle, NotesCollectionError> {
         self.styles
             .get(style_id)
@@ -347,21 +1324,103 @@ impl NotesCollection {
         &mut self,
         style_id: &Uuid,
     ) -> Result<&mut NoteStyle, NotesCollectionError> {
-        self.styles
+        let style = self
+            .styles
             .get_mut(style_id)
-            .ok_or(NotesCollectionError::StyleNotFound(*style_id))
+            .ok_or(NotesCollectionError::StyleNotFound(*style_id))?;
+        self.lent_styles
+            .entry(*style_id)
+            .or_insert(style.revision());
+        Ok(style)
+    }
+
+    /// Rename the style returning it for further changes, the collection becomes unsaved if the name differs
+    pub fn rename_style(
+        &mut self,
+        style_id: &Uuid,
+        new_name: &str,
+    ) -> Result<&mut NoteStyle, NotesCollectionError> {
+        let style = self.try_get_style_mut(style_id)?;
+        style.set_name(new_name);
+        Ok(style)
+    }
+
+    /// Change the background of the default style, the collection becomes unsaved if the color differs
+    pub fn set_default_style_color(&mut self, color: Color) -> Result<(), NotesCollectionError> {
+        let default_style = self.default_style;
+        self.try_get_style_mut(&default_style)?
+            .set_background_color(color);
+        Ok(())
+    }
+
+    // Templates
+
+    #[must_use]
+    pub fn templates(&self) -> &[NoteTemplate] {
+        &self.templates
+    }
+
+    /// Add the template returning its index
+    pub fn add_template(&mut self, template: NoteTemplate) -> usize {
+        tracing::debug!(
+            "(*) unsaved collection: added template {}",
+            template.get_name()
+        );
+        self.templates.push(template);
+        self.is_dirty = true;
+        self.templates.len() - 1
+    }
+
+    pub fn try_get_template(&self, index: usize) -> Result<&NoteTemplate, NotesCollectionError> {
+        self.templates
+            .get(index)
+            .ok_or(NotesCollectionError::TemplateIndexNotFound(index))
+    }
+
+    pub fn try_get_template_mut(
+        &mut self,
+        index: usize,
+    ) -> Result<&mut NoteTemplate, NotesCollectionError> {
+        self.templates
+            .get_mut(index)
+            .ok_or(NotesCollectionError::TemplateIndexNotFound(index))
+    }
+
+    pub fn delete_template(&mut self, index: usize) -> Result<NoteTemplate, NotesCollectionError> {
+        if index < self.templates.len() {
+            let template = self.templates.remove(index);
+            tracing::debug!(
+                "(*) unsaved collection: deleted template {}",
+                template.get_name()
+            );
+            self.is_dirty = true;
+            Ok(template)
+        } else {
+            Err(NotesCollectionError::TemplateIndexNotFound(index))
+        }
     }
 
     pub fn for_each_style_mut<F>(&mut self, f: F)
     where
         F: Fn(&mut NoteStyle),
     {
-        self.styles.values_mut().for_each(f);
+        for (style_id, style) in &mut self.styles {
+            let revision = style.revision();
+            f(style);
+            if style.revision() != revision {
+                log_change(&mut self.change_log, ChangeEvent::StyleChanged(*style_id));
+            }
+        }
     }
 
-    pub fn new_style(&mut self, name: String) -> Uuid {
+    /// Add new style having the font and color of the `source` style,
+    /// the default style is the source if `source` is not given or missing
+    pub fn new_style(&mut self, name: String, source: Option<Uuid>) -> Uuid {
         let id = Uuid::new_v4();
-        let new_style = if let Ok(source) = self.try_get_default_style() {
+        let source = source
+            .and_then(|source_id| self.styles.get(&source_id))
+            .map_or_else(|| self.try_get_default_style(), Ok);
+        let new_style = if let Ok(source) = source {
             NoteStyle::new(
                 name,
                 source.get_font().clone(),
@@ -371,38 +1430,193 @@ impl NotesCollection {
             NoteStyle::new(name, Font::default(), Color::WHITE)
         };
         self.styles.insert(id, new_style);
+        self.log_change(ChangeEvent::StyleChanged(id));
         id
     }
 
+    /// Add new style which is not saved until it is confirmed,
+    /// so an abrupt exit doesn't leave a half-created style behind
+    pub fn new_draft_style(&mut self, name: String, source: Option<Uuid>) -> Uuid {
+        let style_id = self.new_style(name, source);
+        self.draft_styles.insert(style_id);
+        style_id
+    }
+
+    /// Add a draft copy of the style, so a variant of the style is made quickly,
+    /// the copy is named by `copy_name` from the name of the source style
+    pub fn duplicate_style<F>(
+        &mut self,
+        source_id: &Uuid,
+        copy_name: F,
+    ) -> Result<Uuid, NotesCollectionError>
+    where
+        F: FnOnce(&str) -> String,
+    {
+        let mut style = self.try_get_style(source_id)?.clone();
+        style.set_name(&copy_name(style.get_name()));
+        let style_id = Uuid::new_v4();
+        tracing::debug!("duplicated style {source_id} as {style_id}");
+        self.styles.insert(style_id, style);
+        self.draft_styles.insert(style_id);
+        self.log_change(ChangeEvent::StyleChanged(style_id));
+        Ok(style_id)
+    }
+
+    /// Let the draft style be saved, nothing is changed if the style is not a draft one
+    pub fn confirm_style(&mut self, style_id: &Uuid) {
+        if self.draft_styles.remove(style_id) {
+            tracing::debug!("(*) unsaved collection: confirmed new style {style_id}");
+            self.is_dirty = true;
+        }
+    }
+
+    /// Get the name to save the style with: trimmed, generated from `fallback` if blank
+    /// and suffixed with a number if another style has the same name already
+    #[must_use]
+    pub fn validate_style_name(&self, style_id: &Uuid, name: &str, fallback: &str) -> String {
+        let name = name.trim();
+        let name = if name.is_empty() {
+            format!("{fallback}-{}", self.styles.len())
+        } else {
+            name.to_string()
+        };
+        let is_taken = |candidate: &str| {
+            self.styles
+                .iter()
+                .any(|(id, style)| id != style_id && style.get_name() == candidate)
+        };
+        if !is_taken(&name) {
+            return name;
+        }
+        // there is a free suffix since every other style takes one at most
+        (2..=self.styles.len() + 2)
+            .map(|suffix| format!("{name} {suffix}"))
+            .find(|candidate| !is_taken(candidate))
+            .unwrap_or(name)
+    }
+
     pub fn delete_style(&mut self, style_id: Uuid) -> Result<(), NotesCollectionError> {
         if self.styles.len() < 2 {
             Err(NotesCollectionError::DeleteLastStyle)
-        } else if self.styles.remove(&style_id).is_some() {
+        } else if self.remove_style(style_id) {
             tracing::debug!("(*) unsaved collection: deleted style {style_id}");
             self.is_dirty = true;
-            // if default style is being deleted select another one as default
-            if style_id == self.default_style {
-                self.default_style = self.styles.keys().next().copied().unwrap_or_default();
-                tracing::debug!(
-                    "replaced default style {style_id} with {}",
-                    self.default_style
-                );
-            }
-            // replace all existing notes style if it is being deleted
-            let default_style = self.default_style;
-            self.for_each_note_mut(|note| {
-                if note.style() == style_id {
-                    note.set_style(default_style);
-                }
-            });
             Ok(())
         } else {
             Err(NotesCollectionError::StyleNotFound(style_id))
         }
     }
 
+    /// Drop the draft style whose dialog is cancelled, the collection stays saved unless the draft is used already,
+    /// returns false if the style is not a draft one
+    pub fn discard_draft_style(&mut self, style_id: Uuid) -> bool {
+        if self.styles.len() < 2 || !self.draft_styles.contains(&style_id) {
+            return false;
+        }
+        tracing::debug!("discarded draft style {style_id}");
+        self.remove_style(style_id)
+    }
+
+    /// Styles referred by neither notes (the deleted ones included) nor templates,
+    /// except the default one and the `keep` one, e.g. the style being edited
+    #[must_use]
+    pub fn unreferenced_styles(&self, keep: Option<Uuid>) -> Vec<Uuid> {
+        let referenced: HashSet<Uuid> = self
+            .notes
+            .values()
+            .chain(self.deleted_notes.values())
+            .map(NoteData::style)
+            .chain(self.templates.iter().map(NoteTemplate::style))
+            .collect();
+        self.styles
+            .keys()
+            .filter(|style_id| {
+                **style_id != self.default_style
+                    && Some(**style_id) != keep
+                    && !referenced.contains(style_id)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Deletes all of the unreferenced styles keeping the default, the `keep` and the last remaining one,
+    /// returns the deleted style ids
+    pub fn gc_unreferenced_styles(&mut self, keep: Option<Uuid>) -> Vec<Uuid> {
+        self.unreferenced_styles(keep)
+            .into_iter()
+            .filter(|style_id| self.delete_style(*style_id).is_ok())
+            .collect()
+    }
+
+    /// Clears due reminders of live notes and returns those notes, so each reminder fires only once.
+    /// The hidden notes of due reminders are shown
+    pub fn take_due_reminders(&mut self, now: DateTime<Utc>) -> Vec<Uuid> {
+        self.notes
+            .iter_mut()
+            .filter(|(_, note)| note.is_reminder_due(now))
+            .map(|(note_id, note)| {
+                note.clear_reminder();
+                note.set_visibility(true);
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(*note_id));
+                *note_id
+            })
+            .collect()
+    }
+
+    /// The number of live notes using the style
+    #[must_use]
+    pub fn count_notes_with_style(&self, style_id: &Uuid) -> usize {
+        self.notes
+            .values()
+            .filter(|note| note.style() == *style_id)
+            .count()
+    }
+
+    /// Moves all notes of style `from` to style `to`, returns the number of notes changed.
+    /// Nothing is changed if `to` is not a known style.
+    pub fn reassign_notes_style(&mut self, from: Uuid, to: Uuid) -> usize {
+        if from == to || !self.styles.contains_key(&to) {
+            return 0;
+        }
+        let mut count = 0;
+        self.notes
+            .iter_mut()
+            .filter(|(_, note)| note.style() == from)
+            .for_each(|(note_id, note)| {
+                note.set_style(to);
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(*note_id));
+                count += 1;
+            });
+        if count > 0 {
+            tracing::debug!("reassigned {count} notes from style {from} to {to}");
+        }
+        count
+    }
+
     // operations with particular note style
 
+    // the style of the note falling back to the default one,
+    // none only if the default style is missing as well which is repaired on reading
+    fn resolve_style(&self, note: &NoteData) -> Option<&NoteStyle> {
+        self.styles
+            .get(&note.style())
+            .or_else(|| self.styles.get(&self.default_style))
+    }
+
+    fn resolve_style_or_builtin(&self, note: &NoteData) -> &NoteStyle {
+        self.resolve_style(note).unwrap_or(&BUILTIN_STYLE)
+    }
+
+    pub fn try_get_note_with_style(
+        &self,
+        note_id: &Uuid,
+    ) -> Result<(&NoteData, &NoteStyle), NotesCollectionError> {
+        let note = self.try_get_note(note_id)?;
+        self.resolve_style(note)
+            .map(|style| (note, style))
+            .ok_or(NotesCollectionError::StyleNotFound(note.style()))
+    }
+
     pub fn try_get_note_style(&self, note_id: Uuid) -> Result<&NoteStyle, NotesCollectionError> {
         // the first, search among live notes
         self.try_get_note(&note_id)
@@ -443,8 +1657,100 @@ impl NotesCollection {
         }
     }
 
+    /// Copies the chosen live notes together with the styles they refer to.
+    /// The default style is kept if any chosen note uses it, otherwise another referenced style becomes the default one.
+    #[must_use]
+    pub fn subset(&self, ids: &[Uuid]) -> NotesCollection {
+        let notes: HashMap<Uuid, NoteData> = ids
+            .iter()
+            .filter_map(|id| self.notes.get(id).map(|note| (*id, note.clone())))
+            .collect();
+        let mut styles: HashMap<Uuid, NoteStyle> = notes
+            .values()
+            .filter_map(|note| {
+                self.styles
+                    .get(&note.style())
+                    .map(|style| (note.style(), style.clone()))
+            })
+            .collect();
+        if styles.is_empty()
+            && let Ok(default_style) = self.try_get_default_style()
+        {
+            // keep the collection valid even if nothing is chosen
+            styles.insert(self.default_style, default_style.clone());
+        }
+        let mut instance = Self {
+            notes,
+            styles,
+            default_style: self.default_style,
+            context_default_styles: HashMap::new(),
+            templates: Vec::new(),
+            is_dirty: true, // not saved yet
+            deleted_notes: HashMap::new(),
+            draft_styles: HashSet::new(),
+            stored_notes: HashSet::new(),
+            removed_notes: HashSet::new(),
+            change_log: Vec::new(),
+            lent_notes: HashMap::new(),
+            lent_styles: HashMap::new(),
+        };
+        instance.ensure_default_style();
+        instance
+    }
+
     // private methods
 
+    // the notes and templates of the removed style get the default one, returns false if the style is not found
+    fn remove_style(&mut self, style_id: Uuid) -> bool {
+        if self.styles.remove(&style_id).is_none() {
+            return false;
+        }
+        self.draft_styles.remove(&style_id);
+        self.log_change(ChangeEvent::StyleChanged(style_id));
+        // if default style is being deleted select another one as default
+        if style_id == self.default_style {
+            self.default_style = self.styles.keys().next().copied().unwrap_or_default();
+            tracing::debug!(
+                "replaced default style {style_id} with {}",
+                self.default_style
+            );
+        }
+        // forget the style as a default one in any context
+        self.context_default_styles
+            .retain(|_, default_style| *default_style != style_id);
+        // replace all existing notes style if it is being deleted
+        let default_style = self.default_style;
+        for (note_id, note) in &mut self.notes {
+            if note.style() == style_id {
+                note.set_style(default_style);
+                log_change(&mut self.change_log, ChangeEvent::NoteModified(*note_id));
+            }
+        }
+        for template in &mut self.templates {
+            if template.style() == style_id {
+                template.set_style(default_style);
+            }
+        }
+        true
+    }
+
+    fn log_change(&mut self, event: ChangeEvent) {
+        log_change(&mut self.change_log, event);
+    }
+
+    fn ensure_notes_styles(&mut self) {
+        let default_style = self.default_style;
+        for (note_id, note) in &mut self.notes {
+            if !self.styles.contains_key(&note.style()) {
+                tracing::warn!(
+                    "note {note_id} refers to unknown style {}, default style is used",
+                    note.style()
+                );
+                note.set_style(default_style);
+            }
+        }
+    }
+
     fn ensure_default_style(&mut self) {
         // ensure default_style is correct
         if !self.styles.contains_key(&self.default_style) {
@@ -466,21 +1772,47 @@ impl NotesCollection {
 
 impl Default for NotesCollection {
     fn default() -> Self {
+        Self::with_default_background(Color::WHITE)
+    }
+}
+
+impl NotesCollection {
+    /// The same as `default()` but the default note style has the `bgcolor` background
+    #[must_use]
+    pub fn with_default_background(bgcolor: Color) -> Self {
         // instantiate default note style
         let default_style = Uuid::new_v4();
-        let styles = HashMap::from_iter([(default_style, NoteStyle::default())]);
+        let styles = HashMap::from_iter([(
+            default_style,
+            NoteStyle::new(DEF_NOTE_STYLE_NAME.to_string(), Font::default(), bgcolor),
+        )]);
         // create note with default style
         let notes = HashMap::from_iter([(Uuid::new_v4(), NoteData::new(default_style))]);
         Self {
             notes,
             styles,
             default_style,
+            context_default_styles: HashMap::new(),
+            templates: Vec::new(),
             is_dirty: false,
             deleted_notes: HashMap::new(),
+            draft_styles: HashSet::new(),
+            stored_notes: HashSet::new(),
+            removed_notes: HashSet::new(),
+            change_log: Vec::new(),
+            lent_notes: HashMap::new(),
+            lent_styles: HashMap::new(),
         }
     }
 }
 
+// the same event in a row (e.g. a note being dragged) is logged once
+fn log_change(change_log: &mut Vec<ChangeEvent>, event: ChangeEvent) {
+    if change_log.last() != Some(&event) {
+        change_log.push(event);
+    }
+}
+
 #[tokio::test]
 async fn write_and_read_json() {
     const INPUT_FILE: &str = "test_data/indicator-stickynotes";
@@ -579,3 +1911,1843 @@ fn for_each_note_mut() {
     // test all of notes are hidden
     assert!(!collection.iter_notes().any(|(_, note)| note.is_visible()));
 }
+
+#[test]
+fn iter_visible_and_hidden_notes() {
+    const HIDDEN_COUNT: usize = 3;
+    const VISIBLE_COUNT: usize = 5;
+
+    let mut collection = NotesCollection::default();
+    // the auto created note is visible as well
+    for _ in 1..VISIBLE_COUNT {
+        collection.new_note();
+    }
+    for _ in 0..HIDDEN_COUNT {
+        let note_id = collection.new_note();
+        collection
+            .try_get_note_mut(&note_id)
+            .expect("new note must exist")
+            .set_visibility(false);
+    }
+    assert_eq!(collection.get_notes_count(), VISIBLE_COUNT + HIDDEN_COUNT);
+
+    assert_eq!(collection.iter_visible_notes().count(), VISIBLE_COUNT);
+    assert!(
+        collection
+            .iter_visible_notes()
+            .all(|(_, note)| note.is_visible())
+    );
+    assert_eq!(collection.iter_hidden_notes().count(), HIDDEN_COUNT);
+    assert!(
+        collection
+            .iter_hidden_notes()
+            .all(|(_, note)| !note.is_visible())
+    );
+
+    // hide all, then nothing is visible
+    collection.for_each_note_mut(|note| note.set_visibility(false));
+    assert_eq!(collection.iter_visible_notes().count(), 0);
+    assert_eq!(
+        collection.iter_hidden_notes().count(),
+        VISIBLE_COUNT + HIDDEN_COUNT
+    );
+}
+
+#[test]
+fn default_style_for_context() {
+    const CONTEXT: &str = "DP-2";
+    const OTHER_CONTEXT: &str = "HDMI-1";
+
+    let mut collection = NotesCollection::default();
+    let global_style = collection.default_style;
+    let context_style = collection.new_style("context".to_string(), None);
+
+    // no context entries yet, so global is used everywhere
+    assert_eq!(collection.default_style_for(None), global_style);
+    assert_eq!(collection.default_style_for(Some(CONTEXT)), global_style);
+
+    // unknown style cannot be default
+    assert!(
+        collection
+            .try_set_default_style_for(CONTEXT, Uuid::new_v4())
+            .is_err()
+    );
+
+    assert!(
+        collection
+            .try_set_default_style_for(CONTEXT, context_style)
+            .is_ok()
+    );
+    assert!(collection.is_unsaved());
+    assert_eq!(collection.default_style_for(Some(CONTEXT)), context_style);
+    assert_eq!(
+        collection.default_style_for(Some(OTHER_CONTEXT)),
+        global_style
+    );
+    assert!(
+        collection
+            .get_default_style_index_for(OTHER_CONTEXT)
+            .is_none()
+    );
+
+    // the style is chosen by index in settings
+    let global_index = collection
+        .try_get_default_style_index()
+        .expect("default style must exist");
+    assert!(
+        collection
+            .try_set_default_style_for_by_index(OTHER_CONTEXT, global_index)
+            .is_ok()
+    );
+    assert_eq!(
+        collection.get_default_style_index_for(OTHER_CONTEXT),
+        Some(global_index)
+    );
+    assert!(
+        collection
+            .try_set_default_style_for_by_index(OTHER_CONTEXT, collection.get_styles_count())
+            .is_err()
+    );
+    collection.reset_default_style_for(OTHER_CONTEXT);
+    assert!(
+        collection
+            .get_default_style_index_for(OTHER_CONTEXT)
+            .is_none()
+    );
+    assert_eq!(collection.default_style_for(None), global_style);
+
+    // new notes get the style of their context
+    let note_id = collection.new_note_for(Some(CONTEXT));
+    assert_eq!(
+        collection.try_get_note(&note_id).map(NoteData::style).ok(),
+        Some(context_style)
+    );
+    let note_id = collection.new_note_for(Some(OTHER_CONTEXT));
+    assert_eq!(
+        collection.try_get_note(&note_id).map(NoteData::style).ok(),
+        Some(global_style)
+    );
+
+    // context defaults survive save and load
+    let json = collection
+        .try_write()
+        .expect("serialize notes must succeed");
+    let result = NotesCollection::try_read(&json).expect("deserialize notes must succeed");
+    assert_eq!(result.default_style_for(Some(CONTEXT)), context_style);
+
+    // deleted style is no more default in any context
+    assert!(collection.delete_style(context_style).is_ok());
+    assert_eq!(collection.default_style_for(Some(CONTEXT)), global_style);
+}
+
+#[test]
+fn read_json_without_context_default_styles() {
+    let collection = NotesCollection::default();
+    let mut json: serde_json::Value = serde_json::from_str(
+        &collection
+            .try_write()
+            .expect("serialize notes must succeed"),
+    )
+    .expect("parse json must succeed");
+    // the data saved before contexts were introduced
+    json.as_object_mut()
+        .expect("collection must be an object")
+        .remove("context_default_styles");
+
+    let result =
+        NotesCollection::try_read(&json.to_string()).expect("read legacy notes must succeed");
+    assert_eq!(
+        result.default_style_for(Some("any")),
+        collection.default_style
+    );
+}
+
+#[cfg(test)]
+fn build_conflicting_collections() -> (NotesCollection, NotesCollection, Uuid) {
+    let mut mine = NotesCollection::default();
+    let note_id = mine.new_note();
+    mine.try_get_note_mut(&note_id)
+        .expect("new note must exist")
+        .set_content("mine".to_string());
+    let mut theirs = mine.clone();
+    theirs
+        .try_get_note_mut(&note_id)
+        .expect("cloned note must exist")
+        .set_content("theirs".to_string());
+    (mine, theirs, note_id)
+}
+
+#[test]
+fn merge_keep_mine() {
+    let (mut mine, theirs, note_id) = build_conflicting_collections();
+    let notes_count = mine.get_notes_count();
+    mine.merge(theirs, MergeStrategy::KeepMine, StyleMatch::ById);
+    assert_eq!(mine.get_notes_count(), notes_count);
+    assert_eq!(
+        mine.try_get_note(&note_id).map(NoteData::get_content).ok(),
+        Some("mine")
+    );
+}
+
+#[test]
+fn merge_keep_theirs() {
+    let (mut mine, theirs, note_id) = build_conflicting_collections();
+    let notes_count = mine.get_notes_count();
+    mine.merge(theirs, MergeStrategy::KeepTheirs, StyleMatch::ById);
+    assert_eq!(mine.get_notes_count(), notes_count);
+    assert_eq!(
+        mine.try_get_note(&note_id).map(NoteData::get_content).ok(),
+        Some("theirs")
+    );
+}
+
+#[test]
+fn merge_keep_newer() {
+    // their note was modified after mine
+    let (mut mine, theirs, note_id) = build_conflicting_collections();
+    mine.merge(theirs, MergeStrategy::KeepNewer, StyleMatch::ById);
+    assert_eq!(
+        mine.try_get_note(&note_id).map(NoteData::get_content).ok(),
+        Some("theirs")
+    );
+
+    // my note was modified after theirs
+    let (mut mine, theirs, note_id) = build_conflicting_collections();
+    mine.try_get_note_mut(&note_id)
+        .expect("note must exist")
+        .set_content("mine again".to_string());
+    mine.merge(theirs, MergeStrategy::KeepNewer, StyleMatch::ById);
+    assert_eq!(
+        mine.try_get_note(&note_id).map(NoteData::get_content).ok(),
+        Some("mine again")
+    );
+}
+
+#[test]
+fn merge_new_notes_and_colliding_styles() {
+    let mut mine = NotesCollection::default();
+    mine.commit_changes();
+    let mut theirs = NotesCollection::default();
+    // their default style has the same uuid as mine but looks different
+    let my_style_id = mine.default_style;
+    let their_style = theirs
+        .try_get_default_style()
+        .expect("default style must exist")
+        .clone();
+    theirs.styles.clear();
+    theirs.styles.insert(my_style_id, their_style);
+    theirs.default_style = my_style_id;
+    theirs
+        .try_get_style_mut(&my_style_id)
+        .expect("style must exist")
+        .set_name("theirs");
+    let their_note_id = theirs.new_note();
+
+    let notes_count = mine.get_notes_count() + theirs.get_notes_count();
+    mine.merge(theirs, MergeStrategy::KeepMine, StyleMatch::ById);
+    assert!(mine.is_unsaved());
+    assert_eq!(mine.get_notes_count(), notes_count);
+    assert_eq!(mine.get_styles_count(), 2);
+    // my style is untouched
+    assert_eq!(
+        mine.try_get_style(&my_style_id)
+            .map(NoteStyle::get_name)
+            .ok(),
+        Some(super::DEF_NOTE_STYLE_NAME)
+    );
+    // their note refers to their (remapped) style
+    let their_note_style = mine
+        .try_get_note(&their_note_id)
+        .map(NoteData::style)
+        .expect("merged note must exist");
+    assert_ne!(their_note_style, my_style_id);
+    assert_eq!(
+        mine.try_get_style(&their_note_style)
+            .map(NoteStyle::get_name)
+            .ok(),
+        Some("theirs")
+    );
+}
+
+#[test]
+fn detect_notes_offscreen() {
+    use cosmic::iced::Size;
+
+    let mut collection = NotesCollection::default();
+    let onscreen = collection.new_note();
+    let offscreen = collection.new_note();
+    let on_second_screen = collection.new_note();
+    collection
+        .try_get_note_mut(&onscreen)
+        .expect("note must exist")
+        .set_position(100, 100);
+    collection
+        .try_get_note_mut(&offscreen)
+        .expect("note must exist")
+        .set_position(5000, 100);
+    collection
+        .try_get_note_mut(&on_second_screen)
+        .expect("note must exist")
+        .set_position(2000, 100);
+
+    let primary = Rectangle::new(Point::ORIGIN, Size::new(1920.0, 1080.0));
+    let secondary = Rectangle::new(Point::new(1920.0, 0.0), Size::new(1920.0, 1080.0));
+
+    // the auto created note at (0, 0) is on the primary screen
+    let result = collection.notes_offscreen(&[primary]);
+    assert_eq!(result.len(), 2);
+    assert!(result.contains(&offscreen));
+    assert!(result.contains(&on_second_screen));
+
+    let result = collection.notes_offscreen(&[primary, secondary]);
+    assert_eq!(result, vec![offscreen]);
+
+    // no screens known
+    assert_eq!(
+        collection.notes_offscreen(&[]).len(),
+        collection.get_notes_count()
+    );
+}
+
+#[test]
+fn build_collection_with_known_styles() {
+    let (mut collection, white) =
+        NotesCollection::with_style("white".to_string(), Font::default(), Color::WHITE);
+    assert_eq!(collection.get_notes_count(), 0);
+    assert_eq!(collection.get_styles_count(), 1);
+    assert_eq!(collection.default_style_for(None), white);
+    assert!(!collection.is_unsaved());
+
+    let black = collection.new_style("black".to_string(), None);
+    let white_note = collection
+        .add_note_with_style("white note".to_string(), white)
+        .expect("add note with existing style must succeed");
+    let black_note = collection
+        .add_note_with_style("black note".to_string(), black)
+        .expect("add note with existing style must succeed");
+    assert!(matches!(
+        collection.add_note_with_style("lost note".to_string(), Uuid::new_v4()),
+        Err(NotesCollectionError::StyleNotFound(_))
+    ));
+    assert_eq!(collection.get_notes_count(), 2);
+    assert!(collection.is_unsaved());
+
+    let note = collection
+        .try_get_note(&white_note)
+        .expect("note must exist");
+    assert_eq!(note.get_content(), "white note");
+    assert_eq!(note.style(), white);
+    assert_eq!(
+        collection
+            .try_get_note_style(black_note)
+            .map(NoteStyle::get_name)
+            .ok(),
+        Some("black")
+    );
+}
+
+#[test]
+fn import_export_preserves_font() {
+    const INPUT_FILE: &str = "test_data/indicator-stickynotes";
+
+    let buf =
+        std::fs::read(INPUT_FILE).unwrap_or_else(|_| panic!("reading input file {INPUT_FILE}"));
+    let imported = StickyNotesDatabase::try_import(&buf).expect("import must succeed");
+    let collection = NotesCollection::from(imported);
+    let default_style = collection
+        .try_get_default_style()
+        .expect("default style must exist");
+    assert_eq!(
+        default_style.get_font().family.as_deref(),
+        Some("Fira Sans")
+    );
+    assert_eq!(default_style.get_font().size, 14);
+
+    let exported = StickyNotesDatabase::from(collection);
+    let default_category = exported
+        .try_get_default_category()
+        .expect("default category must exist");
+    assert_eq!(default_category.font.as_str(), "Fira Sans 14");
+}
+
+#[test]
+fn reassign_notes_to_another_style() {
+    let (mut collection, white) =
+        NotesCollection::with_style("white".to_string(), Font::default(), Color::WHITE);
+    let black = collection.new_style("black".to_string(), None);
+    let first = collection
+        .add_note_with_style("first".to_string(), white)
+        .expect("add note with existing style must succeed");
+    let second = collection
+        .add_note_with_style("second".to_string(), white)
+        .expect("add note with existing style must succeed");
+    let third = collection
+        .add_note_with_style("third".to_string(), black)
+        .expect("add note with existing style must succeed");
+
+    assert_eq!(collection.count_notes_with_style(&white), 2);
+    assert_eq!(collection.count_notes_with_style(&black), 1);
+
+    // unknown target style or the same style change nothing
+    assert_eq!(collection.reassign_notes_style(white, Uuid::new_v4()), 0);
+    assert_eq!(collection.reassign_notes_style(white, white), 0);
+
+    assert_eq!(collection.reassign_notes_style(white, black), 2);
+    for note_id in [first, second, third] {
+        assert_eq!(
+            collection.try_get_note(&note_id).map(NoteData::style).ok(),
+            Some(black)
+        );
+    }
+    // the source style is kept and has no notes anymore
+    assert!(collection.try_get_style(&white).is_ok());
+    assert_eq!(collection.count_notes_with_style(&white), 0);
+    assert_eq!(collection.count_notes_with_style(&black), 3);
+    assert_eq!(collection.reassign_notes_style(white, black), 0);
+}
+
+#[test]
+fn default_collection_with_background() {
+    let collection = NotesCollection::with_default_background(Color::BLACK);
+    assert_eq!(collection.get_notes_count(), 1);
+    assert_eq!(collection.get_styles_count(), 1);
+    let style = collection
+        .try_get_default_style()
+        .expect("default style must exist");
+    assert_eq!(style.get_background_color(), Color::BLACK);
+    assert!(style.looks_like(&NoteStyle::new(
+        NoteStyle::default().get_name().to_string(),
+        Font::default(),
+        Color::BLACK
+    )));
+    assert!(!collection.is_unsaved());
+}
+
+#[test]
+fn subset_with_referenced_styles() {
+    let (mut collection, white) =
+        NotesCollection::with_style("white".to_string(), Font::default(), Color::WHITE);
+    let black = collection.new_style("black".to_string(), None);
+    let grey = collection.new_style("grey".to_string(), None);
+    let white_note = collection
+        .add_note_with_style("white note".to_string(), white)
+        .expect("add note with existing style must succeed");
+    let black_note = collection
+        .add_note_with_style("black note".to_string(), black)
+        .expect("add note with existing style must succeed");
+    let grey_note = collection
+        .add_note_with_style("grey note".to_string(), grey)
+        .expect("add note with existing style must succeed");
+
+    // unknown notes are ignored, the default style is not referenced
+    let subset = collection.subset(&[black_note, grey_note, Uuid::new_v4()]);
+    assert_eq!(subset.get_notes_count(), 2);
+    assert_eq!(subset.get_styles_count(), 2);
+    assert!(subset.try_get_note(&white_note).is_err());
+    assert!(subset.try_get_style(&white).is_err());
+    assert!(subset.try_get_default_style().is_ok());
+    assert!(subset.try_get_note_style(black_note).is_ok());
+    assert!(subset.try_get_note_style(grey_note).is_ok());
+
+    // the default style is kept if referenced
+    let subset = collection.subset(&[white_note]);
+    assert_eq!(subset.get_styles_count(), 1);
+    assert_eq!(subset.default_style_for(None), white);
+
+    // nothing chosen still gives a valid collection
+    let subset = collection.subset(&[]);
+    assert_eq!(subset.get_notes_count(), 0);
+    assert_eq!(subset.default_style_for(None), white);
+
+    // the subset survives export & import
+    let exported = StickyNotesDatabase::from(collection.subset(&[black_note, grey_note]));
+    let imported = NotesCollection::from(exported);
+    assert_eq!(imported.get_notes_count(), 2);
+    assert_eq!(imported.get_styles_count(), 2);
+    assert!(imported.try_get_note_style(black_note).is_ok());
+    assert!(imported.try_get_note_style(grey_note).is_ok());
+}
+
+#[test]
+fn due_reminder_shows_hidden_note() {
+    let (mut collection, white) =
+        NotesCollection::with_style("white".to_string(), Font::default(), Color::WHITE);
+    let now = Utc::now();
+    let visible = collection
+        .add_note_with_style("visible".to_string(), white)
+        .expect("add note with existing style must succeed");
+    let hidden = collection
+        .add_note_with_style("hidden".to_string(), white)
+        .expect("add note with existing style must succeed");
+    let note = collection
+        .try_get_note_mut(&hidden)
+        .expect("note must exist");
+    note.set_reminder(now - chrono::Duration::minutes(1));
+    note.set_visibility(false);
+
+    assert_eq!(collection.take_due_reminders(now), vec![hidden]);
+    // the note of the reminder is kept visible over the limit, another one is hidden instead
+    assert_eq!(collection.hide_oldest_visible(1, &[hidden]), vec![visible]);
+    assert!(
+        collection
+            .try_get_note(&hidden)
+            .expect("note must exist")
+            .is_visible()
+    );
+}
+
+#[test]
+fn take_due_reminders_once() {
+    let (mut collection, white) =
+        NotesCollection::with_style("white".to_string(), Font::default(), Color::WHITE);
+    let now = Utc::now();
+    let due = collection
+        .add_note_with_style("due".to_string(), white)
+        .expect("add note with existing style must succeed");
+    let later = collection
+        .add_note_with_style("later".to_string(), white)
+        .expect("add note with existing style must succeed");
+    let deleted = collection
+        .add_note_with_style("deleted".to_string(), white)
+        .expect("add note with existing style must succeed");
+    for (note_id, reminder) in [
+        (due, now - chrono::Duration::hours(1)),
+        (later, now + chrono::Duration::hours(1)),
+        (deleted, now - chrono::Duration::hours(1)),
+    ] {
+        collection
+            .try_get_note_mut(&note_id)
+            .expect("note must exist")
+            .set_reminder(reminder);
+    }
+    collection.delete_note(deleted);
+
+    assert_eq!(collection.take_due_reminders(now), vec![due]);
+    // fired reminders are not repeated
+    assert!(collection.take_due_reminders(now).is_empty());
+    assert_eq!(
+        collection.take_due_reminders(now + chrono::Duration::hours(2)),
+        vec![later]
+    );
+}
+
+#[test]
+fn read_legacy_json_with_minimal_fields() {
+    const NOTE_ID: &str = "6f1c1f3e-5b0a-4a4e-9d55-0c6b8c1f2a10";
+    const STYLE_ID: &str = "0b7f4d8a-2c3e-4f5a-8b6c-7d8e9f0a1b2c";
+    let json = format!(
+        r#"{{
+            "notes": {{
+                "{NOTE_ID}": {{ "content": "legacy note", "position": [10, 20], "size": [200, 100] }}
+            }},
+            "styles": {{
+                "{STYLE_ID}": {{ "name": "legacy", "bgcolor": [1.0, 1.0, 0.5] }}
+            }}
+        }}"#
+    );
+
+    let collection = NotesCollection::try_read(&json).expect("read legacy notes must succeed");
+    let note_id = Uuid::parse_str(NOTE_ID).expect("note id must be valid");
+    let style_id = Uuid::parse_str(STYLE_ID).expect("style id must be valid");
+    let note = collection
+        .try_get_note(&note_id)
+        .expect("legacy note must be read");
+    assert_eq!(note.get_content(), "legacy note");
+    assert_eq!((note.left(), note.top()), (10, 20));
+    assert_eq!((note.width(), note.height()), (200, 100));
+    assert!(note.is_visible());
+    assert!(!note.is_locked());
+    assert!(note.get_reminder().is_none());
+    assert!(note.get_attachments().is_empty());
+    assert!(note.get_icon_size().is_none());
+    // the missing styles are repaired
+    assert_eq!(collection.default_style, style_id);
+    assert_eq!(note.style(), style_id);
+    let style = collection
+        .try_get_style(&style_id)
+        .expect("legacy style must be read");
+    assert_eq!(style.get_name(), "legacy");
+    assert_eq!(style.get_font(), &Font::default());
+    assert!(style.get_group().is_none());
+}
+
+#[test]
+fn style_groups() {
+    const STYLE_ID: &str = "0b7f4d8a-2c3e-4f5a-8b6c-7d8e9f0a1b2c";
+    let json = format!(
+        r#"{{
+            "styles": {{
+                "{STYLE_ID}": {{ "name": "yellow", "bgcolor": [1.0, 1.0, 0.5] }}
+            }}
+        }}"#
+    );
+
+    let mut collection = NotesCollection::try_read(&json).expect("read notes must succeed");
+    let style_id = Uuid::parse_str(STYLE_ID).expect("style id must be valid");
+    // the style without group is migrated as ungrouped
+    assert_eq!(collection.get_style_names(), vec!["yellow".to_string()]);
+
+    let style = collection
+        .try_get_style_mut(&style_id)
+        .expect("style must be read");
+    style.set_group(Some(" "));
+    assert!(style.get_group().is_none());
+    assert!(!style.is_changed());
+    style.set_group(Some("Work"));
+    assert_eq!(style.get_group(), Some("Work"));
+    assert!(style.is_changed());
+    assert_eq!(
+        collection.get_style_names(),
+        vec!["Work / yellow".to_string()]
+    );
+
+    let style = collection
+        .try_get_style(&style_id)
+        .expect("style must be read");
+    let text = serde_json::to_string(style).expect("style must be serialized");
+    let read: NoteStyle = serde_json::from_str(&text).expect("style must be read back");
+    assert_eq!(read.get_group(), Some("Work"));
+}
+
+#[test]
+fn collection_stats() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("white".to_string(), Font::default(), Color::WHITE);
+    assert_eq!(
+        collection.stats(),
+        CollectionStats {
+            styles_count: 1,
+            ..CollectionStats::default()
+        }
+    );
+
+    let first = collection
+        .add_note_with_style("Привет".to_string(), style_id)
+        .expect("add note must succeed");
+    let second = collection
+        .add_note_with_style("hello".to_string(), style_id)
+        .expect("add note must succeed");
+    let deleted = collection
+        .add_note_with_style("deleted".to_string(), style_id)
+        .expect("add note must succeed");
+    collection.delete_note(deleted);
+    collection.new_style("other".to_string(), None);
+
+    let first_modified = collection
+        .try_get_note(&first)
+        .expect("note must exist")
+        .get_modified()
+        .to_utc();
+    let second_modified = collection
+        .try_get_note(&second)
+        .expect("note must exist")
+        .get_modified()
+        .to_utc();
+    let stats = collection.stats();
+    assert_eq!(stats.notes_count, 2);
+    assert_eq!(stats.deleted_count, 1);
+    assert_eq!(stats.styles_count, 2);
+    // characters are counted rather than bytes
+    assert_eq!(stats.total_chars, 11);
+    assert_eq!(
+        stats.oldest_modified,
+        Some(first_modified.min(second_modified))
+    );
+    assert_eq!(
+        stats.newest_modified,
+        Some(first_modified.max(second_modified))
+    );
+}
+
+#[cfg(test)]
+fn build_export_collection() -> (NotesCollection, Uuid, Uuid) {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("white".to_string(), Font::default(), Color::WHITE);
+    let first = collection
+        .add_note_with_style("first\nline two\n".to_string(), style_id)
+        .expect("add note must succeed");
+    let second = collection
+        .add_note_with_style("second".to_string(), style_id)
+        .expect("add note must succeed");
+    (collection, first, second)
+}
+
+#[test]
+fn format_as_indicator_stickynotes() {
+    let (collection, first, second) = build_export_collection();
+    let content = collection
+        .try_format(ExportFormat::IndicatorStickyNotes)
+        .expect("format must succeed");
+    let exported = NotesCollection::from(
+        StickyNotesDatabase::try_import(&content).expect("import must succeed"),
+    );
+    for note_id in [first, second] {
+        assert_eq!(
+            exported
+                .try_get_note(&note_id)
+                .expect("note must be exported")
+                .get_content(),
+            collection
+                .try_get_note(&note_id)
+                .expect("note must exist")
+                .get_content()
+        );
+    }
+}
+
+#[test]
+fn format_as_native_json() {
+    let (collection, _, _) = build_export_collection();
+    let content = collection
+        .try_format(ExportFormat::NativeJson)
+        .expect("format must succeed");
+    let exported = NotesCollection::try_read(
+        &String::from_utf8(content).expect("exported content must be utf8"),
+    )
+    .expect("read must succeed");
+    assert_eq!(exported.stats(), collection.stats());
+    for (note_id, note) in collection.iter_notes() {
+        let exported_note = exported
+            .try_get_note(note_id)
+            .expect("note must be exported");
+        assert_eq!(exported_note.get_content(), note.get_content());
+        assert_eq!(exported_note.style(), note.style());
+    }
+}
+
+#[test]
+fn format_as_markdown() {
+    let (collection, first, _) = build_export_collection();
+    let content = String::from_utf8(
+        collection
+            .try_format(ExportFormat::Markdown)
+            .expect("format must succeed"),
+    )
+    .expect("exported content must be utf8");
+    let modified = collection
+        .try_get_note(&first)
+        .expect("note must exist")
+        .get_modified()
+        .format("%Y-%m-%d %H:%M");
+    assert!(content.contains(&format!(
+        "## first\n\n_white, {modified}_\n\nfirst\nline two\n"
+    )));
+    assert!(content.contains("\n## second\n"));
+}
+
+#[test]
+fn format_as_plain_text() {
+    let (collection, _, _) = build_export_collection();
+    let content = String::from_utf8(
+        collection
+            .try_format(ExportFormat::PlainText)
+            .expect("format must succeed"),
+    )
+    .expect("exported content must be utf8");
+    assert_eq!(content, "first\nline two\n----------\nsecond\n");
+}
+
+#[tokio::test]
+async fn import_keep_takeout_into_collection() {
+    const INPUT_DIR: &str = "test_data/google-keep";
+
+    let collection = NotesCollection::try_import_keep(INPUT_DIR)
+        .await
+        .expect("import must succeed");
+    assert!(collection.is_unsaved());
+    let stats = collection.stats();
+    // the trashed note is deleted, the archived one is hidden
+    assert_eq!(stats.notes_count, 2);
+    assert_eq!(stats.deleted_count, 1);
+    assert_eq!(collection.iter_hidden_notes().count(), 1);
+    // a style per color, the default color is the default style
+    assert_eq!(stats.styles_count, 3);
+    let default_style = collection
+        .try_get_default_style()
+        .expect("default style must exist");
+    assert_eq!(default_style.get_name(), "Keep default");
+    assert_eq!(default_style.get_background_color(), Color::WHITE);
+
+    let (_, shopping) = collection
+        .iter_visible_notes()
+        .next()
+        .expect("pinned note must be visible");
+    assert_eq!(shopping.get_title(), "Shopping");
+    assert!(shopping.is_always_on_top());
+    let (_, ideas) = collection
+        .iter_hidden_notes()
+        .next()
+        .expect("archived note must be hidden");
+    assert!(!ideas.is_always_on_top());
+    let style = collection
+        .try_get_style(&shopping.style())
+        .expect("note style must exist");
+    assert_eq!(style.get_name(), "Keep yellow");
+}
+
+#[test]
+fn notes_predicates() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("white".to_string(), Font::default(), Color::WHITE);
+    assert!(collection.is_empty());
+    assert!(!collection.has_visible_notes());
+    assert!(!collection.has_hidden_notes());
+    assert!(!collection.has_deleted_notes());
+    assert!(!collection.all_locked());
+    assert!(!collection.any_unlocked());
+
+    let first = collection
+        .add_note_with_style("first".to_string(), style_id)
+        .expect("add note must succeed");
+    let second = collection
+        .add_note_with_style("second".to_string(), style_id)
+        .expect("add note must succeed");
+    assert!(!collection.is_empty());
+    assert!(collection.has_visible_notes());
+    assert!(!collection.has_hidden_notes());
+    assert!(!collection.all_locked());
+    assert!(collection.any_unlocked());
+
+    collection.for_each_note_mut(|note| note.set_locking(true));
+    collection
+        .try_get_note_mut(&first)
+        .expect("note must exist")
+        .set_visibility(false);
+    assert!(collection.has_visible_notes());
+    assert!(collection.has_hidden_notes());
+    assert!(collection.all_locked());
+    assert!(!collection.any_unlocked());
+
+    collection.delete_note(second);
+    assert!(collection.has_deleted_notes());
+    assert!(!collection.has_visible_notes());
+    assert!(collection.has_hidden_notes());
+}
+
+#[cfg(test)]
+fn build_sort_collection() -> NotesCollection {
+    let json = r#"{
+        "notes": {
+            "00000000-0000-0000-0000-000000000001": {
+                "content": "banana",
+                "modified": "2024-03-01T00:00:00Z",
+                "created": "2024-01-01T00:00:00Z"
+            },
+            "00000000-0000-0000-0000-000000000002": {
+                "content": "Apple",
+                "modified": "2024-02-01T00:00:00Z",
+                "created": "2024-01-03T00:00:00Z"
+            },
+            "00000000-0000-0000-0000-000000000003": {
+                "content": "cherry",
+                "modified": "2024-04-01T00:00:00Z",
+                "created": "2024-01-02T00:00:00Z"
+            }
+        }
+    }"#;
+    NotesCollection::try_read(json).expect("read notes must succeed")
+}
+
+#[cfg(test)]
+fn sorted_titles(collection: &NotesCollection, by: NoteSort) -> Vec<String> {
+    collection
+        .notes_sorted(by)
+        .into_iter()
+        .map(|(_, note)| note.get_title().to_string())
+        .collect()
+}
+
+#[test]
+fn notes_sorted_by_title() {
+    let collection = build_sort_collection();
+    assert_eq!(
+        sorted_titles(&collection, NoteSort::TitleAsc),
+        vec!["Apple", "banana", "cherry"]
+    );
+}
+
+#[test]
+fn notes_sorted_by_modified() {
+    let collection = build_sort_collection();
+    assert_eq!(
+        sorted_titles(&collection, NoteSort::ModifiedDesc),
+        vec!["cherry", "banana", "Apple"]
+    );
+}
+
+#[test]
+fn notes_sorted_by_created() {
+    let collection = build_sort_collection();
+    assert_eq!(
+        sorted_titles(&collection, NoteSort::CreatedDesc),
+        vec!["Apple", "cherry", "banana"]
+    );
+}
+
+#[test]
+fn deleted_notes_sorted() {
+    let mut collection = build_sort_collection();
+    let cherry_id = Uuid::parse_str("00000000-0000-0000-0000-000000000003").expect("valid id");
+    let apple_id = Uuid::parse_str("00000000-0000-0000-0000-000000000002").expect("valid id");
+    collection.delete_note(apple_id);
+    collection.delete_note(cherry_id);
+    let deleted: Vec<Uuid> = collection
+        .deleted_notes_sorted(NoteSort::TitleAsc)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(deleted, vec![apple_id, cherry_id]);
+}
+
+#[test]
+fn validate_style_name() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let other_id = collection.new_style("Blue".to_string(), None);
+    let third_id = collection.new_style("Blue 2".to_string(), None);
+
+    // the name is trimmed
+    assert_eq!(
+        collection.validate_style_name(&style_id, "  Green ", "Style"),
+        "Green"
+    );
+    // the blank name is generated
+    assert_eq!(
+        collection.validate_style_name(&style_id, "   ", "Style"),
+        "Style-3"
+    );
+    // the style keeps its own name
+    assert_eq!(
+        collection.validate_style_name(&other_id, "Blue", "Style"),
+        "Blue"
+    );
+    // the duplicate gets the first free suffix
+    assert_eq!(
+        collection.validate_style_name(&style_id, "Blue", "Style"),
+        "Blue 3"
+    );
+    assert_eq!(
+        collection.validate_style_name(&third_id, " Blue ", "Style"),
+        "Blue 2"
+    );
+}
+
+#[test]
+fn sanitize_geometry() {
+    let mut collection = NotesCollection::default();
+    let tiny_id = collection.new_note();
+    let huge_id = collection.new_note();
+    let fine_id = collection.new_note();
+    if let Ok(note) = collection.try_get_note_mut(&tiny_id) {
+        note.set_size(1, 1);
+    }
+    if let Ok(note) = collection.try_get_note_mut(&huge_id) {
+        note.set_size(30000, 30000);
+        note.set_position(100_000, 50);
+    }
+    if let Ok(note) = collection.try_get_note_mut(&fine_id) {
+        note.set_size(300, 200);
+        note.set_position(10, 20);
+    }
+
+    assert_eq!(collection.sanitize((64, 64), (4096, 4096)), 2);
+
+    let geometry = |id: &Uuid| {
+        let note = collection.try_get_note(id).expect("note must exist");
+        (note.left(), note.top(), note.width(), note.height())
+    };
+    let (_, _, width, height) = geometry(&tiny_id);
+    assert_eq!((width, height), (64, 64));
+    assert_eq!(geometry(&huge_id), (MAX_NOTE_POSITION, 50, 4096, 4096));
+    assert_eq!(geometry(&fine_id), (10, 20, 300, 200));
+    // nothing to correct the second time
+    assert_eq!(collection.sanitize((64, 64), (4096, 4096)), 0);
+}
+
+#[test]
+fn validate_json() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let good_note = collection
+        .add_note_with_style("good".to_string(), style_id)
+        .expect("add note must succeed");
+    let json = collection
+        .try_write()
+        .expect("serialize notes must succeed");
+
+    let report = NotesCollection::validate_json(&json).expect("valid notes must be read");
+    assert_eq!(
+        report,
+        ValidationReport {
+            notes_count: 1,
+            styles_count: 1,
+            ..ValidationReport::default()
+        }
+    );
+    assert!(!report.has_issues());
+
+    // the style of the note and the default style refer nothing
+    let missing_style = Uuid::new_v4();
+    let mut value: serde_json::Value =
+        serde_json::from_str(&json).expect("parse json must succeed");
+    value["notes"][good_note.to_string()]["style_id"] =
+        serde_json::Value::String(missing_style.to_string());
+    value["default_style"] = serde_json::Value::String(Uuid::new_v4().to_string());
+
+    let report =
+        NotesCollection::validate_json(&value.to_string()).expect("broken notes must be read");
+    assert!(report.has_issues());
+    assert!(report.missing_default_style);
+    assert_eq!(report.unknown_styles, vec![(good_note, missing_style)]);
+
+    assert!(matches!(
+        NotesCollection::validate_json("not a json"),
+        Err(crate::Error::Collection(NotesCollectionError::Json(_)))
+    ));
+}
+
+#[test]
+fn bulk_actions() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let other_style = collection.new_style("Blue".to_string(), None);
+    let ids: Vec<Uuid> = (0..3)
+        .map(|i| {
+            collection
+                .add_note_with_style(format!("note {i}"), style_id)
+                .expect("add note must succeed")
+        })
+        .collect();
+    let subset = &ids[..2];
+    let untouched = ids[2];
+    let note = |collection: &NotesCollection, id: &Uuid| {
+        collection
+            .try_get_note(id)
+            .expect("note must exist")
+            .clone()
+    };
+
+    collection.commit_changes();
+    collection.bulk_set_locking(subset, true);
+    assert!(collection.is_unsaved());
+    assert!(subset.iter().all(|id| note(&collection, id).is_locked()));
+    assert!(!note(&collection, &untouched).is_locked());
+
+    collection
+        .bulk_set_style(subset, other_style)
+        .expect("set existing style must succeed");
+    assert!(
+        subset
+            .iter()
+            .all(|id| note(&collection, id).style() == other_style)
+    );
+    assert_eq!(note(&collection, &untouched).style(), style_id);
+    let missing_style = Uuid::new_v4();
+    assert!(matches!(
+        collection.bulk_set_style(subset, missing_style),
+        Err(NotesCollectionError::StyleNotFound(id)) if id == missing_style
+    ));
+
+    collection.bulk_delete(subset);
+    assert_eq!(collection.get_notes_count(), 1);
+    assert!(collection.try_get_note(&untouched).is_ok());
+    // deleted notes are restorable
+    for id in subset {
+        collection
+            .try_restore_deleted_note(*id)
+            .expect("deleted note must be restored");
+    }
+    assert_eq!(collection.get_notes_count(), 3);
+}
+
+#[test]
+fn rename_style_and_default_color() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    collection.commit_changes();
+
+    // the same name changes nothing
+    collection
+        .rename_style(&style_id, "Yellow")
+        .expect("rename existing style must succeed");
+    assert!(!collection.is_unsaved());
+    collection
+        .rename_style(&style_id, "Sunny")
+        .expect("rename existing style must succeed");
+    assert!(collection.is_unsaved());
+    assert_eq!(
+        collection
+            .try_get_style(&style_id)
+            .expect("style must exist")
+            .get_name(),
+        "Sunny"
+    );
+
+    let missing_style = Uuid::new_v4();
+    assert!(matches!(
+        collection.rename_style(&missing_style, "Missing"),
+        Err(NotesCollectionError::StyleNotFound(id)) if id == missing_style
+    ));
+
+    collection.commit_changes();
+    collection
+        .set_default_style_color(Color::WHITE)
+        .expect("default style must exist");
+    assert!(!collection.is_unsaved());
+    collection
+        .set_default_style_color(Color::BLACK)
+        .expect("default style must exist");
+    assert!(collection.is_unsaved());
+    assert_eq!(
+        collection
+            .try_get_default_style()
+            .expect("default style must exist")
+            .get_background_color(),
+        Color::BLACK
+    );
+}
+
+#[test]
+fn templates_crud() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let other_style = collection.new_style("Blue".to_string(), None);
+    collection.commit_changes();
+
+    let index = collection.add_template(NoteTemplate::new(
+        "Meeting".to_string(),
+        "Meeting {date}".to_string(),
+        other_style,
+    ));
+    assert_eq!(index, 0);
+    assert!(collection.is_unsaved());
+    collection.commit_changes();
+
+    collection
+        .try_get_template_mut(index)
+        .expect("template must exist")
+        .set_name("Standup");
+    assert!(collection.is_unsaved());
+    assert_eq!(
+        collection
+            .try_get_template(index)
+            .expect("template must exist")
+            .get_name(),
+        "Standup"
+    );
+
+    let now = Local::now();
+    let note_id = collection
+        .new_note_from_template(index, &now)
+        .expect("new note from template must succeed");
+    let note = collection.try_get_note(&note_id).expect("note must exist");
+    assert_eq!(
+        note.get_content(),
+        format!("Meeting {}", now.format("%Y-%m-%d"))
+    );
+    assert_eq!(note.style(), other_style);
+
+    // the template of the deleted style gets the default one
+    collection
+        .delete_style(other_style)
+        .expect("delete style must succeed");
+    assert_eq!(collection.templates()[index].style(), style_id);
+
+    assert!(matches!(
+        collection.new_note_from_template(1, &now),
+        Err(NotesCollectionError::TemplateIndexNotFound(1))
+    ));
+    collection
+        .delete_template(index)
+        .expect("delete template must succeed");
+    assert!(collection.templates().is_empty());
+    assert!(matches!(
+        collection.delete_template(index),
+        Err(NotesCollectionError::TemplateIndexNotFound(0))
+    ));
+}
+
+#[test]
+fn gc_unreferenced_styles() {
+    let (mut collection, used_style) =
+        NotesCollection::with_style("Used".to_string(), Font::default(), Color::WHITE);
+    let default_style = collection.new_style("Default".to_string(), None);
+    let unused_style = collection.new_style("Unused".to_string(), None);
+    let deleted_note_style = collection.new_style("Deleted note".to_string(), None);
+    let template_style = collection.new_style("Template".to_string(), None);
+    let default_index = collection
+        .iter_styles()
+        .position(|(style_id, _)| *style_id == default_style)
+        .expect("style must exist");
+    collection
+        .try_set_default_style_by_index(default_index)
+        .expect("default style must be set");
+
+    collection
+        .add_note_with_style("used".to_string(), used_style)
+        .expect("style must exist");
+    let deleted_note = collection.new_note();
+    collection
+        .try_get_note_mut(&deleted_note)
+        .expect("note must exist")
+        .set_style(deleted_note_style);
+    collection.delete_note(deleted_note);
+    collection.add_template(NoteTemplate::new(
+        "Template".to_string(),
+        String::new(),
+        template_style,
+    ));
+    collection.commit_changes();
+
+    assert_eq!(collection.unreferenced_styles(None), vec![unused_style]);
+    // the style being edited is kept
+    assert!(
+        collection
+            .gc_unreferenced_styles(Some(unused_style))
+            .is_empty()
+    );
+    assert_eq!(collection.gc_unreferenced_styles(None), vec![unused_style]);
+    assert!(collection.is_unsaved());
+    assert_eq!(collection.get_styles_count(), 4);
+    assert!(collection.try_get_style(&unused_style).is_err());
+    assert!(collection.try_get_style(&default_style).is_ok());
+
+    // nothing is left to collect
+    collection.commit_changes();
+    assert!(collection.gc_unreferenced_styles(None).is_empty());
+    assert!(!collection.is_unsaved());
+}
+
+#[test]
+fn discard_new_style_before_confirm() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let note_id = collection
+        .add_note_with_style("note".to_string(), style_id)
+        .expect("style must exist");
+    collection.commit_changes();
+
+    // the new style is picked up while its dialog is still open
+    let new_style = collection.new_draft_style("new-style-1".to_string(), None);
+    let new_style_index = collection
+        .iter_styles()
+        .position(|(id, _)| *id == new_style)
+        .expect("style must exist");
+    collection
+        .try_set_default_style_by_index(new_style_index)
+        .expect("default style must be set");
+    collection
+        .try_set_note_style_by_index(note_id, new_style_index)
+        .expect("note style must be set");
+    collection.add_template(NoteTemplate::new(
+        "Template".to_string(),
+        String::new(),
+        new_style,
+    ));
+
+    // the dialog window is closed without Ok
+    assert!(collection.discard_draft_style(new_style));
+    assert!(!collection.validate().has_issues());
+    assert_eq!(
+        collection
+            .try_get_default_style_index()
+            .expect("default style must exist"),
+        0
+    );
+    assert_eq!(
+        collection
+            .try_get_note(&note_id)
+            .expect("note must exist")
+            .style(),
+        style_id
+    );
+    assert_eq!(collection.templates()[0].style(), style_id);
+}
+
+#[test]
+fn draft_style_is_not_written() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let note_id = collection
+        .add_note_with_style("note".to_string(), style_id)
+        .expect("style must exist");
+    let draft_style = collection.new_draft_style("new-style-1".to_string(), None);
+    let draft_index = collection
+        .iter_styles()
+        .position(|(id, _)| *id == draft_style)
+        .expect("style must exist");
+    collection
+        .try_set_note_style_by_index(note_id, draft_index)
+        .expect("note style must be set");
+
+    // the app exits while the style is not confirmed yet
+    let json = collection
+        .try_write()
+        .expect("serialize notes must succeed");
+    let written = NotesCollection::try_read(&json).expect("deserialize notes must succeed");
+    assert!(written.try_get_style(&draft_style).is_err());
+    assert!(!written.validate().has_issues());
+    assert_eq!(
+        written
+            .try_get_note(&note_id)
+            .expect("note must exist")
+            .style(),
+        style_id
+    );
+    // the draft is kept in memory meanwhile
+    assert!(collection.try_get_style(&draft_style).is_ok());
+
+    collection.commit_changes();
+    collection.confirm_style(&draft_style);
+    assert!(collection.is_unsaved());
+    let json = collection
+        .try_write()
+        .expect("serialize notes must succeed");
+    let written = NotesCollection::try_read(&json).expect("deserialize notes must succeed");
+    assert!(written.try_get_style(&draft_style).is_ok());
+    assert_eq!(
+        written
+            .try_get_note(&note_id)
+            .expect("note must exist")
+            .style(),
+        draft_style
+    );
+}
+
+#[test]
+fn prune_empty_notes() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let text_note = collection
+        .add_note_with_style("text".to_string(), style_id)
+        .expect("style must exist");
+    let blank_note = collection.new_note();
+    let attachment_note = collection.new_note();
+    collection
+        .try_get_note_mut(&attachment_note)
+        .expect("note must exist")
+        .add_attachment("/home/user/picture.png".to_string());
+    collection.commit_changes();
+
+    assert_eq!(collection.prune_empty_notes(), 1);
+    assert!(collection.is_unsaved());
+    assert!(collection.try_get_note(&blank_note).is_err());
+    assert!(collection.try_get_note(&text_note).is_ok());
+    assert!(collection.try_get_note(&attachment_note).is_ok());
+
+    collection.commit_changes();
+    assert_eq!(collection.prune_empty_notes(), 0);
+    assert!(!collection.is_unsaved());
+}
+
+#[cfg(test)]
+fn build_collections_with_same_named_styles() -> (NotesCollection, NotesCollection, Uuid, Uuid) {
+    let (mine, my_style) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let (mut theirs, their_style) =
+        NotesCollection::with_style("YELLOW".to_string(), Font::default(), Color::WHITE);
+    let their_note = theirs
+        .add_note_with_style("theirs".to_string(), their_style)
+        .expect("style must exist");
+    (mine, theirs, my_style, their_note)
+}
+
+#[test]
+fn merge_styles_by_name() {
+    let (mut mine, theirs, my_style, their_note) = build_collections_with_same_named_styles();
+    mine.merge(theirs, MergeStrategy::KeepMine, StyleMatch::ByName);
+    assert_eq!(mine.get_styles_count(), 1);
+    assert_eq!(
+        mine.try_get_note(&their_note)
+            .map(NoteData::style)
+            .expect("merged note must exist"),
+        my_style
+    );
+
+    // the same name of another color is a different style
+    let (mut mine, mut theirs, my_style, their_note) = build_collections_with_same_named_styles();
+    theirs
+        .set_default_style_color(Color::BLACK)
+        .expect("default style must exist");
+    mine.merge(theirs, MergeStrategy::KeepMine, StyleMatch::ByName);
+    assert_eq!(mine.get_styles_count(), 2);
+    let their_style = mine
+        .try_get_note(&their_note)
+        .map(NoteData::style)
+        .expect("merged note must exist");
+    assert_ne!(their_style, my_style);
+    assert!(mine.try_get_style(&their_style).is_ok());
+
+    // styles are matched by uuid only unless asked
+    let (mut mine, theirs, _, _) = build_collections_with_same_named_styles();
+    mine.merge(theirs, MergeStrategy::KeepMine, StyleMatch::ById);
+    assert_eq!(mine.get_styles_count(), 2);
+}
+
+#[cfg(test)]
+fn read_parts(parts: &CollectionParts, storage: &mut HashMap<String, String>) -> NotesCollection {
+    storage.extend(parts.changed.iter().cloned());
+    for key in &parts.removed {
+        storage.insert(key.clone(), String::new());
+    }
+    NotesCollection::try_read_parts(&parts.index, |key| storage.get(key).cloned())
+        .expect("read stored notes must succeed")
+}
+
+#[test]
+fn write_changed_parts_only() {
+    let mut collection = NotesCollection::default();
+    let first_note = collection.new_note();
+    let second_note = collection.new_note();
+    let mut storage = HashMap::new();
+
+    // everything is written first
+    let parts = collection
+        .try_write_parts()
+        .expect("write parts must succeed");
+    assert_eq!(parts.changed.len(), collection.get_notes_count());
+    assert!(parts.removed.is_empty());
+    let stored = read_parts(&parts, &mut storage);
+    collection.commit_changes();
+    assert_eq!(stored.get_notes_count(), collection.get_notes_count());
+
+    // the changed and removed notes only
+    collection
+        .try_get_note_mut(&first_note)
+        .expect("note must exist")
+        .set_content("changed".to_string());
+    collection.delete_note(second_note);
+    let parts = collection
+        .try_write_parts()
+        .expect("write parts must succeed");
+    assert_eq!(
+        parts
+            .changed
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>(),
+        vec![NotesCollection::note_key(&first_note)]
+    );
+    assert_eq!(parts.removed, vec![NotesCollection::note_key(&second_note)]);
+    let stored = read_parts(&parts, &mut storage);
+    collection.commit_changes();
+    assert_eq!(stored.get_notes_count(), collection.get_notes_count());
+    assert_eq!(
+        stored
+            .try_get_note(&first_note)
+            .map(NoteData::get_content)
+            .ok(),
+        Some("changed")
+    );
+    assert!(stored.try_get_note(&second_note).is_err());
+
+    // nothing is changed
+    let parts = collection
+        .try_write_parts()
+        .expect("write parts must succeed");
+    assert!(parts.changed.is_empty());
+    assert!(parts.removed.is_empty());
+
+    // the replaced collection removes all of the stored notes
+    collection.replace(NotesCollection::default());
+    let parts = collection
+        .try_write_parts()
+        .expect("write parts must succeed");
+    assert_eq!(parts.changed.len(), 1);
+    assert_eq!(parts.removed.len(), stored.get_notes_count());
+}
+
+#[test]
+fn write_focus_only_change() {
+    let mut collection = NotesCollection::default();
+    let note_id = collection.new_note();
+    let mut storage = HashMap::new();
+    let parts = collection
+        .try_write_parts()
+        .expect("write parts must succeed");
+    read_parts(&parts, &mut storage);
+    collection.commit_changes();
+
+    // focusing doesn't make the collection unsaved, but the note is stored anyway
+    let focused = Utc::now();
+    collection
+        .try_get_note_mut(&note_id)
+        .expect("note must exist")
+        .set_last_focused(focused);
+    assert!(!collection.is_unsaved());
+    assert!(collection.needs_storing());
+    let parts = collection
+        .try_write_parts()
+        .expect("write parts must succeed");
+    assert_eq!(parts.changed.len(), 1);
+    let stored = read_parts(&parts, &mut storage);
+    collection.commit_changes();
+    assert!(!collection.needs_storing());
+    assert_eq!(
+        stored
+            .try_get_note(&note_id)
+            .expect("note must exist")
+            .get_last_focused(),
+        Some(focused)
+    );
+}
+
+#[test]
+fn read_parts_of_single_blob() {
+    let mut collection = NotesCollection::default();
+    let note_id = collection.new_note();
+    let json = collection
+        .try_write()
+        .expect("serialize notes must succeed");
+
+    // the notes saved as a whole by earlier versions are migrated on the next save
+    let mut migrated =
+        NotesCollection::try_read_parts(&json, |_| None).expect("read legacy notes must succeed");
+    assert_eq!(migrated.get_notes_count(), collection.get_notes_count());
+    assert!(migrated.try_get_note(&note_id).is_ok());
+    let parts = migrated
+        .try_write_parts()
+        .expect("write parts must succeed");
+    assert_eq!(parts.changed.len(), collection.get_notes_count());
+    let stored = read_parts(&parts, &mut HashMap::new());
+    migrated.commit_changes();
+    assert_eq!(stored.get_notes_count(), collection.get_notes_count());
+    assert!(stored.try_get_note(&note_id).is_ok());
+}
+
+#[test]
+fn change_log() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    let note_id = collection
+        .add_note_with_style("note".to_string(), style_id)
+        .expect("style must exist");
+    let new_note_id = collection.new_note();
+    collection
+        .try_get_note_mut(&note_id)
+        .expect("note must exist")
+        .set_position(10, 10);
+    collection
+        .try_get_note_mut(&note_id)
+        .expect("note must exist")
+        .set_position(20, 20);
+    collection.delete_note(new_note_id);
+    let new_style_id = collection.new_style("Green".to_string(), None);
+    // the notes handed out to be changed are logged once the log is taken
+    assert_eq!(
+        collection.take_change_log(),
+        vec![
+            ChangeEvent::NoteAdded(note_id),
+            ChangeEvent::NoteAdded(new_note_id),
+            ChangeEvent::NoteDeleted(new_note_id),
+            ChangeEvent::StyleChanged(new_style_id),
+            ChangeEvent::NoteModified(note_id),
+        ]
+    );
+    // handing out the unchanged note or style is not a change
+    collection
+        .try_get_note_mut(&note_id)
+        .expect("note must exist")
+        .set_position(20, 20);
+    collection
+        .try_get_style_mut(&style_id)
+        .expect("style must exist");
+    collection.for_each_note_mut(|note| note.set_visibility(true));
+    assert!(collection.take_change_log().is_empty());
+    // the log is taken, while the changes are still unsaved
+    assert!(collection.take_change_log().is_empty());
+    assert!(collection.is_unsaved());
+
+    collection.commit_changes();
+    assert!(collection.take_change_log().is_empty());
+    collection
+        .try_restore_deleted_note(new_note_id)
+        .expect("note must be restored");
+    assert_eq!(collection.reassign_notes_style(style_id, new_style_id), 2);
+    let change_log = collection.take_change_log();
+    assert_eq!(change_log.len(), 3);
+    assert_eq!(change_log[0], ChangeEvent::NoteAdded(new_note_id));
+    // reassigned notes are logged in arbitrary order
+    assert!(change_log.contains(&ChangeEvent::NoteModified(note_id)));
+    assert!(change_log.contains(&ChangeEvent::NoteModified(new_note_id)));
+}
+
+#[test]
+fn duplicate_style() {
+    let (mut collection, style_id) = NotesCollection::with_style(
+        "Yellow".to_string(),
+        Font::default(),
+        Color::from_rgb(1.0, 1.0, 0.0),
+    );
+    collection
+        .try_get_style_mut(&style_id)
+        .expect("style must exist")
+        .set_text_align(super::TextAlign::Center);
+    let copy_id = collection
+        .duplicate_style(&style_id, |name| format!("{name} (copy)"))
+        .expect("style must be duplicated");
+    let copy = collection.try_get_style(&copy_id).expect("copy must exist");
+    assert_eq!(copy.get_name(), "Yellow (copy)");
+    assert_eq!(copy.get_background_color(), Color::from_rgb(1.0, 1.0, 0.0));
+    assert_eq!(copy.get_text_align(), super::TextAlign::Center);
+    // the copy is a draft until confirmed in the dialog
+    let json = collection
+        .try_write()
+        .expect("serialize notes must succeed");
+    let written = NotesCollection::try_read(&json).expect("deserialize notes must succeed");
+    assert!(written.try_get_style(&copy_id).is_err());
+
+    let missing = Uuid::new_v4();
+    assert!(matches!(
+        collection.duplicate_style(&missing, str::to_string),
+        Err(NotesCollectionError::StyleNotFound(id)) if id == missing
+    ));
+}
+
+#[test]
+fn new_style_from_source() {
+    let (mut collection, default_style) =
+        NotesCollection::with_style("Default".to_string(), Font::default(), Color::WHITE);
+    let source = collection.new_style("Blue".to_string(), None);
+    collection
+        .try_get_style_mut(&source)
+        .expect("style must exist")
+        .set_background_color(Color::from_rgb(0.0, 0.0, 1.0));
+
+    let seeded = collection.new_style("Seeded".to_string(), Some(source));
+    assert_eq!(
+        collection
+            .try_get_style(&seeded)
+            .expect("style must exist")
+            .get_background_color(),
+        Color::from_rgb(0.0, 0.0, 1.0)
+    );
+    // the default style is the source otherwise
+    for source in [None, Some(Uuid::new_v4())] {
+        let style_id = collection.new_style("Plain".to_string(), source);
+        assert_eq!(
+            collection
+                .try_get_style(&style_id)
+                .expect("style must exist")
+                .get_background_color(),
+            collection
+                .try_get_style(&default_style)
+                .expect("style must exist")
+                .get_background_color()
+        );
+    }
+}
+
+#[test]
+fn notes_with_deleted_style() {
+    let (mut collection, style_id) = NotesCollection::with_style(
+        "Yellow".to_string(),
+        Font::default(),
+        Color::from_rgb(1.0, 1.0, 0.0),
+    );
+    let other_style = collection.new_style("Blue".to_string(), None);
+    let kept_id = collection
+        .add_note_with_style("kept".to_string(), style_id)
+        .expect("add note must succeed");
+    let deleted_id = collection
+        .add_note_with_style("deleted".to_string(), other_style)
+        .expect("add note must succeed");
+    assert!(
+        collection
+            .iter_notes_with_style()
+            .any(|(note_id, _, style)| note_id == deleted_id && style.get_name() == "Blue")
+    );
+    collection.delete_note(deleted_id);
+    collection
+        .delete_style(other_style)
+        .expect("delete style must succeed");
+
+    // the deleted note still refers the deleted style, so it is displayed with the default one
+    let deleted = collection.deleted_notes_with_style_sorted(NoteSort::TitleAsc);
+    assert_eq!(deleted.len(), 1);
+    let (note_id, note, style) = deleted[0];
+    assert_eq!(note_id, deleted_id);
+    assert_eq!(note.style(), other_style);
+    assert_eq!(style.get_name(), "Yellow");
+
+    let live: Vec<_> = collection.iter_notes_with_style().collect();
+    assert_eq!(live.len(), 1);
+    assert_eq!(live[0].0, kept_id);
+    assert_eq!(live[0].2.get_name(), "Yellow");
+    let (_, style) = collection
+        .try_get_note_with_style(&kept_id)
+        .expect("note must exist");
+    assert_eq!(style.get_name(), "Yellow");
+    assert!(matches!(
+        collection.try_get_note_with_style(&deleted_id),
+        Err(NotesCollectionError::NoteNotFound(_))
+    ));
+}
+
+#[test]
+fn hide_oldest_visible() {
+    let mut collection = build_sort_collection();
+    let banana_id = Uuid::parse_str("00000000-0000-0000-0000-000000000001").expect("valid id");
+    let apple_id = Uuid::parse_str("00000000-0000-0000-0000-000000000002").expect("valid id");
+    let cherry_id = Uuid::parse_str("00000000-0000-0000-0000-000000000003").expect("valid id");
+    assert!(collection.hide_oldest_visible(3, &[]).is_empty());
+
+    // apple is the oldest one but kept, so banana is hidden instead
+    let hidden = collection.hide_oldest_visible(2, &[apple_id]);
+    assert_eq!(hidden, vec![banana_id]);
+    let banana = collection
+        .try_get_note(&banana_id)
+        .expect("note must exist");
+    assert!(!banana.is_visible());
+    assert_eq!(collection.get_notes_count(), 3);
+
+    // hidden notes are not counted
+    let new_id = collection.new_note();
+    let hidden = collection.hide_oldest_visible(1, &[new_id]);
+    assert_eq!(hidden, vec![apple_id, cherry_id]);
+    assert_eq!(collection.iter_visible_notes().count(), 1);
+    assert!(collection.has_visible_notes());
+}
+
+#[test]
+fn export_import_single() {
+    let (mut collection, style_id) = NotesCollection::with_style(
+        "Yellow".to_string(),
+        Font::default(),
+        Color::from_rgb(1.0, 1.0, 0.0),
+    );
+    let note_id = collection
+        .add_note_with_style("shared text".to_string(), style_id)
+        .expect("add note must succeed");
+    let note = collection
+        .try_get_note_mut(&note_id)
+        .expect("note must exist");
+    note.add_attachment("/home/user/private.txt".to_string());
+    note.set_reminder(Utc::now());
+    note.set_locking(true);
+    note.set_click_through(true);
+    let json = collection
+        .export_single(&note_id)
+        .expect("export note must succeed");
+    assert!(!json.contains("private.txt"));
+
+    // the same named style is reused
+    let copy_id = collection
+        .import_single(&json)
+        .expect("import note must succeed");
+    assert_ne!(copy_id, note_id);
+    assert_eq!(collection.get_styles_count(), 1);
+    let copy = collection.try_get_note(&copy_id).expect("note must exist");
+    assert_eq!(copy.get_content(), "shared text");
+    assert_eq!(copy.style(), style_id);
+    assert!(copy.get_attachments().is_empty());
+    assert!(copy.get_reminder().is_none());
+    assert!(!copy.is_locked());
+    assert!(!copy.is_click_through());
+    assert!(copy.is_visible());
+
+    // the unknown style is added
+    let (mut other, other_style_id) = NotesCollection::with_style(
+        "Blue".to_string(),
+        Font::default(),
+        Color::from_rgb(0.0, 0.0, 1.0),
+    );
+    let imported_id = other
+        .import_single(&json)
+        .expect("import note must succeed");
+    assert_eq!(other.get_styles_count(), 2);
+    let (imported, style) = other
+        .try_get_note_with_style(&imported_id)
+        .expect("note must exist");
+    assert_eq!(imported.get_content(), "shared text");
+    assert_ne!(imported.style(), other_style_id);
+    assert_eq!(style.get_name(), "Yellow");
+    assert!(other.is_unsaved());
+
+    assert!(matches!(
+        other.import_single("not a note"),
+        Err(crate::Error::Collection(NotesCollectionError::Json(_)))
+    ));
+    assert!(matches!(
+        other.export_single(&Uuid::new_v4()),
+        Err(crate::Error::Collection(
+            NotesCollectionError::NoteNotFound(_)
+        ))
+    ));
+}
+
+#[test]
+fn notes_with_style_missing_default() {
+    let mut collection = NotesCollection::default();
+    let note_id = collection.new_note();
+    // the collection read from a broken file, before it is repaired
+    collection.styles.clear();
+    // the default collection has a note already
+    assert_eq!(collection.iter_notes_with_style().count(), 2);
+    let (_, _, style) = collection
+        .iter_notes_with_style()
+        .find(|(id, _, _)| *id == note_id)
+        .expect("note must be listed");
+    assert!(style.looks_like(&NoteStyle::default()));
+    assert_eq!(
+        collection.notes_with_style_sorted(NoteSort::TitleAsc).len(),
+        2
+    );
+}
+
+#[test]
+fn discard_unused_draft_style() {
+    let (mut collection, style_id) =
+        NotesCollection::with_style("Yellow".to_string(), Font::default(), Color::WHITE);
+    collection.commit_changes();
+    let new_style = collection.new_draft_style("new-style-1".to_string(), None);
+    assert!(collection.discard_draft_style(new_style));
+    assert!(collection.try_get_style(&new_style).is_err());
+    assert!(!collection.is_unsaved());
+    // the confirmed style is not a draft any more
+    let new_style = collection.new_draft_style("new-style-2".to_string(), None);
+    collection.confirm_style(&new_style);
+    assert!(!collection.discard_draft_style(new_style));
+    assert!(!collection.discard_draft_style(style_id));
+    assert!(collection.try_get_style(&new_style).is_ok());
+}
+
+#[test]
+fn move_notes_onscreen() {
+    use cosmic::iced::Size;
+
+    let mut collection = NotesCollection::default();
+    let inside_id = collection.new_note();
+    let offscreen_id = collection.new_note();
+    collection
+        .try_get_note_mut(&inside_id)
+        .expect("note must exist")
+        .set_position(100, 100);
+    let offscreen = collection
+        .try_get_note_mut(&offscreen_id)
+        .expect("note must exist");
+    offscreen.set_position(3000, 1500);
+    offscreen.set_size(200, 150);
+    // nothing is known about the screens
+    assert!(collection.move_notes_onscreen(&[]).is_empty());
+
+    let screen = Rectangle::new(Point::ORIGIN, Size::new(1920.0, 1080.0));
+    let moved = collection.move_notes_onscreen(&[screen]);
+    assert_eq!(moved, vec![(offscreen_id, (1720, 930))]);
+    assert!(collection.notes_offscreen(&[screen]).is_empty());
+    let offscreen = collection
+        .try_get_note(&offscreen_id)
+        .expect("note must exist");
+    assert_eq!((offscreen.left(), offscreen.top()), (1720, 930));
+    let inside = collection
+        .try_get_note(&inside_id)
+        .expect("note must exist");
+    assert_eq!((inside.left(), inside.top()), (100, 100));
+    // the moved note is in the second screen already
+    let second = Rectangle::new(Point::new(1920.0, 0.0), Size::new(1280.0, 1024.0));
+    collection
+        .try_get_note_mut(&offscreen_id)
+        .expect("note must exist")
+        .set_position(2000, 500);
+    assert!(collection.move_notes_onscreen(&[screen, second]).is_empty());
+}
@@ -0,0 +1,41 @@
+use super::{
+    NotesCollectionError, import_keep::KeepImportError,
+    indicator_stickynotes::IndicatorStickyNotesError,
+};
+use thiserror::Error;
+
+/// The error of any operation of the notes library, the specific error is available as `source()`
+#[derive(Debug, Error)]
+pub enum Error {
+    // Failed operation with the collection of notes
+    #[error("{0}")]
+    Collection(#[from] NotesCollectionError),
+    // Failed reading or writing indicator-stickynotes data file
+    #[error("{0}")]
+    IndicatorStickyNotes(#[from] IndicatorStickyNotesError),
+    // Failed reading Google Keep Takeout
+    #[error("{0}")]
+    KeepImport(#[from] KeepImportError),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+use std::error::Error as _;
+
+#[test]
+fn specific_error_is_source() {
+    let error = super::NotesCollection::try_read("not a json").expect_err("reading must fail");
+    let source = error
+        .source()
+        .and_then(|source| source.downcast_ref::<NotesCollectionError>())
+        .expect("collection error must be the source");
+    assert!(matches!(source, NotesCollectionError::Json(_)));
+    // the cause of the collection error is available as well
+    assert!(
+        source
+            .source()
+            .and_then(|cause| cause.downcast_ref::<serde_json::Error>())
+            .is_some()
+    );
+}
@@ -0,0 +1,114 @@
+use super::{NoteData, NotesCollection};
+use uuid::Uuid;
+
+const MODIFIED_FORMAT: &str = "%Y-%m-%d %H:%M";
+const PLAIN_TEXT_SEPARATOR: &str = "----------";
+
+const INDICATOR_STICKYNOTES: &str = "INDICATOR_STICKYNOTES";
+const NATIVE_JSON: &str = "NATIVE_JSON";
+const MARKDOWN: &str = "MARKDOWN";
+const PLAIN_TEXT: &str = "PLAIN_TEXT";
+
+/// The file formats the notes collection can be exported to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The data file of indicator-stickynotes, the only format which can be imported back
+    #[default]
+    IndicatorStickyNotes,
+    /// The same JSON as the notes are saved in
+    NativeJson,
+    Markdown,
+    PlainText,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::IndicatorStickyNotes,
+        ExportFormat::NativeJson,
+        ExportFormat::Markdown,
+        ExportFormat::PlainText,
+    ];
+
+    /// The usual extension of the exported file
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::IndicatorStickyNotes | ExportFormat::NativeJson => "json",
+            ExportFormat::Markdown => "md",
+            ExportFormat::PlainText => "txt",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ExportFormat::IndicatorStickyNotes => INDICATOR_STICKYNOTES,
+                ExportFormat::NativeJson => NATIVE_JSON,
+                ExportFormat::Markdown => MARKDOWN,
+                ExportFormat::PlainText => PLAIN_TEXT,
+            }
+        )
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            INDICATOR_STICKYNOTES => Ok(Self::IndicatorStickyNotes),
+            NATIVE_JSON => Ok(Self::NativeJson),
+            MARKDOWN => Ok(Self::Markdown),
+            PLAIN_TEXT => Ok(Self::PlainText),
+            _ => Err(format!("unknown export format {s}")),
+        }
+    }
+}
+
+/// Live notes in order of creation, so the exported text is stable
+fn sorted_notes(notes: &NotesCollection) -> Vec<(&Uuid, &NoteData)> {
+    let mut sorted: Vec<_> = notes.iter_notes().collect();
+    sorted.sort_by_key(|(note_id, note)| (note.get_created(), **note_id));
+    sorted
+}
+
+/// Each note becomes a section titled by the note title
+pub(super) fn to_markdown(notes: &NotesCollection) -> String {
+    sorted_notes(notes)
+        .into_iter()
+        .map(|(note_id, note)| {
+            let style = notes
+                .try_get_note_style(*note_id)
+                .map(|style| style.get_name().to_string())
+                .unwrap_or_default();
+            format!(
+                "## {}\n\n_{style}, {}_\n\n{}\n",
+                note.get_title(),
+                note.get_modified().format(MODIFIED_FORMAT),
+                note.get_content().trim_end()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The content of notes only separated by the line of dashes
+pub(super) fn to_plain_text(notes: &NotesCollection) -> String {
+    sorted_notes(notes)
+        .into_iter()
+        .map(|(_, note)| format!("{}\n", note.get_content().trim_end()))
+        .collect::<Vec<_>>()
+        .join(&format!("{PLAIN_TEXT_SEPARATOR}\n"))
+}
+
+#[test]
+fn parse_and_display_format() {
+    for format in ExportFormat::ALL {
+        assert_eq!(format.to_string().parse::<ExportFormat>(), Ok(format));
+    }
+    assert!("UNKNOWN".parse::<ExportFormat>().is_err());
+}
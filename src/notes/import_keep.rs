@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use cosmic::iced::Color;
+use serde::Deserialize;
+use std::path::Path;
+use thiserror::Error;
+
+const JSON_EXTENSION: &str = "json";
+const CHECKED_ITEM: &str = "☑";
+const UNCHECKED_ITEM: &str = "☐";
+
+#[derive(Debug, Error)]
+pub enum KeepImportError {
+    // Source file or directory does not exist
+    #[error("{0} does not exist")]
+    FileMissing(String),
+    // Failed reading source file
+    #[error("Failed reading file: {0}")]
+    Io(#[from] std::io::Error),
+    // Failed parsing JSON-content
+    #[error("Failed parsing note {0}: {1}")]
+    Parse(String, serde_json::Error),
+}
+
+/// The item of Google Keep checklist
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ListItem {
+    pub text: String,
+    pub is_checked: bool,
+}
+
+/// The note as Google Keep Takeout saves it, one note per JSON file
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Note {
+    pub title: String,
+    pub text_content: String,
+    pub list_content: Vec<ListItem>,
+    pub color: String,
+    pub is_pinned: bool,
+    pub is_archived: bool,
+    pub is_trashed: bool,
+    pub user_edited_timestamp_usec: Option<i64>,
+    pub created_timestamp_usec: Option<i64>,
+}
+
+impl Note {
+    /// Title followed by either the text or the checklist
+    #[must_use]
+    pub fn content(&self) -> String {
+        let body = if self.list_content.is_empty() {
+            self.text_content.clone()
+        } else {
+            self.list_content
+                .iter()
+                .map(|item| {
+                    let mark = if item.is_checked {
+                        CHECKED_ITEM
+                    } else {
+                        UNCHECKED_ITEM
+                    };
+                    format!("{mark} {}", item.text)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        if self.title.is_empty() {
+            body
+        } else if body.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{}\n{body}", self.title)
+        }
+    }
+
+    #[must_use]
+    pub fn modified(&self) -> Option<DateTime<Utc>> {
+        self.user_edited_timestamp_usec
+            .and_then(DateTime::from_timestamp_micros)
+    }
+
+    #[must_use]
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        self.created_timestamp_usec
+            .and_then(DateTime::from_timestamp_micros)
+    }
+}
+
+/// The background of Google Keep note by its color name, unknown names are white
+#[must_use]
+pub fn keep_color(name: &str) -> Color {
+    match name {
+        "RED" => Color::from_rgb8(0xf2, 0x8b, 0x82),
+        "ORANGE" => Color::from_rgb8(0xfb, 0xbc, 0x04),
+        "YELLOW" => Color::from_rgb8(0xff, 0xf4, 0x75),
+        "GREEN" => Color::from_rgb8(0xcc, 0xff, 0x90),
+        "TEAL" => Color::from_rgb8(0xa7, 0xff, 0xeb),
+        "BLUE" => Color::from_rgb8(0xcb, 0xf0, 0xf8),
+        "CERULEAN" | "DARK_BLUE" => Color::from_rgb8(0xae, 0xcb, 0xfa),
+        "PURPLE" => Color::from_rgb8(0xd7, 0xae, 0xfb),
+        "PINK" => Color::from_rgb8(0xfd, 0xcf, 0xe8),
+        "BROWN" => Color::from_rgb8(0xe6, 0xc9, 0xa8),
+        "GRAY" => Color::from_rgb8(0xe8, 0xea, 0xed),
+        _ => Color::WHITE,
+    }
+}
+
+/// Read either the single note file or all note files of the Takeout directory
+pub async fn try_import_keep_notes<P: AsRef<Path> + std::fmt::Debug>(
+    path: P,
+) -> Result<Vec<Note>, KeepImportError> {
+    let metadata = tokio::fs::metadata(&path).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            KeepImportError::FileMissing(path.as_ref().display().to_string())
+        } else {
+            KeepImportError::Io(e)
+        }
+    })?;
+    if metadata.is_dir() {
+        let mut notes = Vec::new();
+        let mut entries = tokio::fs::read_dir(&path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file = entry.path();
+            // Takeout also contains attachments and labels, skip them
+            if file.extension().is_some_and(|ext| ext == JSON_EXTENSION) {
+                notes.push(try_read_keep_note(&file).await?);
+            }
+        }
+        Ok(notes)
+    } else {
+        Ok(vec![try_read_keep_note(path.as_ref()).await?])
+    }
+}
+
+async fn try_read_keep_note(file: &Path) -> Result<Note, KeepImportError> {
+    let content = tokio::fs::read(file).await?;
+    serde_json::from_slice(&content)
+        .map_err(|e| KeepImportError::Parse(file.display().to_string(), e))
+}
+
+#[tokio::test]
+async fn import_keep_takeout() {
+    const INPUT_DIR: &str = "test_data/google-keep";
+
+    let mut notes = try_import_keep_notes(INPUT_DIR)
+        .await
+        .expect("import must succeed");
+    notes.sort_by(|a, b| a.title.cmp(&b.title));
+    assert_eq!(notes.len(), 3);
+
+    let [trashed, ideas, shopping] = notes.as_slice() else {
+        panic!("three notes are expected");
+    };
+    assert!(trashed.is_trashed);
+    assert_eq!(trashed.content(), "Trashed note");
+    assert_eq!(keep_color(&trashed.color), Color::WHITE);
+    assert!(trashed.created().is_none());
+
+    assert!(ideas.is_archived);
+    assert_eq!(
+        ideas.content(),
+        "Ideas\nWrite sticky notes importer\nfor Google Keep"
+    );
+    assert_eq!(
+        ideas.modified(),
+        DateTime::from_timestamp_micros(1_710_000_000_000_000)
+    );
+
+    assert!(shopping.is_pinned);
+    assert_eq!(shopping.content(), "Shopping\n☑ Milk\n☐ Bread");
+    assert_eq!(keep_color(&shopping.color), keep_color("YELLOW"));
+}
+
+#[tokio::test]
+async fn import_keep_missing_file() {
+    const INPUT_FILE: &str = "test_data/no-such-keep-file";
+
+    let result = try_import_keep_notes(INPUT_FILE).await;
+    assert!(matches!(
+        result,
+        Err(KeepImportError::FileMissing(path)) if path == INPUT_FILE
+    ));
+}
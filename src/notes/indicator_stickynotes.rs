@@ -12,6 +12,9 @@ pub const MONOSPACE_FONT_NAME: &str = "Monospace";
 
 #[derive(Debug, Error)]
 pub enum IndicatorStickyNotesError {
+    // Source file does not exist
+    #[error("File {0} does not exist")]
+    FileMissing(String),
     // Failed reading source file
     #[error("Failed reading file: {0}")]
     Io(#[from] std::io::Error),
@@ -32,20 +35,16 @@ pub enum IndicatorStickyNotesError {
 pub async fn try_import_indicator_stickynotes<P: AsRef<Path> + std::fmt::Debug>(
     data_file: P,
 ) -> Result<NotesDatabase, IndicatorStickyNotesError> {
-    let content = tokio::fs::read(data_file).await?;
+    let content = tokio::fs::read(&data_file).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            IndicatorStickyNotesError::FileMissing(data_file.as_ref().display().to_string())
+        } else {
+            IndicatorStickyNotesError::Io(e)
+        }
+    })?;
     NotesDatabase::try_import(&content)
 }
 
-pub async fn try_export_indicator_stickynotes<P: AsRef<Path> + std::fmt::Debug>(
-    data_file: P,
-    data_base: NotesDatabase,
-) -> Result<(), IndicatorStickyNotesError> {
-    let content = data_base.try_export()?;
-    tokio::fs::write(data_file, content)
-        .await
-        .map_err(IndicatorStickyNotesError::Io)
-}
-
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
 pub struct Note {
     pub uuid: Uuid,
@@ -60,14 +59,29 @@ pub struct Note {
 }
 
 const IMPORT_DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+// the local time formats met in exports besides the one above, the fractional seconds are optional
+const IMPORT_DATETIME_FALLBACK_FORMATS: [&str; 2] =
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"];
 
 fn deserialize_from_str<'de, D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: String = Deserialize::deserialize(deserializer)?;
-    let tmp =
-        NaiveDateTime::parse_from_str(&s, IMPORT_DATETIME_FORMAT).map_err(D::Error::custom)?;
+    parse_datetime(&s).map_err(D::Error::custom)
+}
+
+// tries the time having offset (e.g. "Z") first, then the local time in the known formats
+fn parse_datetime(s: &str) -> Result<DateTime<Local>, chrono::ParseError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+        return Ok(time.with_timezone(&Local));
+    }
+    let tmp = NaiveDateTime::parse_from_str(s, IMPORT_DATETIME_FORMAT).or_else(|err| {
+        IMPORT_DATETIME_FALLBACK_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+            .ok_or(err)
+    })?;
     Ok(Local
         .from_local_datetime(&tmp)
         .single()
@@ -114,7 +128,7 @@ impl NotesDatabase {
         self.categories.get(&self.properties.default_cat)
     }
 
-    fn try_import(content: &[u8]) -> Result<Self, IndicatorStickyNotesError> {
+    pub(super) fn try_import(content: &[u8]) -> Result<Self, IndicatorStickyNotesError> {
         serde_json::from_reader(Cursor::new(content)).map_err(IndicatorStickyNotesError::Parse)
     }
 
@@ -124,7 +138,7 @@ impl NotesDatabase {
     /// * preserve notes order
     /// * using local time zone
     /// * (!) but allow to violate categories order
-    fn try_export(&self) -> Result<Vec<u8>, IndicatorStickyNotesError> {
+    pub(super) fn try_export(&self) -> Result<Vec<u8>, IndicatorStickyNotesError> {
         serde_json_fmt::JsonFormat::pretty()
             .ascii(true)
             .indent::<String>(None)
@@ -147,20 +161,38 @@ pub(super) fn parse_font(font_string: &str) -> Font {
         .iter()
         .rev()
         .collect();
-    if let Ok(size) = font_size_string.parse()
-        && size > 0
-    {
-        Font {
+    let size = match font_size_string.parse() {
+        Ok(size) if size > 0 => size,
+        _ => Font::default().size,
+    };
+    // the rest is the font family name
+    let family = font_string[..font_string.len() - font_size_string.len()]
+        .trim_end_matches(|c: char| c.is_whitespace() || c == '-')
+        .trim_start();
+    match family {
+        // the well-known names are displayed with built-in fonts
+        "" | DEFAULT_FONT_NAME => Font {
             style: FontStyle::Default,
             size,
-        }
-    } else {
-        Font::default()
+            family: None,
+        },
+        MONOSPACE_FONT_NAME => Font {
+            style: FontStyle::Monospace,
+            size,
+            family: None,
+        },
+        family => Font {
+            style: FontStyle::Default,
+            size,
+            family: Some(family.to_string()),
+        },
     }
 }
 
 pub(super) fn serialize_font(font: &Font) -> String {
-    if font.style == FontStyle::Monospace {
+    if let Some(family) = &font.family {
+        format!("{family} {}", font.size)
+    } else if font.style == FontStyle::Monospace {
         format!("{MONOSPACE_FONT_NAME} {}", font.size)
     } else {
         format!("{DEFAULT_FONT_NAME} {}", font.size)
@@ -177,7 +209,8 @@ fn parse_font_string() {
     assert_eq!(
         Font {
             style: FontStyle::Default,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         },
         parse_font(&format!("Open Sans {non_default_size}"))
     );
@@ -185,7 +218,8 @@ fn parse_font_string() {
     assert_eq!(
         Font {
             style: FontStyle::Default,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         },
         parse_font(&format!("Open Sans{non_default_size}"))
     );
@@ -193,7 +227,8 @@ fn parse_font_string() {
     assert_eq!(
         Font {
             style: FontStyle::Default,
-            size: DEF_NOTE_FONT_SIZE
+            size: DEF_NOTE_FONT_SIZE,
+            family: None
         },
         parse_font("Open Sans")
     );
@@ -201,7 +236,8 @@ fn parse_font_string() {
     assert_eq!(
         Font {
             style: FontStyle::Default,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         },
         parse_font(&format!("Open Sans -{non_default_size}"))
     );
@@ -209,7 +245,8 @@ fn parse_font_string() {
     assert_eq!(
         Font {
             style: FontStyle::Default,
-            size: DEF_NOTE_FONT_SIZE
+            size: DEF_NOTE_FONT_SIZE,
+            family: None
         },
         parse_font("Open Sans 0")
     );
@@ -217,7 +254,8 @@ fn parse_font_string() {
     assert_eq!(
         Font {
             style: FontStyle::Default,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         },
         parse_font(&format!("{non_default_size}"))
     );
@@ -231,7 +269,8 @@ fn serialize_font_to_string() {
     assert_eq!(
         serialize_font(&Font {
             style: FontStyle::Bold,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         }),
         format!("{DEFAULT_FONT_NAME} {non_default_size}")
     );
@@ -240,7 +279,8 @@ fn serialize_font_to_string() {
     assert_eq!(
         serialize_font(&Font {
             style: FontStyle::Default,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         }),
         format!("{DEFAULT_FONT_NAME} {non_default_size}")
     );
@@ -249,7 +289,8 @@ fn serialize_font_to_string() {
     assert_eq!(
         serialize_font(&Font {
             style: FontStyle::Light,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         }),
         format!("{DEFAULT_FONT_NAME} {non_default_size}")
     );
@@ -258,7 +299,8 @@ fn serialize_font_to_string() {
     assert_eq!(
         serialize_font(&Font {
             style: FontStyle::Semibold,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         }),
         format!("{DEFAULT_FONT_NAME} {non_default_size}")
     );
@@ -267,7 +309,8 @@ fn serialize_font_to_string() {
     assert_eq!(
         serialize_font(&Font {
             style: FontStyle::Monospace,
-            size: non_default_size
+            size: non_default_size,
+            family: None
         }),
         format!("{MONOSPACE_FONT_NAME} {non_default_size}")
     );
@@ -280,6 +323,43 @@ fn serialize_font_to_string() {
     );
 }
 
+#[tokio::test]
+async fn import_missing_file() {
+    const INPUT_FILE: &str = "test_data/no-such-file";
+
+    let result = try_import_indicator_stickynotes(INPUT_FILE).await;
+    assert!(matches!(
+        result,
+        Err(IndicatorStickyNotesError::FileMissing(path)) if path == INPUT_FILE
+    ));
+}
+
+#[test]
+fn parse_and_serialize_font_family() {
+    // custom family is preserved
+    let font = parse_font("Fira Sans 14");
+    assert_eq!(
+        font,
+        Font {
+            style: FontStyle::Default,
+            size: 14,
+            family: Some("Fira Sans".to_string())
+        }
+    );
+    assert_eq!(serialize_font(&font), "Fira Sans 14");
+
+    // well-known families are mapped to the built-in fonts
+    assert_eq!(
+        parse_font(&format!("{MONOSPACE_FONT_NAME} 10")),
+        Font {
+            style: FontStyle::Monospace,
+            size: 10,
+            family: None
+        }
+    );
+    assert_eq!(parse_font(&format!("{DEFAULT_FONT_NAME} 10")).family, None);
+}
+
 #[test]
 fn import_end_export() {
     const INPUT_FILE: &str = "test_data/indicator-stickynotes";
@@ -311,3 +391,27 @@ fn import_end_export() {
     let parsed_again = NotesDatabase::try_import(&export).expect("import from export must succeed");
     assert_eq!(parsed, parsed_again);
 }
+
+#[test]
+fn parse_datetime_variants() {
+    let expected = Local
+        .with_ymd_and_hms(2024, 5, 17, 10, 20, 30)
+        .single()
+        .expect("local time must be valid");
+
+    assert_eq!(parse_datetime("2024-05-17T10:20:30"), Ok(expected));
+    assert_eq!(parse_datetime("2024-05-17 10:20:30"), Ok(expected));
+    let fractional = parse_datetime("2024-05-17T10:20:30.250").expect("fractional seconds");
+    assert_eq!(fractional.timestamp(), expected.timestamp());
+    assert_eq!(fractional.timestamp_subsec_millis(), 250);
+
+    let utc = parse_datetime("2024-05-17T10:20:30Z").expect("UTC time");
+    assert_eq!(utc.to_utc().to_rfc3339(), "2024-05-17T10:20:30+00:00");
+    let utc_fractional = parse_datetime("2024-05-17T10:20:30.5Z").expect("fractional UTC time");
+    assert_eq!(utc_fractional.timestamp(), utc.timestamp());
+    let offset = parse_datetime("2024-05-17T12:20:30+02:00").expect("time with offset");
+    assert_eq!(offset, utc);
+
+    assert!(parse_datetime("17.05.2024 10:20").is_err());
+    assert!(parse_datetime("").is_err());
+}
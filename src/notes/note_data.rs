@@ -1,59 +1,182 @@
 use super::{
-    DEF_NOTE_HEIGHT, DEF_NOTE_WIDTH, EMPTY_TITLE, MAX_TITLE_CHARS, NO_TITLE,
-    indicator_stickynotes as import,
+    DEF_NOTE_HEIGHT, DEF_NOTE_OPACITY, DEF_NOTE_WIDTH, ELLIPSIS, EMPTY_TITLE, MAX_TITLE_CHARS,
+    NO_TITLE, import_keep, indicator_stickynotes as import,
 };
 use chrono::{DateTime, Local, Utc};
+use std::collections::VecDeque;
 use uuid::Uuid;
 
+/// The note as it is saved.
+/// Every field must have a serde default so the notes saved by any earlier version are still readable,
+/// the fields added later are to be defaulted to their "not set" value.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default, PartialEq)]
 pub struct NoteData {
+    #[serde(default)]
     content: String,
+    // the Unix epoch if missing, so any known modification wins on merge
+    #[serde(default)]
     modified: DateTime<Utc>,
+    // is missing in notes saved by earlier versions, see get_created()
+    #[serde(default)]
+    created: Option<DateTime<Utc>>,
+    // when to remind about the note, is missing in notes saved by earlier versions
+    #[serde(default)]
+    reminder: Option<DateTime<Utc>>,
+    // file paths or URIs attached to the note, is missing in notes saved by earlier versions
+    #[serde(default)]
+    attachments: Vec<String>,
+    // overrides the toolbar icon size configured for all notes, is missing in notes saved by earlier versions
+    #[serde(default)]
+    icon_size: Option<u16>,
+    // dims the note regardless of its style, from 0.0 (transparent) to 1.0 (opaque)
+    #[serde(default = "default_opacity")]
+    opacity: f32,
+    // when the note window has gained focus recently, restores the stacking order of windows
+    #[serde(default)]
+    last_focused: Option<DateTime<Utc>>,
+    // nil if missing, the collection replaces it with the default style on read
+    #[serde(default)]
     style_id: Uuid,
+    #[serde(default)]
     position: (usize, usize),
+    #[serde(default = "default_size")]
     size: (usize, usize),
+    #[serde(default)]
     is_locked: bool,
+    // the locked note lets mouse clicks pass to windows below
+    #[serde(default)]
+    click_through: bool,
+    // the note window stays above other windows, e.g. the pinned note of Google Keep
+    #[serde(default)]
+    always_on_top: bool,
+    #[serde(default = "default_visibility")]
     is_visible: bool,
     #[serde(skip)]
     is_dirty: bool,
+    // the note is focused since it is stored, it is to be stored but isn't reported as unsaved
+    #[serde(skip)]
+    is_focus_changed: bool,
+    // counts the changes, so the collection knows if the note handed out to be changed is changed
+    #[serde(skip)]
+    revision: u64,
+    // previous contents to undo editing, the most recent is the last one
+    #[serde(skip)]
+    history: VecDeque<String>,
 }
 
 impl NoteData {
     #[must_use]
     pub fn new(style: Uuid) -> Self {
+        let now = Utc::now();
         Self {
             content: String::new(),
-            modified: Utc::now(),
+            modified: now,
+            created: Some(now),
+            reminder: None,
+            attachments: Vec::new(),
+            icon_size: None,
+            opacity: DEF_NOTE_OPACITY,
+            last_focused: None,
             position: (0, 0),
             size: (DEF_NOTE_WIDTH, DEF_NOTE_HEIGHT),
             style_id: style,
             is_locked: false,
+            click_through: false,
+            always_on_top: false,
             is_visible: true,
             is_dirty: false,
+            is_focus_changed: false,
+            revision: 0,
+            history: VecDeque::new(),
         }
     }
 
+    /// Convert the imported note, the missing parts of its position and size are replaced
+    /// with 0 and the `default_size` respectively
     #[must_use]
-    pub fn new_from_import(src: import::Note, is_visible: bool) -> Self {
-        let position: (usize, usize) = match src.properties.position.get(0..2) {
-            Some([first, second]) => (*first, *second),
-            Some([first]) => (*first, 0),
-            _ => (0, 0),
+    pub fn new_from_import(
+        src: import::Note,
+        is_visible: bool,
+        default_size: (usize, usize),
+    ) -> Self {
+        if src.properties.position.len() != 2 {
+            tracing::warn!(
+                "imported note {} has malformed position {:?}",
+                src.uuid,
+                src.properties.position
+            );
+        }
+        let position: (usize, usize) = match src.properties.position.as_slice() {
+            [first, second, ..] => (*first, *second),
+            [first] => (*first, 0),
+            [] => (0, 0),
         };
-        let size = match src.properties.size.get(0..2) {
-            Some([first, second]) => (*first, *second),
-            Some([first]) => (*first, 1),
-            _ => (1, 1),
+        if src.properties.size.len() != 2 {
+            tracing::warn!(
+                "imported note {} has malformed size {:?}, default is {default_size:?}",
+                src.uuid,
+                src.properties.size
+            );
+        }
+        let size = match src.properties.size.as_slice() {
+            [first, second, ..] => (*first, *second),
+            [first] => (*first, default_size.1),
+            [] => default_size,
         };
         Self {
             content: src.body,
             modified: src.last_modified.into(),
+            // indicator-stickynotes does not track creation time
+            created: Some(src.last_modified.into()),
+            // indicator-stickynotes has neither reminders nor attachments
+            reminder: None,
+            attachments: Vec::new(),
+            icon_size: None,
+            opacity: DEF_NOTE_OPACITY,
+            last_focused: None,
             style_id: src.cat,
             position,
             size,
             is_locked: src.properties.locked,
+            click_through: false,
+            always_on_top: false,
             is_visible,
             is_dirty: false,
+            is_focus_changed: false,
+            revision: 0,
+            history: VecDeque::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn new_from_keep(
+        src: &import_keep::Note,
+        style_id: Uuid,
+        position: (usize, usize),
+    ) -> Self {
+        let modified = src.modified().unwrap_or_else(Utc::now);
+        Self {
+            content: src.content(),
+            modified,
+            created: Some(src.created().unwrap_or(modified)),
+            reminder: None,
+            attachments: Vec::new(),
+            icon_size: None,
+            opacity: DEF_NOTE_OPACITY,
+            last_focused: None,
+            style_id,
+            position,
+            size: (DEF_NOTE_WIDTH, DEF_NOTE_HEIGHT),
+            is_locked: false,
+            click_through: false,
+            // pinned notes stay above other windows, archived ones are kept hidden
+            always_on_top: src.is_pinned,
+            is_visible: !src.is_archived,
+            is_dirty: false,
+            is_focus_changed: false,
+            revision: 0,
+            history: VecDeque::new(),
         }
     }
 
@@ -71,16 +194,86 @@ impl NoteData {
         }
     }
 
+    /// The excerpt of the content fitting into `max_lines` of `max_cols` characters,
+    /// the truncated lines and content are ended with ellipsis
+    #[must_use]
+    pub fn preview(&self, max_lines: usize, max_cols: usize) -> String {
+        if max_lines == 0 || max_cols == 0 {
+            return String::new();
+        }
+        let mut lines = self.content.trim_end().lines().map(str::trim_end);
+        let mut preview: Vec<String> = lines
+            .by_ref()
+            .take(max_lines)
+            .map(|line| ellipsize(line, max_cols, false))
+            .collect();
+        if lines.next().is_some()
+            && let Some(last) = preview.last_mut()
+            && !last.ends_with(ELLIPSIS)
+        {
+            *last = ellipsize(last, max_cols, true);
+        }
+        preview.join("\n")
+    }
+
     #[must_use]
     pub fn get_content(&self) -> &str {
         self.content.as_str()
     }
 
+    /// The content is blank, attachments and reminder are not taken into account
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.content.trim().is_empty()
+    }
+
+    /// The note is blank since it was created, so nothing is lost if it is dropped
+    #[must_use]
+    pub fn is_abandoned(&self) -> bool {
+        self.is_empty()
+            && self.attachments.is_empty()
+            && self.reminder.is_none()
+            && self.created.is_none_or(|created| created == self.modified)
+    }
+
     pub fn set_content(&mut self, content: String) {
         tracing::debug!("(*) unsaved note: content changed");
         self.content = content;
         self.modified = Utc::now();
-        self.is_dirty = true;
+        self.mark_changed();
+    }
+
+    /// Change the content keeping the previous one to undo, the history has at most `limit` entries
+    pub fn set_content_with_history(&mut self, content: String, limit: usize) {
+        if content == self.content {
+            return;
+        }
+        if limit > 0 {
+            while self.history.len() >= limit {
+                self.history.pop_front();
+            }
+            self.history.push_back(std::mem::take(&mut self.content));
+        }
+        self.set_content(content);
+    }
+
+    /// Restore the content preceding the recent change if any, returns true if restored
+    pub fn undo_content(&mut self) -> bool {
+        if let Some(content) = self.history.pop_back() {
+            self.set_content(content);
+            true
+        } else {
+            false
+        }
+    }
+
+    #[must_use]
+    pub fn get_history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
     }
 
     #[must_use]
@@ -88,6 +281,11 @@ impl NoteData {
         self.modified.into()
     }
 
+    #[must_use]
+    pub fn get_created(&self) -> DateTime<Local> {
+        self.created.unwrap_or(self.modified).into()
+    }
+
     #[must_use]
     pub fn left(&self) -> usize {
         self.position.0
@@ -120,7 +318,7 @@ impl NoteData {
                 tracing::debug!("(*) unsaved note: position changed");
             }
             self.position = (left, top);
-            self.is_dirty = true;
+            self.mark_changed();
         }
     }
 
@@ -131,7 +329,7 @@ impl NoteData {
                 tracing::debug!("(*) unsaved note: size changed");
             }
             self.size = (width, height);
-            self.is_dirty = true;
+            self.mark_changed();
         }
     }
 
@@ -147,10 +345,36 @@ impl NoteData {
                 tracing::debug!("(*) unsaved note: lock changed");
             }
             self.is_locked = on;
-            self.is_dirty = true;
+            // unlocking the note makes it clickable again
+            if !on {
+                self.click_through = false;
+            }
+            self.mark_changed();
+        }
+    }
+
+    /// Test if the note lets mouse clicks through, only the locked note can do it
+    #[must_use]
+    pub fn is_click_through(&self) -> bool {
+        self.is_locked && self.click_through
+    }
+
+    pub fn set_click_through(&mut self, on: bool) {
+        if self.click_through != on {
+            if !self.is_dirty {
+                // report only once
+                tracing::debug!("(*) unsaved note: click-through changed");
+            }
+            self.click_through = on;
+            self.mark_changed();
         }
     }
 
+    #[must_use]
+    pub fn is_always_on_top(&self) -> bool {
+        self.always_on_top
+    }
+
     #[must_use]
     pub fn is_visible(&self) -> bool {
         self.is_visible
@@ -163,10 +387,117 @@ impl NoteData {
                 tracing::debug!("(*) unsaved note: visibility changed");
             }
             self.is_visible = on;
-            self.is_dirty = true;
+            self.mark_changed();
+        }
+    }
+
+    #[must_use]
+    pub fn get_reminder(&self) -> Option<DateTime<Local>> {
+        self.reminder.map(Into::into)
+    }
+
+    /// Test if the reminder is set and is due at the moment
+    #[must_use]
+    pub fn is_reminder_due(&self, now: DateTime<Utc>) -> bool {
+        self.reminder.is_some_and(|reminder| reminder <= now)
+    }
+
+    pub fn set_reminder(&mut self, reminder: DateTime<Utc>) {
+        if self.reminder != Some(reminder) {
+            if !self.is_dirty {
+                // report only once
+                tracing::debug!("(*) unsaved note: reminder changed");
+            }
+            self.reminder = Some(reminder);
+            self.mark_changed();
+        }
+    }
+
+    pub fn clear_reminder(&mut self) {
+        if self.reminder.is_some() {
+            if !self.is_dirty {
+                // report only once
+                tracing::debug!("(*) unsaved note: reminder cleared");
+            }
+            self.reminder = None;
+            self.mark_changed();
         }
     }
 
+    #[must_use]
+    pub fn get_attachments(&self) -> &[String] {
+        &self.attachments
+    }
+
+    /// Attach file path or URI to the note unless it is attached already
+    pub fn add_attachment(&mut self, attachment: String) {
+        if !attachment.is_empty() && !self.attachments.contains(&attachment) {
+            if !self.is_dirty {
+                // report only once
+                tracing::debug!("(*) unsaved note: attachment added");
+            }
+            self.attachments.push(attachment);
+            self.mark_changed();
+        }
+    }
+
+    pub fn remove_attachment(&mut self, index: usize) -> Option<String> {
+        if index < self.attachments.len() {
+            if !self.is_dirty {
+                // report only once
+                tracing::debug!("(*) unsaved note: attachment removed");
+            }
+            self.mark_changed();
+            Some(self.attachments.remove(index))
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub fn get_icon_size(&self) -> Option<u16> {
+        self.icon_size
+    }
+
+    pub fn set_icon_size(&mut self, icon_size: u16) {
+        if self.icon_size != Some(icon_size) {
+            if !self.is_dirty {
+                // report only once
+                tracing::debug!("(*) unsaved note: icon size changed");
+            }
+            self.icon_size = Some(icon_size);
+            self.mark_changed();
+        }
+    }
+
+    #[must_use]
+    pub fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if (self.opacity - opacity).abs() > f32::EPSILON {
+            if !self.is_dirty {
+                // report only once
+                tracing::debug!("(*) unsaved note: opacity changed");
+            }
+            self.opacity = opacity;
+            self.mark_changed();
+        }
+    }
+
+    #[must_use]
+    pub fn get_last_focused(&self) -> Option<DateTime<Utc>> {
+        self.last_focused
+    }
+
+    /// Focusing doesn't make the note unsaved, though the stacking order is stored by the next save
+    pub fn set_last_focused(&mut self, focused: DateTime<Utc>) {
+        self.last_focused = Some(focused);
+        self.is_focus_changed = true;
+    }
+
     pub fn set_style(&mut self, style_id: Uuid) {
         if self.style_id != style_id {
             if !self.is_dirty {
@@ -174,7 +505,7 @@ impl NoteData {
                 tracing::debug!("(*) unsaved note: style changed");
             }
             self.style_id = style_id;
-            self.is_dirty = true;
+            self.mark_changed();
         }
     }
 
@@ -183,7 +514,292 @@ impl NoteData {
         self.is_dirty
     }
 
+    /// The note differs from the stored one, it is either changed or just focused
+    #[must_use]
+    pub fn needs_storing(&self) -> bool {
+        self.is_dirty || self.is_focus_changed
+    }
+
+    /// The number of changes since the note is read, see `NotesCollection::take_change_log()`
+    #[must_use]
+    pub(super) fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Mark the note is saved, the undo history is dropped so it never outlives the session
     pub fn commit(&mut self) {
         self.is_dirty = false;
+        self.is_focus_changed = false;
+        self.clear_history();
+    }
+
+    fn mark_changed(&mut self) {
+        self.is_dirty = true;
+        self.revision += 1;
     }
 }
+
+fn default_size() -> (usize, usize) {
+    (DEF_NOTE_WIDTH, DEF_NOTE_HEIGHT)
+}
+
+fn default_visibility() -> bool {
+    true
+}
+
+fn default_opacity() -> f32 {
+    DEF_NOTE_OPACITY
+}
+
+// cuts the line to fit max_cols characters including the ellipsis if the line is too long or forced
+fn ellipsize(line: &str, max_cols: usize, force: bool) -> String {
+    let count = line.chars().count();
+    if count > max_cols || (force && count == max_cols) {
+        let mut cut: String = line.chars().take(max_cols - 1).collect();
+        cut.push(ELLIPSIS);
+        cut
+    } else if force {
+        format!("{line}{ELLIPSIS}")
+    } else {
+        line.to_string()
+    }
+}
+
+#[test]
+fn created_defaults_to_modified() {
+    let note = NoteData::new(Uuid::new_v4());
+    assert_eq!(note.get_created(), note.get_modified());
+
+    // notes saved before created was introduced
+    let mut json = serde_json::to_value(&note).expect("serialize note must succeed");
+    json.as_object_mut()
+        .expect("note must be an object")
+        .remove("created");
+    let legacy: NoteData = serde_json::from_value(json).expect("deserialize note must succeed");
+    assert_eq!(legacy.get_created(), legacy.get_modified());
+}
+
+#[test]
+fn preview_content() {
+    let mut note = NoteData::new(Uuid::new_v4());
+    note.set_content("first line\nsecond\nthird line   \n\n  \n".to_string());
+
+    // fits entirely, trailing whitespace is collapsed
+    assert_eq!(note.preview(5, 20), "first line\nsecond\nthird line");
+    // long lines are ellipsized
+    assert_eq!(note.preview(3, 6), "first…\nsecond\nthird…");
+    // the rest of lines is ellipsized
+    assert_eq!(note.preview(2, 20), "first line\nsecond…");
+    assert_eq!(note.preview(2, 6), "first…\nsecon…");
+    // nothing fits
+    assert_eq!(note.preview(0, 20), "");
+    assert_eq!(note.preview(2, 0), "");
+
+    note.set_content(String::new());
+    assert_eq!(note.preview(2, 20), "");
+}
+
+#[test]
+fn set_and_clear_reminder() {
+    let mut note = NoteData::new(Uuid::new_v4());
+    let now = Utc::now();
+    assert!(note.get_reminder().is_none());
+    assert!(!note.is_reminder_due(now));
+
+    note.set_reminder(now + chrono::Duration::minutes(1));
+    assert!(note.is_changed());
+    assert!(!note.is_reminder_due(now));
+    assert!(note.is_reminder_due(now + chrono::Duration::minutes(1)));
+
+    // the reminder is saved
+    let json = serde_json::to_string(&note).expect("serialize note must succeed");
+    let restored: NoteData = serde_json::from_str(&json).expect("deserialize note must succeed");
+    assert_eq!(restored.get_reminder(), note.get_reminder());
+
+    note.commit();
+    note.clear_reminder();
+    assert!(note.is_changed());
+    assert!(note.get_reminder().is_none());
+}
+
+#[test]
+fn add_and_remove_attachments() {
+    let mut note = NoteData::new(Uuid::new_v4());
+    note.add_attachment("/home/user/picture.png".to_string());
+    note.add_attachment("https://github.com/0xAAE/sticky-notes".to_string());
+    // neither empty nor duplicate attachments are added
+    note.add_attachment(String::new());
+    note.add_attachment("/home/user/picture.png".to_string());
+    assert_eq!(note.get_attachments().len(), 2);
+    assert!(note.is_changed());
+
+    // attachments are saved
+    let json = serde_json::to_string(&note).expect("serialize note must succeed");
+    let restored: NoteData = serde_json::from_str(&json).expect("deserialize note must succeed");
+    assert_eq!(restored.get_attachments(), note.get_attachments());
+
+    note.commit();
+    assert_eq!(
+        note.remove_attachment(0).as_deref(),
+        Some("/home/user/picture.png")
+    );
+    assert!(note.remove_attachment(1).is_none());
+    assert_eq!(
+        note.get_attachments(),
+        ["https://github.com/0xAAE/sticky-notes".to_string()]
+    );
+    assert!(note.is_changed());
+}
+
+#[test]
+fn icon_size_override() {
+    let mut note = NoteData::new(Uuid::new_v4());
+    assert!(note.get_icon_size().is_none());
+
+    note.set_icon_size(24);
+    assert!(note.is_changed());
+    assert_eq!(note.get_icon_size(), Some(24));
+
+    // the override is saved
+    let json = serde_json::to_string(&note).expect("serialize note must succeed");
+    let restored: NoteData = serde_json::from_str(&json).expect("deserialize note must succeed");
+    assert_eq!(restored.get_icon_size(), Some(24));
+
+    // the same size does not change the note
+    note.commit();
+    note.set_icon_size(24);
+    assert!(!note.is_changed());
+}
+
+#[test]
+fn last_focused_is_saved() {
+    let mut note = NoteData::new(Uuid::new_v4());
+    assert!(note.get_last_focused().is_none());
+
+    let now = Utc::now();
+    note.set_last_focused(now);
+    assert!(!note.is_changed());
+    assert!(note.needs_storing());
+    assert_eq!(note.get_last_focused(), Some(now));
+
+    let json = serde_json::to_string(&note).expect("serialize note must succeed");
+    let restored: NoteData = serde_json::from_str(&json).expect("deserialize note must succeed");
+    assert_eq!(restored.get_last_focused(), Some(now));
+}
+
+#[test]
+fn history_is_limited() {
+    const LIMIT: usize = 3;
+
+    let mut note = NoteData::new(Uuid::new_v4());
+    for index in 0..=LIMIT + 1 {
+        note.set_content_with_history(format!("version {index}"), LIMIT);
+    }
+    // the same content is not kept twice
+    note.set_content_with_history(format!("version {}", LIMIT + 1), LIMIT);
+    assert_eq!(note.get_history_len(), LIMIT);
+
+    // the oldest versions are evicted
+    assert!(note.undo_content());
+    assert_eq!(note.get_content(), "version 3");
+    assert!(note.undo_content());
+    assert!(note.undo_content());
+    assert_eq!(note.get_content(), "version 1");
+    assert!(!note.undo_content());
+    assert_eq!(note.get_content(), "version 1");
+
+    // the history is dropped on commit
+    note.set_content_with_history("changed".to_string(), LIMIT);
+    note.commit();
+    assert_eq!(note.get_history_len(), 0);
+
+    // no history at all
+    note.set_content_with_history("no history".to_string(), 0);
+    assert_eq!(note.get_history_len(), 0);
+}
+
+#[test]
+fn click_through_requires_lock() {
+    let mut note = NoteData::new(Uuid::new_v4());
+    note.set_click_through(true);
+    assert!(!note.is_click_through());
+
+    note.set_locking(true);
+    assert!(note.is_click_through());
+
+    let json = serde_json::to_string(&note).expect("serialize note must succeed");
+    let restored: NoteData = serde_json::from_str(&json).expect("deserialize note must succeed");
+    assert!(restored.is_click_through());
+
+    // unlocking turns click-through off
+    note.set_locking(false);
+    note.set_locking(true);
+    assert!(!note.is_click_through());
+}
+
+#[test]
+fn opacity_is_clamped() {
+    let mut note = NoteData::new(Uuid::new_v4());
+    assert!((note.get_opacity() - 1.0).abs() < f32::EPSILON);
+
+    note.set_opacity(1.5);
+    assert!(!note.is_changed());
+    note.set_opacity(-0.5);
+    assert!(note.get_opacity().abs() < f32::EPSILON);
+    note.set_opacity(0.5);
+    assert!((note.get_opacity() - 0.5).abs() < f32::EPSILON);
+
+    let json = serde_json::to_string(&note).expect("serialize note must succeed");
+    let restored: NoteData = serde_json::from_str(&json).expect("deserialize note must succeed");
+    assert!((restored.get_opacity() - 0.5).abs() < f32::EPSILON);
+}
+
+#[test]
+fn import_malformed_position_and_size() {
+    let src = |position: Vec<usize>, size: Vec<usize>| import::Note {
+        uuid: Uuid::new_v4(),
+        body: "imported".to_string(),
+        last_modified: Local::now(),
+        properties: import::NoteProperties {
+            position,
+            size,
+            locked: false,
+        },
+        cat: Uuid::new_v4(),
+    };
+    let default_size = (320, 240);
+
+    let note = NoteData::new_from_import(src(vec![10, 20], vec![300, 200]), true, default_size);
+    assert_eq!((note.left(), note.top()), (10, 20));
+    assert_eq!((note.width(), note.height()), (300, 200));
+
+    let note = NoteData::new_from_import(src(vec![10], vec![300]), true, default_size);
+    assert_eq!((note.left(), note.top()), (10, 0));
+    assert_eq!((note.width(), note.height()), (300, 240));
+
+    let note = NoteData::new_from_import(src(Vec::new(), Vec::new()), true, default_size);
+    assert_eq!((note.left(), note.top()), (0, 0));
+    assert_eq!((note.width(), note.height()), default_size);
+}
+
+#[test]
+fn empty_content() {
+    let mut note = NoteData::new(Uuid::new_v4());
+    assert!(note.is_empty());
+    assert!(note.is_abandoned());
+    note.set_content("\n  text \n".to_string());
+    assert!(!note.is_empty());
+
+    // the note was edited, so it is kept even being blank
+    let note: NoteData = serde_json::from_str(
+        r#"{"content":" \t\n  \n","created":"2024-03-07T09:00:00Z","modified":"2024-03-07T09:05:00Z"}"#,
+    )
+    .expect("note must be read");
+    assert!(note.is_empty());
+    assert!(!note.is_abandoned());
+
+    let mut note = NoteData::new(Uuid::new_v4());
+    note.add_attachment("/home/user/picture.png".to_string());
+    assert!(note.is_empty());
+    assert!(!note.is_abandoned());
+}
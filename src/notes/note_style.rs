@@ -25,11 +25,49 @@ impl std::fmt::Display for FontStyle {
     }
 }
 
-/// The set of font parameters to display a text
+/// The horizontal alignment of the note text, the start and the end follow the text direction
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum TextAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+/// The path of the image file, the file existence is tested once the path is set or read,
+/// so notes are drawn without querying the file system
 #[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(from = "String", into = "String")]
+struct BackgroundImage {
+    path: String,
+    is_found: bool,
+}
+
+impl From<String> for BackgroundImage {
+    fn from(path: String) -> Self {
+        let is_found = std::path::Path::new(&path).is_file();
+        if !is_found {
+            tracing::warn!("background image {path} is not found");
+        }
+        Self { path, is_found }
+    }
+}
+
+impl From<BackgroundImage> for String {
+    fn from(image: BackgroundImage) -> Self {
+        image.path
+    }
+}
+
+/// The set of font parameters to display a text, missing parameters are defaulted on read
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(default)]
 pub struct Font {
     pub style: FontStyle,
     pub size: u16,
+    /// The font family name, the built-in font of the style is used if not set
+    #[serde(default)]
+    pub family: Option<String>,
 }
 
 impl Default for Font {
@@ -37,20 +75,36 @@ impl Default for Font {
         Self {
             style: FontStyle::default(),
             size: DEF_NOTE_FONT_SIZE,
+            family: None,
         }
     }
 }
 
-/// The style to use when display a sticky note
+/// The style to use when display a sticky note, missing parameters are defaulted on read
 #[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(default)]
 pub struct NoteStyle {
     name: String,
     //#[serde(rename(deserialize = "font_name"), deserialize_with = "font_from_str")]
     font: Font,
     #[serde(deserialize_with = "color_from_str", serialize_with = "color_to_str")]
     bgcolor: Color,
+    /// The optional group to list the style in, styles without group are listed first
+    group: Option<String>,
+    text_align: TextAlign,
+    /// The path of svg or raster image displayed under the note text, e.g. lined paper
+    background_image: Option<BackgroundImage>,
+    /// The radius of note corners, the radius of the theme is used if not set
+    corner_radius: Option<u16>,
+    /// The width of note border, there is no border if zero
+    border_width: u16,
+    #[serde(deserialize_with = "color_from_str", serialize_with = "color_to_str")]
+    border_color: Color,
     #[serde(skip)]
     is_dirty: bool,
+    // counts the changes, so the collection knows if the style handed out to be changed is changed
+    #[serde(skip)]
+    revision: u64,
 }
 
 // fn font_from_str<'de, D>(deserializer: D) -> Result<Font, D::Error>
@@ -87,7 +141,14 @@ impl Default for NoteStyle {
             name: DEF_NOTE_STYLE_NAME.to_string(),
             font: Font::default(),
             bgcolor: Color::WHITE,
+            group: None,
+            text_align: TextAlign::default(),
+            background_image: None,
+            corner_radius: None,
+            border_width: 0,
+            border_color: Color::BLACK,
             is_dirty: false,
+            revision: 0,
         }
     }
 }
@@ -99,7 +160,14 @@ impl NoteStyle {
             name,
             font,
             bgcolor,
+            group: None,
+            text_align: TextAlign::default(),
+            background_image: None,
+            corner_radius: None,
+            border_width: 0,
+            border_color: Color::BLACK,
             is_dirty: false,
+            revision: 0,
         }
     }
 
@@ -108,6 +176,20 @@ impl NoteStyle {
         &self.name
     }
 
+    #[must_use]
+    pub fn get_group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// The name to display in style selectors, "Group / Name" if the style is grouped
+    #[must_use]
+    pub fn get_display_name(&self) -> String {
+        match &self.group {
+            Some(group) => format!("{group} / {}", self.name),
+            None => self.name.clone(),
+        }
+    }
+
     #[must_use]
     pub fn get_font(&self) -> &Font {
         &self.font
@@ -118,11 +200,60 @@ impl NoteStyle {
         self.bgcolor
     }
 
+    #[must_use]
+    pub fn get_text_align(&self) -> TextAlign {
+        self.text_align
+    }
+
+    #[must_use]
+    pub fn get_background_image(&self) -> Option<&str> {
+        self.background_image
+            .as_ref()
+            .map(|image| image.path.as_str())
+    }
+
+    /// Get the background image path if the file exists, see `get_background_image()`
+    #[must_use]
+    pub fn get_found_background_image(&self) -> Option<&str> {
+        self.background_image
+            .as_ref()
+            .filter(|image| image.is_found)
+            .map(|image| image.path.as_str())
+    }
+
+    #[must_use]
+    pub fn get_corner_radius(&self) -> Option<u16> {
+        self.corner_radius
+    }
+
+    #[must_use]
+    pub fn get_border_width(&self) -> u16 {
+        self.border_width
+    }
+
+    #[must_use]
+    pub fn get_border_color(&self) -> Color {
+        self.border_color
+    }
+
     pub fn set_name(&mut self, name: &str) {
         if self.name != name {
             tracing::debug!("(*) unsaved style: renamed {} into {name}", self.name);
             self.name = name.to_string();
-            self.is_dirty = true;
+            self.mark_changed();
+        }
+    }
+
+    /// Set the group of the style, the blank group name removes style from any group
+    pub fn set_group(&mut self, group: Option<&str>) {
+        let group = group
+            .map(str::trim)
+            .filter(|group| !group.is_empty())
+            .map(ToString::to_string);
+        if self.group != group {
+            tracing::debug!("(*) unsaved style: group changed");
+            self.group = group;
+            self.mark_changed();
         }
     }
 
@@ -130,7 +261,7 @@ impl NoteStyle {
         if self.font != font {
             tracing::debug!("(*) unsaved style: font changed");
             self.font = font;
-            self.is_dirty = true;
+            self.mark_changed();
         }
     }
 
@@ -138,16 +269,162 @@ impl NoteStyle {
         if self.bgcolor != color {
             tracing::debug!("(*) unsaved style: color changed");
             self.bgcolor = color;
-            self.is_dirty = true;
+            self.mark_changed();
+        }
+    }
+
+    pub fn set_text_align(&mut self, text_align: TextAlign) {
+        if self.text_align != text_align {
+            tracing::debug!("(*) unsaved style: text alignment changed");
+            self.text_align = text_align;
+            self.mark_changed();
+        }
+    }
+
+    /// Set the background image path, the blank path removes the image
+    pub fn set_background_image(&mut self, path: &str) {
+        let path = Some(path.trim()).filter(|path| !path.is_empty());
+        if self.get_background_image() != path {
+            tracing::debug!("(*) unsaved style: background image changed");
+            self.background_image = path.map(|path| BackgroundImage::from(path.to_string()));
+            self.mark_changed();
+        }
+    }
+
+    /// Set the radius of note corners, `None` follows the theme
+    pub fn set_corner_radius(&mut self, corner_radius: Option<u16>) {
+        if self.corner_radius != corner_radius {
+            tracing::debug!("(*) unsaved style: corner radius changed");
+            self.corner_radius = corner_radius;
+            self.mark_changed();
+        }
+    }
+
+    /// Set the note border, zero width removes the border
+    pub fn set_border(&mut self, width: u16, color: Color) {
+        if self.border_width != width || self.border_color != color {
+            tracing::debug!("(*) unsaved style: border changed");
+            self.border_width = width;
+            self.border_color = color;
+            self.mark_changed();
         }
     }
 
+    /// Test if other style displays notes the same way regardless of saved state
+    #[must_use]
+    pub fn looks_like(&self, other: &NoteStyle) -> bool {
+        self.name == other.name
+            && self.font == other.font
+            && self.bgcolor == other.bgcolor
+            && self.text_align == other.text_align
+            && self.background_image == other.background_image
+            && self.corner_radius == other.corner_radius
+            && self.border_width == other.border_width
+            && self.border_color == other.border_color
+    }
+
     #[must_use]
     pub fn is_changed(&self) -> bool {
         self.is_dirty
     }
 
+    /// The number of changes since the style is read, see `NotesCollection::take_change_log()`
+    #[must_use]
+    pub(super) fn revision(&self) -> u64 {
+        self.revision
+    }
+
     pub fn commit(&mut self) {
         self.is_dirty = false;
     }
+
+    fn mark_changed(&mut self) {
+        self.is_dirty = true;
+        self.revision += 1;
+    }
+}
+
+#[test]
+fn legacy_style_is_read_with_defaults() {
+    // the styles saved by the previous versions, each one knows less parameters
+    let legacy = [
+        (
+            r#"{"name":"Old","bgcolor":[1.0,1.0,0.0]}"#,
+            TextAlign::Start,
+        ),
+        (
+            r#"{"name":"Old","font":{"style":"Bold","size":14},"bgcolor":[1.0,1.0,0.0]}"#,
+            TextAlign::Start,
+        ),
+        (
+            r#"{"name":"Old","bgcolor":[1.0,1.0,0.0],"group":"Work"}"#,
+            TextAlign::Start,
+        ),
+        (
+            r#"{"name":"Old","bgcolor":[1.0,1.0,0.0],"group":"Work","text_align":"End"}"#,
+            TextAlign::End,
+        ),
+    ];
+    for (json, text_align) in legacy {
+        let style: NoteStyle = serde_json::from_str(json).expect("style must be read");
+        assert_eq!(style.get_text_align(), text_align, "{json}");
+        assert_eq!(style.get_background_image(), None, "{json}");
+        assert_eq!(style.get_found_background_image(), None, "{json}");
+        assert_eq!(style.get_corner_radius(), None, "{json}");
+        assert_eq!(style.get_border_width(), 0, "{json}");
+        assert!(!style.is_changed(), "{json}");
+    }
+}
+
+#[test]
+fn text_align() {
+    let mut style = NoteStyle::default();
+    style.set_text_align(TextAlign::End);
+    assert!(style.is_changed());
+    let json = serde_json::to_string(&style).expect("style must be written");
+    let restored: NoteStyle = serde_json::from_str(&json).expect("style must be read");
+    assert_eq!(restored.get_text_align(), TextAlign::End);
+    assert!(!restored.looks_like(&NoteStyle::default()));
+}
+
+#[test]
+fn background_image() {
+    let mut style = NoteStyle::default();
+    style.set_background_image("  ");
+    assert!(!style.is_changed());
+    style.set_background_image(" /usr/share/backgrounds/paper.svg ");
+    assert!(style.is_changed());
+    let json = serde_json::to_string(&style).expect("style must be written");
+    let restored: NoteStyle = serde_json::from_str(&json).expect("style must be read");
+    assert_eq!(
+        restored.get_background_image(),
+        Some("/usr/share/backgrounds/paper.svg")
+    );
+    assert!(!restored.looks_like(&NoteStyle::default()));
+
+    // the missing file is not displayed, the existing one is found once it is set
+    let missing = std::env::temp_dir().join(format!("{}.svg", uuid::Uuid::new_v4()));
+    style.set_background_image(&missing.to_string_lossy());
+    assert!(style.get_background_image().is_some());
+    assert_eq!(style.get_found_background_image(), None);
+    std::fs::write(&missing, "<svg/>").expect("image must be written");
+    let found = missing.to_string_lossy().to_string();
+    let mut style = NoteStyle::default();
+    style.set_background_image(&found);
+    std::fs::remove_file(&missing).expect("image must be removed");
+    assert_eq!(style.get_found_background_image(), Some(found.as_str()));
+}
+
+#[test]
+fn frame() {
+    let mut style = NoteStyle::default();
+    style.set_corner_radius(Some(0));
+    style.set_border(2, Color::from_rgb(1.0, 0.0, 0.0));
+    assert!(style.is_changed());
+    let json = serde_json::to_string(&style).expect("style must be written");
+    let restored: NoteStyle = serde_json::from_str(&json).expect("style must be read");
+    assert_eq!(restored.get_corner_radius(), Some(0));
+    assert_eq!(restored.get_border_width(), 2);
+    assert_eq!(restored.get_border_color(), Color::from_rgb(1.0, 0.0, 0.0));
+    assert!(!restored.looks_like(&NoteStyle::default()));
 }
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Local};
+use uuid::Uuid;
+
+const DATE_PLACEHOLDER: &str = "{date}";
+const TIME_PLACEHOLDER: &str = "{time}";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M";
+
+/// The initial content and style of the similarly structured notes (e.g. meeting notes, daily logs)
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+pub struct NoteTemplate {
+    #[serde(default)]
+    name: String,
+    /// The content may contain {date} and {time} placeholders expanded when a note is created
+    #[serde(default)]
+    content: String,
+    // the default style is used if the style is missing
+    #[serde(default)]
+    style_id: Uuid,
+    #[serde(skip)]
+    is_dirty: bool,
+}
+
+impl NoteTemplate {
+    #[must_use]
+    pub fn new(name: String, content: String, style_id: Uuid) -> Self {
+        Self {
+            name,
+            content,
+            style_id,
+            is_dirty: true,
+        }
+    }
+
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn get_content(&self) -> &str {
+        &self.content
+    }
+
+    #[must_use]
+    pub fn style(&self) -> Uuid {
+        self.style_id
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        if self.name != name {
+            tracing::debug!("(*) unsaved template: renamed {} into {name}", self.name);
+            self.name = name.to_string();
+            self.is_dirty = true;
+        }
+    }
+
+    pub fn set_content(&mut self, content: &str) {
+        if self.content != content {
+            tracing::debug!("(*) unsaved template: content changed");
+            self.content = content.to_string();
+            self.is_dirty = true;
+        }
+    }
+
+    pub fn set_style(&mut self, style_id: Uuid) {
+        if self.style_id != style_id {
+            tracing::debug!("(*) unsaved template: style changed");
+            self.style_id = style_id;
+            self.is_dirty = true;
+        }
+    }
+
+    /// The content of the new note having placeholders replaced with the date and time
+    #[must_use]
+    pub fn expand(&self, now: &DateTime<Local>) -> String {
+        self.content
+            .replace(DATE_PLACEHOLDER, &now.format(DATE_FORMAT).to_string())
+            .replace(TIME_PLACEHOLDER, &now.format(TIME_FORMAT).to_string())
+    }
+
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.is_dirty
+    }
+
+    pub fn commit(&mut self) {
+        self.is_dirty = false;
+    }
+}
+
+#[cfg(test)]
+use chrono::TimeZone;
+
+#[test]
+fn expand_placeholders() {
+    let template = NoteTemplate::new(
+        "Daily log".to_string(),
+        "Log {date}\nstarted at {time}, {date} again\n{unknown}".to_string(),
+        Uuid::new_v4(),
+    );
+    let now = Local
+        .with_ymd_and_hms(2024, 3, 7, 9, 5, 0)
+        .single()
+        .expect("local time must be valid");
+    assert_eq!(
+        template.expand(&now),
+        "Log 2024-03-07\nstarted at 09:05, 2024-03-07 again\n{unknown}"
+    );
+}
@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: MPL-2.0
+use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
+
+use crate::notes::NotesCollection;
+
+/// The runtime state of notes published by *notes-service* to be tracked by *notes-applet*
+#[derive(Debug, Clone, Default, CosmicConfigEntry, Eq, PartialEq)]
+#[version = 1]
+pub struct State {
+    pub visible_notes: usize,
+    pub hidden_notes: usize,
+    pub locked_notes: usize,
+    pub unlocked_notes: usize,
+    /// The locked notes passing clicks through, they can't be unlocked from their toolbar
+    pub click_through_notes: usize,
+    pub deleted_notes: usize,
+    pub unsaved: bool,
+    /// The names of note templates in the order they are referred by index
+    pub templates: Vec<String>,
+    /// The error of the recent failed saving of notes, empty if notes were saved successfully
+    pub save_error: String,
+    /// The reason the configured export file is unwritable, empty if it is fine or not checked
+    pub export_warning: String,
+}
+
+impl From<&NotesCollection> for State {
+    fn from(notes: &NotesCollection) -> Self {
+        Self {
+            visible_notes: notes.iter_visible_notes().count(),
+            hidden_notes: notes.iter_hidden_notes().count(),
+            locked_notes: notes
+                .iter_notes()
+                .filter(|(_, note)| note.is_locked())
+                .count(),
+            unlocked_notes: notes
+                .iter_notes()
+                .filter(|(_, note)| !note.is_locked())
+                .count(),
+            click_through_notes: notes
+                .iter_notes()
+                .filter(|(_, note)| note.is_click_through())
+                .count(),
+            deleted_notes: notes.iter_deleted_notes().count(),
+            unsaved: notes.is_unsaved(),
+            templates: notes
+                .templates()
+                .iter()
+                .map(|template| template.get_name().to_string())
+                .collect(),
+            save_error: String::new(),
+            export_warning: String::new(),
+        }
+    }
+}
+
+/// The same predicates as `NotesCollection` has, so the applet decides on menu items the same way
+impl State {
+    #[must_use]
+    pub fn has_visible_notes(&self) -> bool {
+        self.visible_notes > 0
+    }
+
+    #[must_use]
+    pub fn has_hidden_notes(&self) -> bool {
+        self.hidden_notes > 0
+    }
+
+    #[must_use]
+    pub fn has_deleted_notes(&self) -> bool {
+        self.deleted_notes > 0
+    }
+
+    /// There are live notes and all of them are locked
+    #[must_use]
+    pub fn all_locked(&self) -> bool {
+        self.locked_notes > 0 && self.unlocked_notes == 0
+    }
+
+    #[must_use]
+    pub fn any_unlocked(&self) -> bool {
+        self.unlocked_notes > 0
+    }
+
+    #[must_use]
+    pub fn any_locked(&self) -> bool {
+        self.locked_notes > 0
+    }
+
+    #[must_use]
+    pub fn any_click_through(&self) -> bool {
+        self.click_through_notes > 0
+    }
+
+    #[must_use]
+    pub fn is_unsaved(&self) -> bool {
+        self.unsaved
+    }
+
+    #[must_use]
+    pub fn save_failed(&self) -> Option<&str> {
+        (!self.save_error.is_empty()).then_some(self.save_error.as_str())
+    }
+
+    #[must_use]
+    pub fn export_unwritable(&self) -> Option<&str> {
+        (!self.export_warning.is_empty()).then_some(self.export_warning.as_str())
+    }
+}